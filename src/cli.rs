@@ -9,6 +9,22 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// Unlock once, run a single command (e.g. "list password"), and exit
+    #[arg(short = 'c', long = "command", value_name = "COMMAND")]
+    pub one_shot: Option<String>,
+
+    /// Use this config file instead of the default (also CRYPTOKEEPER_CONFIG)
+    #[arg(long = "config", global = true, value_name = "PATH")]
+    pub config_file: Option<std::path::PathBuf>,
+
+    /// Never touch the system clipboard; copy actions are refused
+    #[arg(long, global = true)]
+    pub no_clipboard: bool,
+
+    /// Print diagnostics on stderr (-vv for more detail); secrets are never shown
+    #[arg(short = 'v', long, action = clap::ArgAction::Count, global = true)]
+    pub verbose: u8,
 }
 
 #[derive(Subcommand)]
@@ -24,17 +40,25 @@ pub enum Commands {
         /// Filter by entry type (e.g. "password", "privatekey", "seedphrase")
         #[arg(name = "filter")]
         filter: Option<String>,
+
+        /// List archived entries instead of the ones in use
+        #[arg(long)]
+        archived: bool,
     },
 
     /// View entry details and optionally reveal the secret
     View {
-        /// Name or index number of the entry
+        /// Name, index number, or public address of the entry
         name: String,
+
+        /// Print the secret instead of masking it
+        #[arg(long)]
+        show_secret: bool,
     },
 
     /// Edit an existing entry's fields
     Edit {
-        /// Name or index number of the entry
+        /// Name, index number, or public address of the entry
         name: String,
     },
 
@@ -48,32 +72,57 @@ pub enum Commands {
 
     /// Delete an entry (with confirmation)
     Delete {
-        /// Name or index number of the entry
+        /// Name, index number, or public address of the entry
         name: String,
     },
 
-    /// Copy a secret to the clipboard (auto-clears after 10 seconds)
+    /// Copy a secret to the clipboard (auto-clears after the configured timeout)
     Copy {
-        /// Name or index number of the entry
+        /// Name, index number, or public address of the entry
         name: String,
+
+        /// Write the secret to this open file descriptor (3 or higher) instead of
+        /// the clipboard, printing nothing; whoever reads the descriptor sees it
+        #[arg(long, value_name = "FD")]
+        print_fd: Option<i32>,
     },
 
     /// Search entries by name, network, or notes
     Search {
         /// Search query
         query: String,
+
+        /// Also search decrypted secrets and custom fields (unlocks and decrypts every entry)
+        #[arg(long)]
+        deep: bool,
     },
 
     /// Export vault as an encrypted backup (creates backup.ck in the specified directory)
     Export {
-        /// Directory path where backup.ck will be created
+        /// Directory path where backup.ck will be created (default: the configured export directory)
+        directory: Option<String>,
+
+        /// Print the encrypted backup as a pasteable text block instead of writing a file
+        #[arg(long, conflicts_with = "directory")]
+        armor: bool,
+    },
+
+    /// Export a single entry as an encrypted backup (creates <name>.ck in the specified directory)
+    ExportEntry {
+        /// Name, index number, or public address of the entry
+        name: String,
+        /// Directory path where the backup will be created
         directory: String,
     },
 
     /// Import entries from an encrypted backup
     Import {
-        /// Backup file path
+        /// Backup file path (a .ck file or a text file holding an armored backup)
         file: String,
+
+        /// Merge entries from another CryptoKeeper vault file instead of a backup
+        #[arg(long)]
+        from_vault: bool,
     },
 
     /// Change the master password
@@ -91,11 +140,81 @@ pub enum Commands {
         /// Set clipboard auto-clear timeout in seconds
         #[arg(long)]
         clipboard_timeout: Option<u64>,
+
+        /// Add a custom network, optionally with a derivation engine (e.g. Polygon:ethereum)
+        #[arg(long, value_name = "NAME[:ENGINE]")]
+        add_network: Option<String>,
+
+        /// Remove a custom network by name
+        #[arg(long, value_name = "NAME")]
+        remove_network: Option<String>,
+
+        /// Set the directory `export` uses when none is given (empty to unset)
+        #[arg(long, value_name = "DIR")]
+        export_dir: Option<String>,
+
+        /// Set the entry list columns, e.g. name,network,address (empty to reset)
+        #[arg(long, value_name = "COLUMNS")]
+        columns: Option<String>,
+    },
+
+    /// Show a summary of the vault contents
+    Stats {
+        /// Also show how long each step of the unlock took (for tuning the KDF cost)
+        #[arg(long)]
+        timings: bool,
     },
 
     /// Derive and save the public address for an entry from its private key or seed phrase
     Derive {
-        /// Name or index number of the entry
+        /// Name, index number, or public address of the entry
         name: String,
+
+        /// Print the address on every supported network instead of saving one
+        #[arg(long, conflicts_with_all = ["account", "index"])]
+        all: bool,
+
+        /// Print a seed phrase's address for this account index instead of saving one
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(..0x8000_0000))]
+        account: Option<u32>,
+
+        /// Print a seed phrase's address at this address index (the last level of the path) instead of saving one
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(..0x8000_0000))]
+        index: Option<u32>,
+    },
+
+    /// Derive and save missing public addresses for all entries
+    Rederive {
+        /// Only entries on this network (e.g. btc, Ethereum, Polygon)
+        #[arg(long)]
+        network: Option<String>,
     },
+
+    /// Print entry names for shell completion (used by completion scripts)
+    #[command(name = "__complete_names", hide = true)]
+    CompleteNames,
+}
+
+impl Commands {
+    /// Whether the command may create or rewrite files in the vault directory.
+    pub fn writes_vault(&self) -> bool {
+        matches!(
+            self,
+            Commands::Init
+                | Commands::Add
+                | Commands::Edit { .. }
+                | Commands::Rename { .. }
+                | Commands::Delete { .. }
+                | Commands::Import { .. }
+                | Commands::Passwd
+                | Commands::Recover
+                | Commands::Derive {
+                    all: false,
+                    account: None,
+                    index: None,
+                    ..
+                }
+                | Commands::Rederive { .. }
+        )
+    }
 }
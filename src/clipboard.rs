@@ -3,8 +3,34 @@ use std::time::Duration;
 
 use arboard::Clipboard;
 
+use crate::config::Config;
 use crate::error::{CryptoKeeperError, Result};
 
+/// Set by `--no-clipboard`; may also be exported to keep every run off the clipboard.
+pub const NO_CLIPBOARD_ENV: &str = "CRYPTOKEEPER_NO_CLIPBOARD";
+
+/// Whether copying is turned off, by `--no-clipboard`, the environment or the config.
+pub fn is_disabled(config: &Config) -> bool {
+    config.disable_clipboard || std::env::var_os(NO_CLIPBOARD_ENV).is_some_and(|v| v != "0")
+}
+
+/// Shown before copying in a remote session.
+pub const REMOTE_WARNING: &str =
+    "This is an SSH session, so the clipboard is the remote host's, not your local one.";
+
+/// Whether this process runs in an SSH session, where the system clipboard
+/// belongs to the remote host rather than the user's own machine.
+pub fn is_remote_session() -> bool {
+    ["SSH_CONNECTION", "SSH_TTY", "SSH_CLIENT"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some_and(|v| !v.is_empty()))
+}
+
+/// Whether copy actions should warn and ask first because the session is remote.
+pub fn warn_remote(config: &Config) -> bool {
+    config.warn_remote_clipboard && is_remote_session()
+}
+
 /// Copy text to clipboard and spawn a background thread to clear it after `clear_after` seconds.
 pub fn copy_and_clear(text: &str, clear_after_secs: u64) -> Result<()> {
     let mut clipboard =
@@ -18,10 +44,16 @@ pub fn copy_and_clear(text: &str, clear_after_secs: u64) -> Result<()> {
     let duration = Duration::from_secs(clear_after_secs);
     thread::spawn(move || {
         thread::sleep(duration);
-        if let Ok(mut cb) = Clipboard::new() {
-            let _ = cb.set_text(String::new());
-        }
+        let _ = clear();
     });
 
     Ok(())
 }
+
+/// Empty the clipboard now. Callers about to exit use this rather than rely
+/// on the thread from `copy_and_clear`, which dies with the process.
+pub fn clear() -> Result<()> {
+    Clipboard::new()
+        .and_then(|mut cb| cb.set_text(String::new()))
+        .map_err(|e| CryptoKeeperError::Clipboard(e.to_string()))
+}
@@ -4,16 +4,25 @@ use dialoguer::{Input, Select};
 use zeroize::Zeroizing;
 
 use crate::error::{CryptoKeeperError, Result};
-use crate::ui::borders::print_success;
+use crate::crypto::derive::{
+    check_private_key, derive_address, resolve_network, solana_keypair_from_json,
+    suggest_mnemonic_fixes,
+};
+use crate::logging;
+use crate::ui::borders::{print_success, print_warning};
+use crate::ui::screens::add_entry::network_options;
 use crate::ui::theme::heading;
-use crate::vault::model::{Entry, SecretType, VaultData};
+use crate::vault::model::{normalize_name, Entry, SecretType, VaultData};
 use crate::vault::storage;
 
+/// Secret type option that reads a Solana CLI keypair file.
+const KEYPAIR_FILE: &str = "Solana keypair file";
+
 pub fn run() -> Result<()> {
     let (mut vault, password) = storage::prompt_and_unlock()?;
     run_with_vault(&mut vault)?;
-    eprintln!("Saving vault...");
-    storage::save_vault(&vault, password.as_bytes())?;
+    logging::info!("Saving vault...");
+    super::save_vault(&vault, password.as_bytes())?;
     Ok(())
 }
 
@@ -29,7 +38,7 @@ pub fn run_with_vault(vault: &mut VaultData) -> Result<()> {
         .interact_text()
         .map_err(|e| CryptoKeeperError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
 
-    let name = name.trim().to_string();
+    let name = normalize_name(&name);
     if name.is_empty() {
         return Err(CryptoKeeperError::Cancelled);
     }
@@ -38,51 +47,33 @@ pub fn run_with_vault(vault: &mut VaultData) -> Result<()> {
         return Err(CryptoKeeperError::EntryAlreadyExists(name));
     }
 
-    // Secret type
-    let type_options = &["Private Key", "Seed Phrase", "Password", "Exit"];
+    // Secret type; a keypair file can only be read with Solana support
+    let mut type_options = vec!["Private Key", "Seed Phrase", "Password"];
+    if cfg!(feature = "derive-sol") {
+        type_options.push(KEYPAIR_FILE);
+    }
+    type_options.push("Exit");
     let type_idx = Select::new()
         .with_prompt("Secret type")
-        .items(type_options)
+        .items(&type_options)
         .default(0)
         .interact()
         .map_err(|e| CryptoKeeperError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
 
-    if type_idx == 3 {
-        return Err(CryptoKeeperError::Cancelled);
-    }
-
-    let secret_type = match type_idx {
-        0 => SecretType::PrivateKey,
-        1 => SecretType::SeedPhrase,
-        _ => SecretType::Password,
+    let from_keypair_file = type_options[type_idx] == KEYPAIR_FILE;
+    let secret_type = match type_options[type_idx] {
+        "Exit" => return Err(CryptoKeeperError::Cancelled),
+        "Seed Phrase" => SecretType::SeedPhrase,
+        "Password" => SecretType::Password,
+        _ => SecretType::PrivateKey,
     };
 
-    // Secret (hidden input)
-    let secret_label = match secret_type {
-        SecretType::Password => "Password",
-        _ => "Paste your secret",
-    };
-    let secret = Zeroizing::new(
-        rpassword::prompt_password(format!("{} (hidden): ", secret_label))
-            .map_err(CryptoKeeperError::Io)?,
-    );
-
-    if secret.is_empty() {
-        return Err(CryptoKeeperError::Cancelled);
-    }
-
-    let confirm_label = match secret_type {
-        SecretType::Password => "Confirm password",
-        _ => "Confirm secret",
+    // Secret (hidden input, or read from the keypair file without echoing it)
+    let secret = if from_keypair_file {
+        read_keypair_file()?
+    } else {
+        prompt_secret(&secret_type)?
     };
-    let confirm = Zeroizing::new(
-        rpassword::prompt_password(format!("{} (hidden): ", confirm_label))
-            .map_err(CryptoKeeperError::Io)?,
-    );
-
-    if *secret != *confirm {
-        return Err(CryptoKeeperError::PasswordMismatch);
-    }
 
     // Network & address (skip for Password type)
     let (network, public_address, username, url) = if secret_type == SecretType::Password {
@@ -107,30 +98,75 @@ pub fn run_with_vault(vault: &mut VaultData) -> Result<()> {
             if uname.is_empty() { None } else { Some(uname) },
             if url_input.is_empty() { None } else { Some(url_input) },
         )
+    } else if from_keypair_file {
+        let address = derive_address(&secret, &secret_type, "Solana")?;
+        print_warning(
+            "The keypair file still holds this key unencrypted. Keep it safe, or delete it once your vault is backed up.",
+        );
+        ("Solana".to_string(), address, None, None)
     } else {
         // PrivateKey / SeedPhrase: network + optional address
-        let network_options = &["Ethereum", "Bitcoin", "Solana", "Other", "Exit"];
+        let config = crate::config::load_config()?;
+        let mut network_options = network_options(&config);
+        let other_idx = network_options.len() - 1;
+        network_options.push("Exit".to_string());
         let net_idx = Select::new()
             .with_prompt("Network")
-            .items(network_options)
+            .items(&network_options)
             .default(0)
             .interact()
             .map_err(|e| CryptoKeeperError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
 
-        if net_idx == 4 {
+        if net_idx == other_idx + 1 {
             return Err(CryptoKeeperError::Cancelled);
         }
 
-        let network = if net_idx == 3 {
+        let network = if net_idx == other_idx {
             let custom: String = Input::new()
                 .with_prompt("Enter network name")
                 .interact_text()
                 .map_err(|e| CryptoKeeperError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
             custom.trim().to_string()
         } else {
-            network_options[net_idx].to_string()
+            network_options[net_idx].clone()
         };
 
+        if secret_type == SecretType::PrivateKey {
+            let resolved = resolve_network(&network, &config.custom_networks);
+            if let Err(e) = check_private_key(&secret, resolved) {
+                print_warning(&e.to_string());
+                let keep = dialoguer::Confirm::new()
+                    .with_prompt("Store it anyway?")
+                    .default(false)
+                    .interact()
+                    .map_err(|e| CryptoKeeperError::Io(std::io::Error::other(e)))?;
+                if !keep {
+                    return Err(CryptoKeeperError::Cancelled);
+                }
+            }
+        }
+
+        if secret_type == SecretType::SeedPhrase {
+            let fixes = suggest_mnemonic_fixes(&secret);
+            for (index, word) in &fixes {
+                print_warning(&format!(
+                    "Word {} is not a BIP39 word. Did you mean '{}'?",
+                    index + 1,
+                    word
+                ));
+            }
+            if !fixes.is_empty() {
+                let keep = dialoguer::Confirm::new()
+                    .with_prompt("Store it anyway?")
+                    .default(false)
+                    .interact()
+                    .map_err(|e| CryptoKeeperError::Io(std::io::Error::other(e)))?;
+                if !keep {
+                    return Err(CryptoKeeperError::Cancelled);
+                }
+            }
+        }
+
         let public_address = match secret_type {
             SecretType::PrivateKey => {
                 let addr: String = Input::new()
@@ -151,6 +187,21 @@ pub fn run_with_vault(vault: &mut VaultData) -> Result<()> {
         (network, public_address, None, None)
     };
 
+    if let Some(existing) = public_address.as_deref().and_then(|a| vault.find_by_address(a)) {
+        print_warning(&format!(
+            "This address is already stored as '{}'.",
+            existing.name
+        ));
+        let keep = dialoguer::Confirm::new()
+            .with_prompt("Store it anyway?")
+            .default(false)
+            .interact()
+            .map_err(|e| CryptoKeeperError::Io(std::io::Error::other(e)))?;
+        if !keep {
+            return Err(CryptoKeeperError::Cancelled);
+        }
+    }
+
     // Notes (optional)
     let notes: String = Input::new()
         .with_prompt("Notes (optional, press Enter to skip)")
@@ -158,6 +209,7 @@ pub fn run_with_vault(vault: &mut VaultData) -> Result<()> {
         .interact_text()
         .map_err(|e| CryptoKeeperError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
 
+    let address_is_manual = public_address.is_some() && !from_keypair_file;
     let now = Utc::now();
     let entry = Entry {
         name: name.clone(),
@@ -165,17 +217,25 @@ pub fn run_with_vault(vault: &mut VaultData) -> Result<()> {
         secret_type,
         network,
         public_address,
+        address_is_manual,
+        derivation_account: 0,
+        derivation_index: 0,
         username,
         url,
         notes: notes.trim().to_string(),
+        custom_fields: Vec::new(),
         created_at: now,
         updated_at: now,
         has_secondary_password: false,
         entry_key_wrapped: None,
         entry_key_nonce: None,
         entry_key_salt: None,
+        entry_key_kdf: None,
         encrypted_secret: None,
         encrypted_secret_nonce: None,
+        mask_metadata: false,
+        clipboard_timeout_override: None,
+        archived: false,
     };
 
     vault.entries.push(entry);
@@ -187,3 +247,57 @@ pub fn run_with_vault(vault: &mut VaultData) -> Result<()> {
 
     Ok(())
 }
+
+/// Ask for the secret twice with hidden input.
+fn prompt_secret(secret_type: &SecretType) -> Result<Zeroizing<String>> {
+    let secret_label = match secret_type {
+        SecretType::Password => "Password",
+        _ => "Paste your secret",
+    };
+    let secret = Zeroizing::new(
+        rpassword::prompt_password(format!("{} (hidden): ", secret_label))
+            .map_err(CryptoKeeperError::Io)?,
+    );
+
+    if secret.is_empty() {
+        return Err(CryptoKeeperError::Cancelled);
+    }
+
+    let confirm_label = match secret_type {
+        SecretType::Password => "Confirm password",
+        _ => "Confirm secret",
+    };
+    let confirm = Zeroizing::new(
+        rpassword::prompt_password(format!("{} (hidden): ", confirm_label))
+            .map_err(CryptoKeeperError::Io)?,
+    );
+
+    if *secret != *confirm {
+        return Err(CryptoKeeperError::PasswordMismatch);
+    }
+
+    Ok(secret)
+}
+
+/// Read a Solana CLI keypair file such as `~/.config/solana/id.json` and
+/// return its key as base58. The contents are never echoed.
+fn read_keypair_file() -> Result<Zeroizing<String>> {
+    let default = storage::home_dir().join(".config/solana/id.json");
+    let path: String = Input::new()
+        .with_prompt("Keypair file")
+        .default(default.display().to_string())
+        .interact_text()
+        .map_err(|e| CryptoKeeperError::Io(std::io::Error::other(e)))?;
+    let path = path.trim();
+    let path = match path.strip_prefix("~/") {
+        Some(rest) => storage::home_dir().join(rest),
+        None => std::path::PathBuf::from(path),
+    };
+    let contents = Zeroizing::new(std::fs::read_to_string(&path).map_err(|e| {
+        CryptoKeeperError::Io(std::io::Error::new(
+            e.kind(),
+            format!("Could not read keypair file {}: {}", path.display(), e),
+        ))
+    })?);
+    solana_keypair_from_json(&contents)
+}
@@ -0,0 +1,14 @@
+use crate::error::Result;
+use crate::vault::storage;
+
+/// Print entry names one per line for shell completion. Names come from the
+/// plaintext V2 metadata, so no password is needed; V1 vaults, a missing
+/// vault, or any read error produce no output. Masked entries are left out.
+pub fn run_names() -> Result<()> {
+    if let Ok(entries) = storage::read_vault_metadata() {
+        for entry in entries.iter().filter(|e| !e.mask_metadata) {
+            println!("{}", entry.name);
+        }
+    }
+    Ok(())
+}
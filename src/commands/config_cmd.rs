@@ -1,25 +1,105 @@
-use crate::config;
-use crate::error::Result;
+use crate::config::{
+    self,
+    model::{parse_list_columns, CustomNetwork},
+};
+use crate::error::{CryptoKeeperError, Result};
 use crate::ui::borders::print_success;
 
-pub fn run(show: bool, clipboard_timeout: Option<u64>) -> Result<()> {
+pub fn run(
+    show: bool,
+    clipboard_timeout: Option<u64>,
+    add_network: Option<&str>,
+    remove_network: Option<&str>,
+    export_dir: Option<&str>,
+    columns: Option<&str>,
+) -> Result<()> {
     let mut cfg = config::load_config()?;
 
-    if show || clipboard_timeout.is_none() {
+    let has_changes = clipboard_timeout.is_some()
+        || add_network.is_some()
+        || remove_network.is_some()
+        || export_dir.is_some()
+        || columns.is_some();
+
+    if show || !has_changes {
         println!();
         println!("  CryptoKeeper Configuration");
         println!("  ─────────────────────────");
         println!("  Vault path:         {}", cfg.vault_path);
         println!("  Clipboard timeout:  {} seconds", cfg.clipboard_timeout_secs);
+        if cfg.reveal_timeout_secs == 0 {
+            println!("  Reveal timeout:     Off");
+        } else {
+            println!("  Reveal timeout:     {} seconds", cfg.reveal_timeout_secs);
+        }
+        if cfg.blank_after_secs == 0 {
+            println!("  Blank screen after: Off");
+        } else {
+            println!("  Blank screen after: {} seconds", cfg.blank_after_secs);
+        }
+        println!("  Focus-loss blank:   {}", cfg.blank_on_focus_loss);
+        if cfg.fast_unlock_warn_ms == 0 {
+            println!("  Weak KDF warning:   Off");
+        } else {
+            println!("  Weak KDF warning:   under {} ms", cfg.fast_unlock_warn_ms);
+        }
+        println!("  Vault size limit:   {} MB", cfg.max_vault_size_mb);
+        println!("  Confirm key copy:   {}", cfg.confirm_secret_copy);
+        println!("  Clipboard disabled: {}", cfg.disable_clipboard);
+        println!("  SSH copy warning:   {}", cfg.warn_remote_clipboard);
+        println!("  Mask character:     {}", cfg.mask_char);
+        println!("  Reveal last chars:  {}", cfg.reveal_last_chars);
+        println!("  Network glyphs:     {}", cfg.network_glyphs);
+        println!("  Default type:       {}", cfg.default_secret_type);
+        println!("  Default network:    {}", cfg.default_network);
+        println!("  Lock memory:        {}", cfg.lock_memory);
+        println!(
+            "  Secondary KDF:      {} MB, {} iterations",
+            cfg.entry_key_kdf.m_cost / 1024,
+            cfg.entry_key_kdf.t_cost
+        );
+        println!(
+            "  Export directory:   {}",
+            cfg.default_export_dir.as_deref().unwrap_or("Not set")
+        );
+        println!(
+            "  List columns:       {}",
+            cfg.list_columns.as_ref().map_or("Default".to_string(), |columns| columns
+                .iter()
+                .map(|c| c.id())
+                .collect::<Vec<_>>()
+                .join(", "))
+        );
+        println!(
+            "  Password policy:    at least {} characters{}",
+            cfg.password_policy.min_length,
+            if cfg.password_policy.require_mix {
+                ", mixed character types"
+            } else {
+                ""
+            }
+        );
         println!("  First run complete: {}", cfg.first_run_complete);
         println!(
-            "  Recovery question:  {}",
+            "  Recovery question:  {}{}",
             if cfg.recovery.is_some() {
                 "Configured"
             } else {
                 "Not set"
+            },
+            if cfg.recovery_in_separate_file {
+                " (kept in recovery.json)"
+            } else {
+                ""
             }
         );
+        for network in &cfg.custom_networks {
+            println!(
+                "  Custom network:     {} ({})",
+                network.name,
+                network.engine.map_or("no derivation", |e| e.network())
+            );
+        }
         println!();
         return Ok(());
     }
@@ -30,5 +110,58 @@ pub fn run(show: bool, clipboard_timeout: Option<u64>) -> Result<()> {
         print_success(&format!("Clipboard timeout set to {} seconds.", timeout));
     }
 
+    if let Some(dir) = export_dir {
+        let dir = dir.trim();
+        if dir.is_empty() {
+            cfg.default_export_dir = None;
+            config::save_config(&cfg)?;
+            print_success("Default export directory cleared.");
+        } else {
+            cfg.default_export_dir = Some(dir.to_string());
+            config::save_config(&cfg)?;
+            print_success(&format!("Default export directory set to {}.", dir));
+        }
+    }
+
+    if let Some(spec) = columns {
+        cfg.list_columns = parse_list_columns(spec)?;
+        config::save_config(&cfg)?;
+        if cfg.list_columns.is_some() {
+            print_success("List columns updated.");
+        } else {
+            print_success("List columns reset to the default.");
+        }
+    }
+
+    if let Some(spec) = add_network {
+        let (name, engine) = match spec.split_once(':') {
+            Some((name, engine)) => (name.trim(), Some(engine.parse()?)),
+            None => (spec.trim(), None),
+        };
+        if name.is_empty() {
+            return Err(CryptoKeeperError::ConfigError("Network name cannot be empty.".into()));
+        }
+        cfg.custom_networks.retain(|n| !n.name.eq_ignore_ascii_case(name));
+        cfg.custom_networks.push(CustomNetwork {
+            name: name.to_string(),
+            engine,
+        });
+        config::save_config(&cfg)?;
+        print_success(&format!("Custom network '{}' added.", name));
+    }
+
+    if let Some(name) = remove_network {
+        let before = cfg.custom_networks.len();
+        cfg.custom_networks.retain(|n| !n.name.eq_ignore_ascii_case(name.trim()));
+        if cfg.custom_networks.len() == before {
+            return Err(CryptoKeeperError::ConfigError(format!(
+                "No custom network named '{}'.",
+                name
+            )));
+        }
+        config::save_config(&cfg)?;
+        print_success(&format!("Custom network '{}' removed.", name));
+    }
+
     Ok(())
 }
@@ -1,40 +1,143 @@
 use colored::Colorize;
+use zeroize::Zeroizing;
 
 use crate::clipboard;
+use crate::config;
+use crate::crypto::entry_key;
 use crate::error::{CryptoKeeperError, Result};
-use crate::ui::borders::print_success;
+use crate::ui::borders::{print_success, print_warning};
 use crate::vault::model::VaultData;
 use crate::vault::storage;
 
-const CLEAR_AFTER_SECS: u64 = 10;
-
 pub fn run(name: &str) -> Result<()> {
     let (vault, _password) = storage::prompt_and_unlock()?;
     run_with_vault(&vault, name, true)
 }
 
+/// Write the secret to file descriptor `fd` instead of the clipboard.
+pub fn run_print_fd(name: &str, fd: i32) -> Result<()> {
+    let (vault, _password) = storage::prompt_and_unlock()?;
+    print_fd_with_vault(&vault, name, fd)
+}
+
+/// Core `--print-fd` logic without prompt_and_unlock (for REPL mode). The
+/// secret goes only to `fd`: nothing reaches stdout, the terminal or the
+/// clipboard. Entries with a secondary password ask for it first.
+pub fn print_fd_with_vault(vault: &VaultData, name: &str, fd: i32) -> Result<()> {
+    let name = &super::resolve_id(vault, name)?;
+    let entry = vault
+        .find_entry_by_id(name)
+        .ok_or_else(|| CryptoKeeperError::EntryNotFound(name.to_string()))?;
+
+    let secret = if entry.has_secondary_password {
+        let view_password = super::prompt_secondary_password(entry.listed_name())?;
+        entry_key::decrypt_entry(entry, &view_password)?
+    } else {
+        Zeroizing::new(entry.secret.clone())
+    };
+    write_to_fd(fd, secret.as_bytes())
+}
+
+#[cfg(unix)]
+fn write_to_fd(fd: i32, data: &[u8]) -> Result<()> {
+    use std::io::Write;
+    use std::os::fd::FromRawFd;
+
+    if fd < 3 {
+        return Err(fd_error(fd, "is stdin, stdout or stderr; use 3 or higher"));
+    }
+    // SAFETY: fcntl and isatty only inspect the descriptor number.
+    if unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+        return Err(fd_error(fd, "is not open"));
+    }
+    if unsafe { libc::isatty(fd) } == 1 {
+        return Err(fd_error(fd, "is a terminal"));
+    }
+    // SAFETY: the descriptor is open and was handed to this process for the
+    // secret; the File closes it once written, so nothing else uses it.
+    let mut file = unsafe { std::fs::File::from_raw_fd(fd) };
+    file.write_all(data)?;
+    file.flush()?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn write_to_fd(_fd: i32, _data: &[u8]) -> Result<()> {
+    Err(CryptoKeeperError::Io(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "--print-fd is only supported on Unix",
+    )))
+}
+
+#[cfg(unix)]
+fn fd_error(fd: i32, reason: &str) -> CryptoKeeperError {
+    CryptoKeeperError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("File descriptor {fd} {reason}."),
+    ))
+}
+
 /// Core copy logic without prompt_and_unlock (for REPL mode).
 /// When `wait` is false (REPL mode), don't block waiting for clipboard clear.
 pub fn run_with_vault(vault: &VaultData, name: &str, wait: bool) -> Result<()> {
+    let name = &super::resolve_id(vault, name)?;
     let entry = vault
         .find_entry_by_id(name)
         .ok_or_else(|| CryptoKeeperError::EntryNotFound(name.to_string()))?;
 
-    clipboard::copy_and_clear(&entry.secret, CLEAR_AFTER_SECS)?;
+    let config = config::load_config().unwrap_or_default();
+    if clipboard::is_disabled(&config) {
+        return Err(CryptoKeeperError::ClipboardDisabled);
+    }
+    if clipboard::warn_remote(&config) {
+        print_warning(clipboard::REMOTE_WARNING);
+        let proceed = dialoguer::Confirm::new()
+            .with_prompt("Copy anyway?")
+            .default(false)
+            .interact()
+            .map_err(|e| CryptoKeeperError::Io(std::io::Error::other(e)))?;
+        if !proceed {
+            return Err(CryptoKeeperError::Cancelled);
+        }
+    }
+
+    let clear_after = config.clipboard_timeout_for(entry);
+    clipboard::copy_and_clear(&entry.secret, clear_after)?;
 
     print_success(&format!(
         "Secret for '{}' copied to clipboard.",
-        entry.name.cyan()
+        entry.listed_name().cyan()
     ));
     println!(
         "{}",
-        format!("  Clipboard will be cleared in {CLEAR_AFTER_SECS} seconds.").dimmed()
+        format!("  Clipboard will be cleared in {clear_after} seconds.").dimmed()
     );
 
     if wait {
-        std::thread::sleep(std::time::Duration::from_secs(CLEAR_AFTER_SECS));
+        count_down(clear_after);
+        clipboard::clear()?;
         println!("{}", "  Clipboard cleared.".dimmed());
     }
 
     Ok(())
 }
+
+/// Block for `secs` seconds, showing the time left on one line when stderr
+/// is a terminal.
+fn count_down(secs: u64) {
+    use std::io::{IsTerminal, Write};
+    use std::time::Duration;
+
+    if !std::io::stderr().is_terminal() {
+        std::thread::sleep(Duration::from_secs(secs));
+        return;
+    }
+    let mut stderr = std::io::stderr();
+    for left in (1..=secs).rev() {
+        let _ = write!(stderr, "\r  Clearing clipboard in {left:>3}s... ");
+        let _ = stderr.flush();
+        std::thread::sleep(Duration::from_secs(1));
+    }
+    let _ = write!(stderr, "\r{}\r", " ".repeat(40));
+    let _ = stderr.flush();
+}
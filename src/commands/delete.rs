@@ -2,6 +2,7 @@ use colored::Colorize;
 use dialoguer::Confirm;
 
 use crate::error::{CryptoKeeperError, Result};
+use crate::logging;
 use crate::ui::borders::print_success;
 use crate::vault::model::VaultData;
 use crate::vault::storage;
@@ -9,13 +10,14 @@ use crate::vault::storage;
 pub fn run(name: &str) -> Result<()> {
     let (mut vault, password) = storage::prompt_and_unlock()?;
     run_with_vault(&mut vault, name)?;
-    eprintln!("Saving vault...");
-    storage::save_vault(&vault, password.as_bytes())?;
+    logging::info!("Saving vault...");
+    super::save_vault(&vault, password.as_bytes())?;
     Ok(())
 }
 
 /// Core delete logic without prompt_and_unlock or save (for REPL mode).
 pub fn run_with_vault(vault: &mut VaultData, name: &str) -> Result<()> {
+    let name = &super::resolve_id(vault, name)?;
     let resolved_name = vault
         .resolve_entry_name(name)
         .ok_or_else(|| CryptoKeeperError::EntryNotFound(name.to_string()))?;
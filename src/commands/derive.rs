@@ -1,24 +1,44 @@
+use colored::Colorize;
+
+use crate::crypto::derive::{self, ChainAddress, DerivationPath};
 use crate::error::{CryptoKeeperError, Result};
-use crate::ui::borders::print_success;
+use crate::ui::borders::{print_box, print_success};
+use crate::vault::model::SecretType;
 use crate::vault::storage;
 
-pub fn run(name: &str) -> Result<()> {
+pub fn run(name: &str, all: bool, account: Option<u32>, index: Option<u32>) -> Result<()> {
+    if all {
+        return run_all(name);
+    }
+    if account.is_some() || index.is_some() {
+        return run_path(name, account.unwrap_or(0), index);
+    }
+
     let (mut vault, password) = storage::prompt_and_unlock()?;
 
+    let name = &super::resolve_id(&vault, name)?;
     let entry = vault
         .find_entry_mut_by_id(name)
         .ok_or_else(|| CryptoKeeperError::EntryNotFound(name.to_string()))?;
 
-    #[cfg(any(feature = "derive-eth", feature = "derive-btc", feature = "derive-sol"))]
+    #[cfg(any(feature = "derive-eth", feature = "derive-btc", feature = "derive-sol", feature = "derive-cosmos", feature = "derive-xrp"))]
     {
-        use crate::crypto::derive;
-
-        match derive::derive_address(&entry.secret, &entry.secret_type, &entry.network) {
+        let custom_networks = crate::config::load_config()?.custom_networks;
+        let network = derive::resolve_network(&entry.network, &custom_networks);
+        match derive::derive_entry_address(entry, network) {
             Ok(Some(address)) => {
                 println!("  Derived address: {}", address);
+                if let Some(manual) = entry.public_address.as_deref().filter(|_| entry.address_is_manual) {
+                    if !manual.eq_ignore_ascii_case(&address)
+                        && !crate::commands::edit::confirm_replace_manual_address(manual, &address)?
+                    {
+                        return Err(CryptoKeeperError::Cancelled);
+                    }
+                }
                 entry.public_address = Some(address);
+                entry.address_is_manual = false;
                 entry.updated_at = chrono::Utc::now();
-                storage::save_vault(&vault, password.as_bytes())?;
+                super::save_vault(&vault, password.as_bytes())?;
                 print_success("Address derived and saved.");
             }
             Ok(None) => {
@@ -33,7 +53,7 @@ pub fn run(name: &str) -> Result<()> {
         }
     }
 
-    #[cfg(not(any(feature = "derive-eth", feature = "derive-btc", feature = "derive-sol")))]
+    #[cfg(not(any(feature = "derive-eth", feature = "derive-btc", feature = "derive-sol", feature = "derive-cosmos", feature = "derive-xrp")))]
     {
         let _ = &password;
         let _ = entry.name.as_str();
@@ -43,3 +63,106 @@ pub fn run(name: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Print a seed phrase's address for one account and address index without
+/// saving it.
+fn run_path(name: &str, account: u32, index: Option<u32>) -> Result<()> {
+    let (vault, _password) = storage::prompt_and_unlock()?;
+
+    let name = &super::resolve_id(&vault, name)?;
+    let entry = vault
+        .find_entry_by_id(name)
+        .ok_or_else(|| CryptoKeeperError::EntryNotFound(name.to_string()))?;
+    if entry.secret_type != SecretType::SeedPhrase {
+        return Err(CryptoKeeperError::DerivationFailed(
+            "only seed phrase entries have account indexes".to_string(),
+        ));
+    }
+    if entry.has_secondary_password {
+        return Err(CryptoKeeperError::SecondaryPasswordRequired);
+    }
+
+    let custom_networks = crate::config::load_config()?.custom_networks;
+    let network = derive::resolve_network(&entry.network, &custom_networks);
+    let mut path = DerivationPath::for_network(network)
+        .ok_or_else(|| {
+            CryptoKeeperError::DerivationFailed(format!(
+                "seed derivation is not supported for {}",
+                entry.network
+            ))
+        })?
+        .account(account);
+    if let Some(index) = index {
+        path = path.index(index).ok_or_else(|| {
+            CryptoKeeperError::DerivationFailed(format!(
+                "{} paths have no address index; use --account",
+                entry.network
+            ))
+        })?;
+    }
+    let address =
+        derive::derive_address_on_path(&entry.secret, &entry.secret_type, network, Some(path))?
+            .unwrap_or_default();
+
+    let title = match index {
+        Some(index) => format!("{} account {}, address {}", entry.listed_name(), account, index),
+        None => format!("{} account {}", entry.listed_name(), account),
+    };
+    println!();
+    print_box(
+        Some(&title),
+        &[
+            format!("{}  {}", "Network".cyan(), entry.network),
+            format!("{}     {}", "Path".cyan(), path),
+            format!("{}  {}", "Address".cyan(), address),
+        ],
+    );
+    Ok(())
+}
+
+/// Print the entry's address on every built-in network without saving anything.
+fn run_all(name: &str) -> Result<()> {
+    let (vault, _password) = storage::prompt_and_unlock()?;
+
+    let name = &super::resolve_id(&vault, name)?;
+    let entry = vault
+        .find_entry_by_id(name)
+        .ok_or_else(|| CryptoKeeperError::EntryNotFound(name.to_string()))?;
+    if entry.secret_type == SecretType::Password {
+        return Err(CryptoKeeperError::DerivationFailed(
+            "password entries have no addresses".to_string(),
+        ));
+    }
+    if entry.has_secondary_password {
+        return Err(CryptoKeeperError::SecondaryPasswordRequired);
+    }
+
+    let report = derive::derive_all(&entry.secret, &entry.secret_type);
+    let width = report.iter().map(|(network, _)| network.len()).max().unwrap_or(0);
+    let mut lines = Vec::new();
+    let mut missing = Vec::new();
+    for (network, address) in report {
+        let value = match address {
+            ChainAddress::Derived(address) => address.normal(),
+            ChainAddress::Failed(reason) => reason.dimmed(),
+            ChainAddress::NotCompiled => {
+                missing.push(network);
+                continue;
+            }
+        };
+        let label = format!("{:<width$}", network, width = width);
+        lines.push(format!("{}  {}", label.cyan(), value));
+    }
+    if !missing.is_empty() {
+        lines.push(String::new());
+        lines.push(
+            format!("Not in this build: {}", missing.join(", "))
+                .dimmed()
+                .to_string(),
+        );
+    }
+
+    println!();
+    print_box(Some(&format!("Addresses for {}", entry.listed_name())), &lines);
+    Ok(())
+}
@@ -3,25 +3,32 @@ use colored::Colorize;
 use dialoguer::{Input, Select};
 use zeroize::Zeroizing;
 
+use crate::config;
+use crate::crypto::derive::refresh_address;
 use crate::error::{CryptoKeeperError, Result};
-use crate::ui::borders::print_success;
+use crate::logging;
+use crate::ui::borders::{print_success, print_warning};
 use crate::ui::theme::heading;
-use crate::vault::model::{SecretType, VaultData};
+use crate::vault::model::{names_match, normalize_name, SecretType, VaultData};
 use crate::vault::storage;
 
 pub fn run(name: &str) -> Result<()> {
     let (mut vault, password) = storage::prompt_and_unlock()?;
-    run_with_vault(&mut vault, name)?;
-    eprintln!("Saving vault...");
-    storage::save_vault(&vault, password.as_bytes())?;
+    if run_with_vault(&mut vault, name)? {
+        logging::info!("Saving vault...");
+        super::save_vault(&vault, password.as_bytes())?;
+    }
     Ok(())
 }
 
-/// Core edit logic without prompt_and_unlock or save (for REPL mode).
-pub fn run_with_vault(vault: &mut VaultData, name: &str) -> Result<()> {
+/// Core edit logic without prompt_and_unlock or save (for REPL mode). Returns
+/// false when nothing was changed.
+pub fn run_with_vault(vault: &mut VaultData, name: &str) -> Result<bool> {
+    let name = &super::resolve_id(vault, name)?;
     let entry = vault
         .find_entry_mut_by_id(name)
         .ok_or_else(|| CryptoKeeperError::EntryNotFound(name.to_string()))?;
+    super::unmask(entry, super::prompt_secondary_password)?;
 
     println!();
     println!("  {}", heading("Edit entry (press Enter to keep current value)"));
@@ -34,10 +41,10 @@ pub fn run_with_vault(vault: &mut VaultData, name: &str) -> Result<()> {
         .interact_text()
         .map_err(|e| CryptoKeeperError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
 
-    let new_name = new_name.trim().to_string();
+    let new_name = normalize_name(&new_name);
 
     // Check for duplicate if name changed
-    if new_name.to_lowercase() != entry.name.to_lowercase() && vault.has_entry(&new_name) {
+    if !names_match(&new_name, &entry.name) && vault.has_entry(&new_name) {
         return Err(CryptoKeeperError::EntryAlreadyExists(new_name));
     }
 
@@ -190,23 +197,83 @@ pub fn run_with_vault(vault: &mut VaultData, name: &str) -> Result<()> {
         .interact_text()
         .map_err(|e| CryptoKeeperError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
 
-    // Apply changes
-    entry.name = new_name.clone();
-    entry.secret_type = new_type;
+    // Apply changes to a copy so they can be reviewed before saving
+    let mut updated = entry.clone();
+    updated.name = new_name.clone();
+    updated.secret_type = new_type;
+    let secret_changed = new_secret.is_some();
     if let Some(secret) = new_secret {
-        entry.secret = secret.to_string();
+        updated.secret = secret.to_string();
+    }
+    updated.network = new_network;
+    if new_public_address != updated.public_address {
+        updated.address_is_manual = new_public_address.is_some();
+    }
+    updated.public_address = new_public_address;
+    updated.username = new_username;
+    updated.url = new_url;
+    updated.notes = new_notes.trim().to_string();
+
+    // A new key means the stored address no longer describes it
+    if secret_changed {
+        let custom_networks = config::load_config()?.custom_networks;
+        let was_manual = updated.address_is_manual;
+        match refresh_address(&mut updated, &custom_networks) {
+            Ok(Some(previous)) if was_manual => {
+                let derived = updated.public_address.clone().unwrap_or_default();
+                if !confirm_replace_manual_address(&previous, &derived)? {
+                    updated.public_address = Some(previous);
+                    updated.address_is_manual = true;
+                }
+            }
+            Ok(_) => {}
+            Err(e) => {
+                print_warning(&format!("Could not derive an address from the new secret: {}", e));
+            }
+        }
+    }
+
+    let changes = entry.diff(&updated);
+    if changes.is_empty() {
+        println!("  {}", "No changes.".dimmed());
+        return Ok(false);
     }
-    entry.network = new_network;
-    entry.public_address = new_public_address;
-    entry.username = new_username;
-    entry.url = new_url;
-    entry.notes = new_notes.trim().to_string();
-    entry.updated_at = Utc::now();
+
+    println!();
+    println!("  {}", heading("Changes"));
+    for change in &changes {
+        println!("    {}", change);
+    }
+    println!();
+    let confirmed = dialoguer::Confirm::new()
+        .with_prompt("Save these changes?")
+        .default(true)
+        .interact()
+        .map_err(|e| CryptoKeeperError::Io(std::io::Error::other(e)))?;
+    if !confirmed {
+        return Err(CryptoKeeperError::Cancelled);
+    }
+
+    updated.updated_at = Utc::now();
+    *entry = updated;
 
     print_success(&format!(
         "Entry '{}' updated successfully.",
         new_name.cyan()
     ));
 
-    Ok(())
+    Ok(true)
+}
+
+/// Ask before a derived address replaces one the user typed in.
+pub fn confirm_replace_manual_address(manual: &str, derived: &str) -> Result<bool> {
+    print_warning(&format!(
+        "The address {} was entered manually; the secret derives {}.",
+        manual, derived
+    ));
+    dialoguer::Confirm::new()
+        .with_prompt("Replace it with the derived address?")
+        .default(false)
+        .interact()
+        .map_err(|e| CryptoKeeperError::Io(std::io::Error::other(e)))
 }
@@ -1,11 +1,14 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use colored::Colorize;
 use zeroize::Zeroizing;
 
+use crate::config;
 use crate::error::{CryptoKeeperError, Result};
+use crate::logging;
 use crate::ui::borders::print_box;
 use crate::ui::theme::heading;
+use crate::vault::formats;
 use crate::vault::model::VaultData;
 use crate::vault::storage;
 
@@ -14,6 +17,21 @@ pub fn run(file: &str) -> Result<()> {
     run_with_vault(&vault, file)
 }
 
+/// Export to the configured `default_export_dir`.
+pub fn run_default() -> Result<()> {
+    let directory = default_directory()?;
+    run(&directory)
+}
+
+fn default_directory() -> Result<String> {
+    config::load_config()?.default_export_dir.ok_or_else(|| {
+        CryptoKeeperError::ConfigError(
+            "No export directory given. Pass one, or set a default with `cryptokeeper config --export-dir <DIR>`."
+                .into(),
+        )
+    })
+}
+
 /// Core export logic without prompt_and_unlock (for REPL mode).
 pub fn run_with_vault(vault: &VaultData, directory: &str) -> Result<()> {
     println!();
@@ -24,6 +42,116 @@ pub fn run_with_vault(vault: &VaultData, directory: &str) -> Result<()> {
     );
     println!();
 
+    let export_password = prompt_backup_password()?;
+    let file_path = prepare_directory(directory)?.join("backup.ck");
+
+    logging::info!("Encrypting backup...");
+    storage::write_backup(vault, export_password.as_bytes(), &file_path)?;
+
+    let lines = vec![
+        format!(
+            "{} Backup exported to '{}'",
+            "✓".green().bold(),
+            file_path.display().to_string().cyan()
+        ),
+        format!(
+            "{} entries exported.",
+            vault.entries.len().to_string().bold()
+        ),
+    ];
+    println!();
+    print_box(Some("Export Complete"), &lines);
+
+    Ok(())
+}
+
+pub fn run_armor() -> Result<()> {
+    let (vault, _password) = storage::prompt_and_unlock()?;
+    run_armor_with_vault(&vault)
+}
+
+/// Print the encrypted backup as armored text on stdout. Everything else goes
+/// to stderr so the output can be redirected straight into a file.
+pub fn run_armor_with_vault(vault: &VaultData) -> Result<()> {
+    eprintln!();
+    eprintln!("  {}", heading("Export encrypted backup as text"));
+    eprintln!(
+        "{}",
+        "  Choose a password for this backup (can differ from master password).".dimmed()
+    );
+    eprintln!();
+
+    let export_password = prompt_backup_password()?;
+
+    logging::info!("Encrypting backup...");
+    let data = storage::backup_bytes(vault, export_password.as_bytes())?;
+    eprintln!();
+    print!("{}", formats::armor(&data));
+    eprintln!();
+    eprintln!(
+        "{} entries exported. Restore with `cryptokeeper import <file>` after saving the block to a file.",
+        vault.entries.len()
+    );
+
+    Ok(())
+}
+
+pub fn run_entry(name: &str, directory: &str) -> Result<()> {
+    let (vault, _password) = storage::prompt_and_unlock()?;
+    run_entry_with_vault(&vault, name, directory)
+}
+
+/// Core single-entry export logic without prompt_and_unlock (for REPL mode).
+pub fn run_entry_with_vault(vault: &VaultData, name: &str, directory: &str) -> Result<()> {
+    let name = &super::resolve_id(vault, name)?;
+    let single = vault
+        .single_entry_vault(name)
+        .ok_or_else(|| CryptoKeeperError::EntryNotFound(name.to_string()))?;
+    super::unmask(&single.entries[0], super::prompt_secondary_password)?;
+    // The file name stays on disk, so a masked entry's is not taken from its name
+    let entry_name = single.entries[0].listed_name().to_string();
+
+    println!();
+    println!("  {}", heading(&format!("Export '{}'", entry_name)));
+    println!(
+        "{}",
+        "  Choose a password for this backup (can differ from master password).".dimmed()
+    );
+    println!();
+
+    let export_password = prompt_backup_password()?;
+    let file_path = prepare_directory(directory)?.join(entry_file_name(&entry_name));
+
+    logging::info!("Encrypting backup...");
+    storage::write_backup(&single, export_password.as_bytes(), &file_path)?;
+
+    let lines = vec![format!(
+        "{} '{}' exported to '{}'",
+        "✓".green().bold(),
+        entry_name,
+        file_path.display().to_string().cyan()
+    )];
+    println!();
+    print_box(Some("Export Complete"), &lines);
+
+    Ok(())
+}
+
+/// File name for a single-entry backup, derived from the entry name.
+pub fn entry_file_name(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let stem = stem.trim_matches('_');
+    if stem.is_empty() {
+        "entry.ck".to_string()
+    } else {
+        format!("{}.ck", stem)
+    }
+}
+
+fn prompt_backup_password() -> Result<Zeroizing<String>> {
     let export_password = Zeroizing::new(
         rpassword::prompt_password("Backup password: ").map_err(CryptoKeeperError::Io)?,
     );
@@ -40,38 +168,23 @@ pub fn run_with_vault(vault: &VaultData, directory: &str) -> Result<()> {
         return Err(CryptoKeeperError::PasswordMismatch);
     }
 
+    Ok(export_password)
+}
+
+/// Strip shell quotes from `directory`, create it (owner-only) if needed, and check it is a
+/// directory.
+pub fn prepare_directory(directory: &str) -> Result<PathBuf> {
     let directory = directory.trim_matches(|c| c == '\'' || c == '"');
     let dir_path = Path::new(directory);
-    
-    if !dir_path.exists() {
-        std::fs::create_dir_all(dir_path).map_err(CryptoKeeperError::Io)?;
-    }
-    
+
+    storage::ensure_private_dir(dir_path)?;
+
     if !dir_path.is_dir() {
         return Err(CryptoKeeperError::Io(std::io::Error::new(
             std::io::ErrorKind::InvalidInput,
             format!("'{}' is not a directory", directory)
         )));
     }
-    
-    let file_path = dir_path.join("backup.ck");
-    
-    eprintln!("Encrypting backup...");
-    storage::write_backup(&vault, export_password.as_bytes(), &file_path)?;
 
-    let lines = vec![
-        format!(
-            "{} Backup exported to '{}'",
-            "✓".green().bold(),
-            file_path.display().to_string().cyan()
-        ),
-        format!(
-            "{} entries exported.",
-            vault.entries.len().to_string().bold()
-        ),
-    ];
-    println!();
-    print_box(Some("Export Complete"), &lines);
-
-    Ok(())
+    Ok(dir_path.to_path_buf())
 }
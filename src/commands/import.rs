@@ -5,23 +5,25 @@ use dialoguer::Select;
 use zeroize::Zeroizing;
 
 use crate::error::{CryptoKeeperError, Result};
+use crate::logging;
 use crate::ui::borders::print_box;
 use crate::vault::model::VaultData;
 use crate::vault::storage;
 
-pub fn run(file: &str) -> Result<()> {
+pub fn run(file: &str, from_vault: bool) -> Result<()> {
     let (mut vault, password) = storage::prompt_and_unlock()?;
-    let modified = run_with_vault(&mut vault, file)?;
+    let modified = run_with_vault(&mut vault, file, from_vault)?;
     if modified {
-        eprintln!("Saving vault...");
-        storage::save_vault(&vault, password.as_bytes())?;
+        logging::info!("Saving vault...");
+        super::save_vault(&vault, password.as_bytes())?;
     }
     Ok(())
 }
 
 /// Core import logic without prompt_and_unlock or save (for REPL mode).
 /// Returns true if the vault was modified and needs saving.
-pub fn run_with_vault(vault: &mut VaultData, file: &str) -> Result<bool> {
+/// With `from_vault`, `file` may be another full vault as well as a backup.
+pub fn run_with_vault(vault: &mut VaultData, file: &str, from_vault: bool) -> Result<bool> {
     let file = file.trim_matches(|c| c == '\'' || c == '"');
     let path = Path::new(file);
     if !path.exists() {
@@ -31,13 +33,26 @@ pub fn run_with_vault(vault: &mut VaultData, file: &str) -> Result<bool> {
         )));
     }
 
+    if from_vault && is_current_vault(path) {
+        return Err(CryptoKeeperError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Cannot import the open vault into itself.",
+        )));
+    }
+
     println!();
+    let prompt = if from_vault { "Vault password: " } else { "Backup password: " };
     let backup_password = Zeroizing::new(
-        rpassword::prompt_password("Backup password: ").map_err(CryptoKeeperError::Io)?,
+        rpassword::prompt_password(prompt).map_err(CryptoKeeperError::Io)?,
     );
 
-    eprintln!("Decrypting backup...");
-    let backup = storage::read_backup(backup_password.as_bytes(), path)?;
+    let backup = if from_vault {
+        logging::info!("Decrypting vault...");
+        storage::read_any_encrypted(backup_password.as_bytes(), path)?
+    } else {
+        logging::info!("Decrypting backup...");
+        storage::read_backup(backup_password.as_bytes(), path)?
+    };
 
     let mut imported = 0;
     let mut skipped = 0;
@@ -108,3 +123,10 @@ pub fn run_with_vault(vault: &mut VaultData, file: &str) -> Result<bool> {
 
     Ok(imported > 0)
 }
+
+fn is_current_vault(path: &Path) -> bool {
+    match (path.canonicalize(), storage::vault_path().canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
@@ -2,6 +2,7 @@ use colored::Colorize;
 use zeroize::Zeroizing;
 
 use crate::error::{CryptoKeeperError, Result};
+use crate::logging;
 use crate::ui::borders::print_box;
 use crate::ui::theme::heading;
 use crate::vault::model::VaultData;
@@ -22,9 +23,7 @@ pub fn run() -> Result<()> {
             .map_err(CryptoKeeperError::Io)?,
     );
 
-    if password.is_empty() {
-        return Err(CryptoKeeperError::EmptyPassword);
-    }
+    crate::config::load_config()?.password_policy.check(&password)?;
 
     let confirm = Zeroizing::new(
         rpassword::prompt_password("Confirm master password: ")
@@ -38,8 +37,8 @@ pub fn run() -> Result<()> {
     storage::ensure_vault_dir()?;
 
     let vault = VaultData::new();
-    eprintln!("Encrypting vault...");
-    storage::save_vault(&vault, password.as_bytes())?;
+    logging::info!("Encrypting vault...");
+    super::save_vault(&vault, password.as_bytes())?;
 
     let lines = vec![
         format!("{}", "Vault created successfully!".green().bold()),
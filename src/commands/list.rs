@@ -1,9 +1,12 @@
 use colored::{ColoredString, Colorize};
 use dialoguer::Select;
 
+use crate::config;
 use crate::error::{CryptoKeeperError, Result};
 use crate::ui;
+use crate::config::model::Config;
 use crate::ui::borders::{print_table_box, truncate_display};
+use crate::ui::columns::{column_spec, configured_columns, ColumnSpec, CLI_COLUMNS};
 use crate::vault::model::{EntryMeta, SecretType};
 use crate::vault::storage;
 
@@ -16,74 +19,44 @@ fn parse_type_filter(filter: &str) -> Option<SecretType> {
     }
 }
 
-fn type_str(st: &SecretType) -> String {
-    match st {
-        SecretType::PrivateKey => "Private Key".to_string(),
-        SecretType::SeedPhrase => "Seed Phrase".to_string(),
-        SecretType::Password => "Password".to_string(),
-    }
-}
+/// Print entries as a table of the configured columns, led by their list number.
+fn print_entries(title: &str, entries: &[(usize, EntryMeta)], config: &Config) {
+    let specs: Vec<ColumnSpec> = configured_columns(config, &CLI_COLUMNS)
+        .into_iter()
+        .map(column_spec)
+        .collect();
 
-fn type_color(s: &str) -> ColoredString {
-    match s {
-        "Private Key" => s.yellow(),
-        "Seed Phrase" => s.magenta(),
-        "Password" => s.green(),
-        _ => s.normal(),
-    }
-}
+    let headers: Vec<String> = std::iter::once("#".to_string())
+        .chain(specs.iter().map(|spec| spec.header.to_uppercase()))
+        .collect();
+    let headers: Vec<&str> = headers.iter().map(String::as_str).collect();
 
-fn build_row(i: usize, entry: &EntryMeta) -> Vec<String> {
-    let addr_or_url = if entry.secret_type == SecretType::Password {
-        entry
-            .url
-            .as_deref()
-            .map(|s| truncate_display(s, 20))
-            .unwrap_or_else(|| "-".to_string())
-    } else {
-        entry
-            .public_address
-            .as_deref()
-            .map(|s| truncate_display(s, 20))
-            .unwrap_or_else(|| "-".to_string())
-    };
-
-    let network = if entry.network.is_empty() {
-        "-".to_string()
-    } else {
-        entry.network.clone()
-    };
-
-    let username = entry
-        .username
-        .as_deref()
-        .unwrap_or("-")
-        .to_string();
-
-    vec![
-        format!("{}", i + 1),
-        entry.name.clone(),
-        network,
-        type_str(&entry.secret_type),
-        username,
-        addr_or_url,
-    ]
-}
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .map(|(i, entry)| {
+            std::iter::once(format!("{}", i + 1))
+                .chain(specs.iter().map(|spec| {
+                    let value = (spec.value)(entry, config.network_glyphs);
+                    if value.is_empty() {
+                        "-".to_string()
+                    } else if spec.max_width > 0 {
+                        truncate_display(&value, spec.max_width)
+                    } else {
+                        value
+                    }
+                }))
+                .collect()
+        })
+        .collect();
 
-fn col_styles() -> Vec<fn(&str) -> ColoredString> {
-    vec![
-        |s| s.dimmed(),       // #
-        |s| s.cyan(),         // NAME
-        |s| s.normal(),       // NETWORK
-        |s| type_color(s),    // TYPE
-        |s| s.normal(),       // USERNAME
-        |s| s.dimmed(),       // ADDRESS / URL
-    ]
-}
+    let styles: Vec<fn(&str) -> ColoredString> = std::iter::once((|s| s.dimmed()) as fn(&str) -> ColoredString)
+        .chain(specs.iter().map(|spec| spec.style))
+        .collect();
 
-const HEADERS: &[&str] = &["#", "NAME", "NETWORK", "TYPE", "USERNAME", "ADDRESS / URL"];
+    print_table_box(Some(title), &headers, &rows, &styles);
+}
 
-pub fn run(filter: Option<&str>) -> Result<()> {
+pub fn run(filter: Option<&str>, archived: bool) -> Result<()> {
     // Validate filter early if provided
     if let Some(f) = filter {
         if parse_type_filter(f).is_none() {
@@ -100,15 +73,19 @@ pub fn run(filter: Option<&str>) -> Result<()> {
     }
 
     if ui::is_interactive() {
-        interactive_loop(filter)
+        interactive_loop(filter, archived)
     } else {
-        print_table(filter)
+        print_table(filter, archived)
     }
 }
 
 /// List entries from a cached vault (for REPL mode — no disk read needed).
 #[allow(dead_code)]
-pub fn run_with_vault(vault: &crate::vault::model::VaultData, filter: Option<&str>) -> Result<()> {
+pub fn run_with_vault(
+    vault: &crate::vault::model::VaultData,
+    filter: Option<&str>,
+    archived: bool,
+) -> Result<()> {
     if let Some(f) = filter {
         if parse_type_filter(f).is_none() {
             eprintln!(
@@ -124,13 +101,16 @@ pub fn run_with_vault(vault: &crate::vault::model::VaultData, filter: Option<&st
     }
 
     let meta = vault.metadata();
-    print_meta_table(&meta, filter)
+    print_meta_table(&meta, filter, archived)
 }
 
-fn filter_meta(meta: &[EntryMeta], filter: Option<&str>) -> Vec<(usize, EntryMeta)> {
+/// Entries of the requested type, either those in use or the archived ones,
+/// with their vault index.
+fn filter_meta(meta: &[EntryMeta], filter: Option<&str>, archived: bool) -> Vec<(usize, EntryMeta)> {
     let type_filter = filter.and_then(parse_type_filter);
     meta.iter()
         .enumerate()
+        .filter(|(_, e)| e.archived == archived)
         .filter(|(_, e)| {
             type_filter
                 .as_ref()
@@ -140,12 +120,12 @@ fn filter_meta(meta: &[EntryMeta], filter: Option<&str>) -> Vec<(usize, EntryMet
         .collect()
 }
 
-fn print_table(filter: Option<&str>) -> Result<()> {
+fn print_table(filter: Option<&str>, archived: bool) -> Result<()> {
     let meta = storage::read_vault_metadata()?;
-    print_meta_table(&meta, filter)
+    print_meta_table(&meta, filter, archived)
 }
 
-fn print_meta_table(meta: &[EntryMeta], filter: Option<&str>) -> Result<()> {
+fn print_meta_table(meta: &[EntryMeta], filter: Option<&str>, archived: bool) -> Result<()> {
     if meta.is_empty() {
         println!();
         println!("{}", "No entries stored yet.".dimmed());
@@ -156,30 +136,39 @@ fn print_meta_table(meta: &[EntryMeta], filter: Option<&str>) -> Result<()> {
         return Ok(());
     }
 
-    let filtered = filter_meta(meta, filter);
+    let filtered = filter_meta(meta, filter, archived);
 
     if filtered.is_empty() {
         println!();
-        println!("{}", "No entries match the given filter.".dimmed());
+        println!("{}", no_matches(filter, archived).dimmed());
         return Ok(());
     }
 
-    let rows: Vec<Vec<String>> = filtered
-        .iter()
-        .map(|(i, entry)| build_row(*i, entry))
-        .collect();
-
-    let title = match filter {
-        Some(f) => format!("Vault — {} ({} entries)", f, filtered.len()),
-        None => format!("Vault ({} entries)", filtered.len()),
-    };
+    let config = config::load_config()?;
+    let title = list_title(filter, archived, filtered.len());
     println!();
-    print_table_box(Some(&title), HEADERS, &rows, &col_styles());
+    print_entries(&title, &filtered, &config);
 
     Ok(())
 }
 
-fn interactive_loop(filter: Option<&str>) -> Result<()> {
+fn no_matches(filter: Option<&str>, archived: bool) -> &'static str {
+    match (filter, archived) {
+        (None, true) => "No archived entries.",
+        _ => "No entries match the given filter.",
+    }
+}
+
+fn list_title(filter: Option<&str>, archived: bool, count: usize) -> String {
+    let vault = if archived { "Archived" } else { "Vault" };
+    match filter {
+        Some(f) => format!("{} — {} ({} entries)", vault, f, count),
+        None => format!("{} ({} entries)", vault, count),
+    }
+}
+
+fn interactive_loop(filter: Option<&str>, archived: bool) -> Result<()> {
+    let config = config::load_config()?;
     loop {
         let meta = storage::read_vault_metadata()?;
 
@@ -193,25 +182,17 @@ fn interactive_loop(filter: Option<&str>) -> Result<()> {
             return Ok(());
         }
 
-        let filtered = filter_meta(&meta, filter);
+        let filtered = filter_meta(&meta, filter, archived);
 
         if filtered.is_empty() {
             println!();
-            println!("{}", "No entries match the given filter.".dimmed());
+            println!("{}", no_matches(filter, archived).dimmed());
             return Ok(());
         }
 
-        let rows: Vec<Vec<String>> = filtered
-            .iter()
-            .map(|(i, entry)| build_row(*i, entry))
-            .collect();
-
-        let title = match filter {
-            Some(f) => format!("Vault — {} ({} entries)", f, filtered.len()),
-            None => format!("Vault ({} entries)", filtered.len()),
-        };
+        let title = list_title(filter, archived, filtered.len());
         println!();
-        print_table_box(Some(&title), HEADERS, &rows, &col_styles());
+        print_entries(&title, &filtered, &config);
 
         // Build selection items: entry names + Exit
         let mut items: Vec<String> = filtered
@@ -254,7 +235,7 @@ fn interactive_loop(filter: Option<&str>) -> Result<()> {
 
         match action_idx {
             0 => {
-                if let Err(e) = super::view::run(&index_str) {
+                if let Err(e) = super::view::run(&index_str, false) {
                     ui::borders::print_error(&e.to_string() as &str);
                 }
             }
@@ -1,4 +1,5 @@
 pub mod add;
+pub mod complete;
 pub mod config_cmd;
 pub mod copy;
 pub mod delete;
@@ -10,6 +11,66 @@ pub mod init;
 pub mod list;
 pub mod passwd;
 pub mod recover;
+pub mod rederive;
 pub mod rename;
 pub mod search;
+pub mod stats;
 pub mod view;
+
+use dialoguer::Select;
+use zeroize::Zeroizing;
+
+use crate::crypto::entry_key;
+use crate::error::{CryptoKeeperError, Result};
+use crate::ui::borders::print_success;
+use crate::vault::model::{Entry, VaultData};
+use crate::vault::storage;
+
+/// Save the vault, telling the user if this upgraded an older vault format.
+pub fn save_vault(vault: &VaultData, password: &[u8]) -> Result<()> {
+    if let Some(from) = storage::save_vault(vault, password)? {
+        print_success(&storage::upgrade_notice(from));
+    }
+    Ok(())
+}
+
+/// Ask for the secondary password of the entry listed as `listed_name`.
+pub fn prompt_secondary_password(listed_name: &str) -> Result<Zeroizing<String>> {
+    rpassword::prompt_password(format!("Secondary password for '{}': ", listed_name))
+        .map(Zeroizing::new)
+        .map_err(CryptoKeeperError::Io)
+}
+
+/// Check the secondary password of a masked entry, got from `ask`, before
+/// its name and details are shown or changed. Other entries pass unasked.
+pub fn unmask(entry: &Entry, ask: impl FnOnce(&str) -> Result<Zeroizing<String>>) -> Result<()> {
+    if entry.mask_metadata {
+        entry_key::decrypt_entry(entry, &ask(entry.listed_name())?)?;
+    }
+    Ok(())
+}
+
+/// An identifier for the entry the user means. A number that is both an
+/// index and another entry's name is put to the user as a choice, and the
+/// chosen entry's index is returned. With no terminal to ask on, the command
+/// is refused instead.
+pub fn resolve_id(vault: &VaultData, id: &str) -> Result<String> {
+    let Some((by_index, by_name)) = vault.index_name_collision(id) else {
+        return Ok(id.to_string());
+    };
+    if !crate::ui::is_interactive() {
+        return Err(CryptoKeeperError::AmbiguousEntry(id.to_string()));
+    }
+    let choices = [
+        format!("Entry #{} ({})", by_index + 1, vault.entries[by_index].listed_name()),
+        format!("The entry named '{}' (#{})", vault.entries[by_name].listed_name(), by_name + 1),
+    ];
+    let choice = Select::new()
+        .with_prompt(format!("'{}' matches two entries", id))
+        .items(&choices)
+        .default(0)
+        .interact()
+        .map_err(|e| CryptoKeeperError::Io(std::io::Error::other(e)))?;
+    let position = if choice == 0 { by_index } else { by_name };
+    Ok((position + 1).to_string())
+}
@@ -1,15 +1,17 @@
 use zeroize::Zeroizing;
 
 use crate::error::{CryptoKeeperError, Result};
+use crate::logging;
 use crate::ui::borders::print_success;
 use crate::ui::theme::heading;
 use crate::vault::storage;
 
 pub fn run() -> Result<()> {
     let (vault, _old_password) = storage::prompt_and_unlock()?;
+    println!("  {}", vault.password_change_summary());
     let new_password = prompt_new_password()?;
-    eprintln!("Re-encrypting vault with new password...");
-    storage::save_vault(&vault, new_password.as_bytes())?;
+    logging::info!("Re-encrypting vault with new password...");
+    super::save_vault(&vault, new_password.as_bytes())?;
     print_success("Master password changed successfully.");
     Ok(())
 }
@@ -24,9 +26,9 @@ pub fn prompt_new_password() -> Result<Zeroizing<String>> {
         rpassword::prompt_password("New master password: ").map_err(CryptoKeeperError::Io)?,
     );
 
-    if new_password.is_empty() {
-        return Err(CryptoKeeperError::EmptyPassword);
-    }
+    crate::config::load_config()?
+        .password_policy
+        .check(&new_password)?;
 
     let confirm = Zeroizing::new(
         rpassword::prompt_password("Confirm new password: ").map_err(CryptoKeeperError::Io)?,
@@ -1,19 +1,22 @@
 use crate::config;
-use crate::config::model::RECOVERY_QUESTIONS;
+use crate::config::model::{RECOVERY_QUESTIONS, RECOVERY_RETRY_DELAY_SECS};
 use crate::crypto::recovery;
 use crate::error::{CryptoKeeperError, Result};
 use crate::ui::borders::{print_error, print_success};
 use crate::ui::theme::heading;
 use crate::vault::storage;
+use chrono::Utc;
+use std::time::Duration;
 use zeroize::Zeroizing;
 
-const MAX_ATTEMPTS: u32 = 5;
-
 pub fn run() -> Result<()> {
-    let cfg = config::load_config()?;
+    let mut cfg = config::load_config()?;
+    if let Some(secs) = cfg.recovery_cooldown_secs(Utc::now()) {
+        return Err(CryptoKeeperError::RecoveryFailed(recovery::cooldown_message(secs)));
+    }
     let recovery = cfg
         .recovery
-        .as_ref()
+        .clone()
         .ok_or(CryptoKeeperError::RecoveryNotConfigured)?;
 
     let question = RECOVERY_QUESTIONS
@@ -27,22 +30,27 @@ pub fn run() -> Result<()> {
     println!("  {}", question);
     println!();
 
-    let mut attempts = 0;
     let master_key = loop {
-        attempts += 1;
-        if attempts > MAX_ATTEMPTS {
-            return Err(CryptoKeeperError::RecoveryFailed(
-                "Too many failed attempts.".into(),
-            ));
-        }
-
         let answer = Zeroizing::new(
             rpassword::prompt_password("Your answer: ").map_err(CryptoKeeperError::Io)?,
         );
 
         let normalized = recovery::normalize_answer(&answer);
-        if !recovery::verify_answer(&normalized, &recovery.answer_salt, &recovery.answer_hash)? {
+        if !recovery::verify_answer(
+            &normalized,
+            &recovery.answer_salt,
+            &recovery.answer_hash,
+            recovery.kdf,
+        )? {
+            let now = Utc::now();
+            let locked = cfg.record_recovery_failure(now);
+            config::save_config(&cfg)?;
+            if locked {
+                let secs = cfg.recovery_cooldown_secs(now).unwrap_or(0);
+                return Err(CryptoKeeperError::RecoveryFailed(recovery::cooldown_message(secs)));
+            }
             print_error("Incorrect answer. Try again.");
+            std::thread::sleep(Duration::from_secs(RECOVERY_RETRY_DELAY_SECS));
             continue;
         }
 
@@ -52,6 +60,7 @@ pub fn run() -> Result<()> {
             &recovery.master_key_blob_nonce,
             &recovery.master_key_blob_salt,
             &normalized,
+            recovery.kdf,
         ) {
             Ok(key) => break key,
             Err(_) => {
@@ -73,10 +82,10 @@ pub fn run() -> Result<()> {
     let new_password = crate::commands::passwd::prompt_new_password()?;
 
     // Re-encrypt vault with new password
-    storage::save_vault(&vault, new_password.as_bytes())?;
+    super::save_vault(&vault, new_password.as_bytes())?;
 
     // Update recovery config with new master key
-    let mut cfg = cfg;
+    cfg.clear_recovery_failures();
     if let Some(ref recovery_cfg) = cfg.recovery {
         let normalized_answer = {
             let answer = Zeroizing::new(
@@ -86,16 +95,12 @@ pub fn run() -> Result<()> {
             recovery::normalize_answer(&answer)
         };
 
-        let (blob, nonce, salt) =
-            recovery::create_recovery_blob(&master_key, &normalized_answer)?;
-        cfg.recovery = Some(config::RecoveryConfig {
-            question_index: recovery_cfg.question_index,
-            answer_hash: recovery_cfg.answer_hash.clone(),
-            answer_salt: recovery_cfg.answer_salt.clone(),
-            master_key_blob: blob,
-            master_key_blob_nonce: nonce,
-            master_key_blob_salt: salt,
-        });
+        // Rebuilt whole, so recovery set up at an older cost moves to the current one
+        cfg.recovery = Some(config::RecoveryConfig::new(
+            recovery_cfg.question_index,
+            &normalized_answer,
+            &master_key,
+        )?);
         config::save_config(&cfg)?;
     }
 
@@ -0,0 +1,95 @@
+use colored::Colorize;
+
+use crate::config::{self, model::DerivationEngine};
+use crate::crypto::derive;
+use crate::error::Result;
+use crate::logging;
+use crate::ui::borders::print_box;
+use crate::vault::model::{Entry, SecretType};
+use crate::vault::storage;
+
+/// Fill in missing public addresses for every entry that can be derived now,
+/// e.g. after rebuilding with an extra `derive-*` feature.
+pub fn run(network: Option<&str>) -> Result<()> {
+    let (mut vault, password) = storage::prompt_and_unlock()?;
+    let custom_networks = config::load_config()?.custom_networks;
+
+    let mut updated = Vec::new();
+    let mut failed = Vec::new();
+    let mut protected = Vec::new();
+
+    for entry in vault.entries.iter_mut() {
+        if entry.public_address.is_some() || entry.secret_type == SecretType::Password {
+            continue;
+        }
+        if let Some(filter) = network {
+            if !matches_network(entry, filter, &custom_networks) {
+                continue;
+            }
+        }
+        if entry.has_secondary_password {
+            protected.push(entry.listed_name().to_string());
+            continue;
+        }
+
+        let resolved = derive::resolve_network(&entry.network, &custom_networks);
+        match derive::derive_entry_address(entry, resolved) {
+            Ok(Some(address)) => {
+                entry.public_address = Some(address);
+                entry.address_is_manual = false;
+                entry.updated_at = chrono::Utc::now();
+                updated.push(entry.listed_name().to_string());
+            }
+            // Network without derivation support in this build
+            Ok(None) => {}
+            Err(e) => failed.push(format!("{}: {}", entry.listed_name(), e)),
+        }
+    }
+
+    if !updated.is_empty() {
+        logging::info!("Saving vault...");
+        super::save_vault(&vault, password.as_bytes())?;
+    }
+
+    let mut lines = vec![format!(
+        "{} {} address{} derived.",
+        "✓".green().bold(),
+        updated.len().to_string().bold(),
+        if updated.len() == 1 { "" } else { "es" }
+    )];
+    for name in &updated {
+        lines.push(format!("  {}", name.cyan()));
+    }
+    if !failed.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("{} {} failed:", "!".yellow().bold(), failed.len()));
+        lines.extend(failed.iter().map(|f| format!("  {}", f)));
+    }
+    if !protected.is_empty() {
+        lines.push(String::new());
+        lines.push(format!(
+            "{} Skipped {} protected by a secondary password:",
+            "!".yellow().bold(),
+            protected.len()
+        ));
+        lines.extend(protected.iter().map(|n| format!("  {}", n.cyan())));
+    }
+    println!();
+    print_box(Some("Re-derive Addresses"), &lines);
+
+    Ok(())
+}
+
+/// Match by entry network name, by the built-in network it derives with, or by
+/// a short alias such as `btc`.
+fn matches_network(
+    entry: &Entry,
+    filter: &str,
+    custom_networks: &[config::model::CustomNetwork],
+) -> bool {
+    let filter = filter
+        .parse::<DerivationEngine>()
+        .map_or(filter, |engine| engine.network());
+    entry.network.eq_ignore_ascii_case(filter)
+        || derive::resolve_network(&entry.network, custom_networks).eq_ignore_ascii_case(filter)
+}
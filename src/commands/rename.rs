@@ -2,39 +2,49 @@ use chrono::Utc;
 use colored::Colorize;
 
 use crate::error::{CryptoKeeperError, Result};
+use crate::logging;
 use crate::ui::borders::print_success;
-use crate::vault::model::VaultData;
+use crate::vault::model::{names_match, normalize_name, VaultData};
 use crate::vault::storage;
 
 pub fn run(old_name: &str, new_name: &str) -> Result<()> {
     let (mut vault, password) = storage::prompt_and_unlock()?;
-    run_with_vault(&mut vault, old_name, new_name)?;
-    eprintln!("Saving vault...");
-    storage::save_vault(&vault, password.as_bytes())?;
+    if run_with_vault(&mut vault, old_name, new_name)? {
+        logging::info!("Saving vault...");
+        super::save_vault(&vault, password.as_bytes())?;
+    }
     Ok(())
 }
 
 /// Core rename logic without prompt_and_unlock or save (for REPL mode).
-pub fn run_with_vault(vault: &mut VaultData, old_name: &str, new_name: &str) -> Result<()> {
-    let new_name = new_name.trim().to_string();
+/// Returns false when the entry already has that exact name.
+pub fn run_with_vault(vault: &mut VaultData, old_name: &str, new_name: &str) -> Result<bool> {
+    let new_name = normalize_name(new_name);
 
+    let old_name = &super::resolve_id(vault, old_name)?;
     let resolved_old = vault
         .resolve_entry_name(old_name)
         .ok_or_else(|| CryptoKeeperError::EntryNotFound(old_name.to_string()))?;
 
-    if vault.has_entry(&new_name) {
+    // Renaming to another spelling of the same name (case, spacing) is allowed
+    if !names_match(&new_name, &resolved_old) && vault.has_entry(&new_name) {
         return Err(CryptoKeeperError::EntryAlreadyExists(new_name));
     }
+    if new_name == resolved_old {
+        println!("  {}", "No changes.".dimmed());
+        return Ok(false);
+    }
 
     let entry = vault.find_entry_mut_by_id(old_name).unwrap();
+    let listed_old = entry.listed_name().to_string();
     entry.name = new_name.clone();
     entry.updated_at = Utc::now();
 
     print_success(&format!(
         "Renamed '{}' → '{}'",
-        resolved_old.dimmed(),
+        listed_old.dimmed(),
         new_name.cyan()
     ));
 
-    Ok(())
+    Ok(true)
 }
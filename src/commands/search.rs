@@ -1,27 +1,71 @@
 use colored::{ColoredString, Colorize};
 
 use crate::error::{CryptoKeeperError, Result};
-use crate::ui::borders::{print_table_box, truncate_display};
-use crate::vault::model::{EntryMeta, SecretType, VaultData};
+use crate::ui::borders::{print_box, print_table_box, print_warning, truncate_display};
+use crate::vault::model::{address_contains, EntryMeta, SecretType, VaultData};
 use crate::vault::storage;
 
-pub fn run(query: &str) -> Result<()> {
+pub fn run(query: &str, deep: bool) -> Result<()> {
+    if deep {
+        print_warning("Deep search unlocks the vault and decrypts every secret to compare it.");
+        let (vault, _password) = storage::prompt_and_unlock()?;
+        return run_deep_with_vault(&vault, query);
+    }
     let meta = storage::read_vault_metadata()?;
     run_with_meta(&meta, query)
 }
 
+/// Search decrypted secrets and custom fields. Only entry names are printed,
+/// never the text that matched.
+pub fn run_deep_with_vault(vault: &VaultData, query: &str) -> Result<()> {
+    let matches = vault.search_secrets(query);
+    let skipped = vault.entries.iter().filter(|e| e.has_secondary_password).count();
+
+    if matches.is_empty() {
+        if skipped > 0 {
+            println!("  {}", skipped_note(skipped).dimmed());
+        }
+        return Err(CryptoKeeperError::NoSearchResults(query.to_string()));
+    }
+
+    let mut lines: Vec<String> = matches
+        .iter()
+        .map(|&i| format!("{:>3}. {}", i + 1, vault.entries[i].listed_name().cyan()))
+        .collect();
+    if skipped > 0 {
+        lines.push(String::new());
+        lines.push(skipped_note(skipped).dimmed().to_string());
+    }
+
+    let title = format!("Deep search ({} found)", matches.len());
+    println!();
+    print_box(Some(&title), &lines);
+
+    Ok(())
+}
+
 /// Core search logic using pre-loaded metadata (for REPL mode).
 pub fn run_with_vault(vault: &VaultData, query: &str) -> Result<()> {
     let meta = vault.metadata();
     run_with_meta(&meta, query)
 }
 
+fn skipped_note(skipped: usize) -> String {
+    if skipped == 1 {
+        "1 secondary-password entry was not searched.".to_string()
+    } else {
+        format!("{} secondary-password entries were not searched.", skipped)
+    }
+}
+
 fn run_with_meta(meta: &[EntryMeta], query: &str) -> Result<()> {
 
     let query_lower = query.to_lowercase();
+    // Archived entries are left out, as they are from `list`
     let matches: Vec<_> = meta
         .iter()
         .enumerate()
+        .filter(|(_, e)| !e.archived)
         .filter(|(_, e)| {
             e.name.to_lowercase().contains(&query_lower)
                 || e.network.to_lowercase().contains(&query_lower)
@@ -32,6 +76,9 @@ fn run_with_meta(meta: &[EntryMeta], query: &str) -> Result<()> {
                 || e.url
                     .as_deref()
                     .map_or(false, |u| u.to_lowercase().contains(&query_lower))
+                || e.public_address
+                    .as_deref()
+                    .is_some_and(|a| address_contains(a, query))
         })
         .collect();
 
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::error::Result;
+use crate::ui::borders::print_box;
+use crate::vault::model::VaultData;
+use crate::vault::storage::{self, UnlockTimings};
+
+pub fn run(timings: bool) -> Result<()> {
+    let (vault, _password, unlock_timings) = storage::prompt_and_unlock_timed()?;
+    run_with_vault(&vault)?;
+    if timings {
+        print_timings(&unlock_timings);
+    }
+    Ok(())
+}
+
+fn print_timings(timings: &UnlockTimings) {
+    let ms = |d: Duration| format!("{:>8.1} ms", d.as_secs_f64() * 1000.0);
+    let (m_cost, t_cost, p_cost) = timings.kdf_params;
+    let lines = vec![
+        format!("Read file:          {}", ms(timings.read)),
+        format!("Key derivation:     {}", ms(timings.kdf)),
+        format!("Decrypt:            {}", ms(timings.decrypt)),
+        format!("Deserialize:        {}", ms(timings.deserialize)),
+        format!("Total:              {}", ms(timings.total()).bold()),
+        String::new(),
+        format!(
+            "Argon2id:           {} MiB, {} iterations, {} lanes",
+            m_cost / 1024,
+            t_cost,
+            p_cost
+        )
+        .dimmed()
+        .to_string(),
+    ];
+    println!();
+    print_box(Some("Unlock Timings"), &lines);
+}
+
+/// Core stats logic without prompt_and_unlock (for REPL mode).
+pub fn run_with_vault(vault: &VaultData) -> Result<()> {
+    let stats = vault.stats();
+
+    let mut lines = vec![
+        format!("Entries:            {}", stats.total.to_string().bold()),
+        format!("  Private keys:     {}", stats.private_keys),
+        format!("  Seed phrases:     {}", stats.seed_phrases),
+        format!("  Passwords:        {}", stats.passwords),
+    ];
+
+    if !stats.by_network.is_empty() {
+        lines.push(String::new());
+        lines.push("By network:".to_string());
+        for (network, count) in &stats.by_network {
+            let label = if network.is_empty() { "(none)" } else { network };
+            lines.push(format!("  {:<18}{}", format!("{}:", label), count));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "Secondary password: {}",
+        stats.with_secondary_password
+    ));
+    lines.push(format!("Derived addresses:  {}", stats.with_address));
+
+    if let (Some((oldest_name, oldest_at)), Some((newest_name, newest_at))) =
+        (&stats.oldest, &stats.newest)
+    {
+        lines.push(format!(
+            "Oldest entry:       {} ({})",
+            oldest_name,
+            oldest_at.format("%Y-%m-%d").to_string().dimmed()
+        ));
+        lines.push(format!(
+            "Newest entry:       {} ({})",
+            newest_name,
+            newest_at.format("%Y-%m-%d").to_string().dimmed()
+        ));
+    }
+
+    lines.push(format!("Notes size:         {} bytes", stats.notes_bytes));
+
+    println!();
+    print_box(Some("Vault Stats"), &lines);
+
+    Ok(())
+}
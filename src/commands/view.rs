@@ -1,59 +1,119 @@
-use colored::Colorize;
+use colored::{ColoredString, Colorize};
 use dialoguer::{Confirm, Select};
+use zeroize::Zeroizing;
 
+use crate::config;
 use crate::error::{CryptoKeeperError, Result};
+use crate::ui;
 use crate::ui::borders::print_box;
-use crate::ui::theme::clear_screen;
+use crate::ui::columns::type_color;
+use crate::ui::theme::{clear_screen, network_label, Mask};
 use crate::vault::model::{SecretType, VaultData};
 use crate::vault::storage;
 
-pub fn run(name: &str) -> Result<()> {
+pub fn run(name: &str, show_secret: bool) -> Result<()> {
     let (vault, _password) = storage::prompt_and_unlock()?;
-    run_with_vault(&vault, name)
+    run_with_vault(&vault, name, show_secret)
+}
+
+/// Width of the label column, wide enough for "Public address:".
+const LABEL_WIDTH: usize = 16;
+
+/// One "Label: value" line with the label padded to a fixed column.
+fn field(label: &str, value: &str, color: bool) -> String {
+    let label = format!("{:<width$}", format!("{}:", label), width = LABEL_WIDTH);
+    if color {
+        format!("{} {}", label.bold(), value)
+    } else {
+        format!("{} {}", label, value)
+    }
 }
 
 /// Core view logic without prompt_and_unlock (for REPL mode).
-pub fn run_with_vault(vault: &VaultData, name: &str) -> Result<()> {
+pub fn run_with_vault(vault: &VaultData, name: &str, show_secret: bool) -> Result<()> {
+    show(vault, name, show_secret, super::prompt_secondary_password)
+}
+
+/// `run_with_vault`, taking a masked entry's secondary password from `ask`.
+fn show(
+    vault: &VaultData,
+    name: &str,
+    show_secret: bool,
+    ask: impl FnOnce(&str) -> Result<Zeroizing<String>>,
+) -> Result<()> {
+    let name = &super::resolve_id(vault, name)?;
     let entry = vault
         .find_entry_by_id(name)
         .ok_or_else(|| CryptoKeeperError::EntryNotFound(name.to_string()))?;
+    super::unmask(entry, ask)?;
 
+    let config = config::load_config()?;
+    let mask = Mask::from_config(&config);
+    // Piped output stays plain: no colors and no prompts
+    let interactive = ui::is_interactive();
+    let paint = |value: &str, style: fn(&str) -> ColoredString| {
+        if interactive {
+            style(value).to_string()
+        } else {
+            value.to_string()
+        }
+    };
+
+    let type_name = entry.secret_type.to_string();
     let mut lines = vec![
-        format!("{:<16} {}", "Name:".bold(), entry.name.cyan()),
-        format!("{:<16} {}", "Type:".bold(), entry.secret_type),
+        field("Name", &paint(&entry.name, |s| s.cyan()), interactive),
+        field("Type", &paint(&type_name, type_color), interactive),
     ];
     if !entry.network.is_empty() {
-        lines.push(format!("{:<16} {}", "Network:".bold(), entry.network));
+        let network = network_label(&entry.network, config.network_glyphs);
+        lines.push(field("Network", &network, interactive));
     }
     if let Some(ref addr) = entry.public_address {
-        lines.push(format!("{:<16} {}", "Public address:".bold(), addr));
+        lines.push(field("Public address", &paint(addr, |s| s.dimmed()), interactive));
+    }
+    if entry.derivation_account != 0 || entry.derivation_index != 0 {
+        let account = format!("{}, address {}", entry.derivation_account, entry.derivation_index);
+        lines.push(field("Account", &account, interactive));
     }
     if entry.secret_type == SecretType::Password {
         if let Some(ref uname) = entry.username {
-            lines.push(format!("{:<16} {}", "Username:".bold(), uname));
+            lines.push(field("Username", uname, interactive));
         }
         if let Some(ref url) = entry.url {
-            lines.push(format!("{:<16} {}", "URL:".bold(), url));
+            lines.push(field("URL", &paint(url, |s| s.dimmed()), interactive));
         }
     }
     if !entry.notes.is_empty() {
-        lines.push(format!("{:<16} {}", "Notes:".bold(), entry.notes));
+        lines.push(field("Notes", &entry.notes, interactive));
     }
-    lines.push(format!(
-        "{:<16} {}",
-        "Created:".bold(),
-        entry.created_at.format("%Y-%m-%d %H:%M:%S UTC")
-    ));
-    lines.push(format!(
-        "{:<16} {}",
-        "Updated:".bold(),
-        entry.updated_at.format("%Y-%m-%d %H:%M:%S UTC")
-    ));
-    lines.push(format!("{:<16} {}", "Secret:".bold(), "••••••••".dimmed()));
+    for custom in &entry.custom_fields {
+        let value = if custom.sensitive && !show_secret {
+            paint(&mask.hide(8), |s| s.dimmed())
+        } else if custom.sensitive {
+            paint(&custom.value, |s| s.red())
+        } else {
+            custom.value.clone()
+        };
+        lines.push(field(&custom.name, &value, interactive));
+    }
+    let created = entry.created_at.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+    let updated = entry.updated_at.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+    lines.push(field("Created", &paint(&created, |s| s.dimmed()), interactive));
+    lines.push(field("Updated", &paint(&updated, |s| s.dimmed()), interactive));
+    let secret = if show_secret {
+        paint(&entry.secret, |s| s.red())
+    } else {
+        paint(&mask.hide_secret(&entry.secret, &entry.secret_type), |s| s.dimmed())
+    };
+    lines.push(field("Secret", &secret, interactive));
 
     println!();
     print_box(Some("Entry Details"), &lines);
 
+    if show_secret || !interactive {
+        return Ok(());
+    }
+
     let reveal = Confirm::new()
         .with_prompt("Reveal secret?")
         .default(false)
@@ -63,6 +123,9 @@ pub fn run_with_vault(vault: &VaultData, name: &str) -> Result<()> {
     if reveal {
         println!();
         println!("  {} {}", "Secret:".bold(), entry.secret.red());
+        for field in entry.custom_fields.iter().filter(|f| f.sensitive) {
+            println!("  {} {}", format!("{}:", field.name).bold(), field.value.red());
+        }
         println!();
         
         let options = &["Clear screen and continue", "Keep visible"];
@@ -85,3 +148,72 @@ pub fn run_with_vault(vault: &VaultData, name: &str) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::entry_key;
+    use crate::crypto::kdf::KdfParams;
+    use crate::vault::model::{Entry, MASKED_NAME};
+    use chrono::Utc;
+
+    const TEST_PARAMS: KdfParams = KdfParams {
+        m_cost: 1024,
+        t_cost: 1,
+        p_cost: 1,
+    };
+
+    fn masked_vault() -> VaultData {
+        let key = entry_key::generate_entry_key();
+        let (secret, secret_nonce) = entry_key::encrypt_secret(&key, "cold secret").unwrap();
+        let (wrapped, nonce, salt) = entry_key::wrap_entry_key(&key, "view", TEST_PARAMS).unwrap();
+        let mut vault = VaultData::new();
+        vault.entries.push(Entry {
+            name: "Cold Storage".to_string(),
+            secret: "[encrypted]".to_string(),
+            secret_type: SecretType::PrivateKey,
+            network: "Ethereum".to_string(),
+            public_address: Some("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string()),
+            address_is_manual: false,
+            derivation_account: 0,
+            derivation_index: 0,
+            username: None,
+            url: None,
+            notes: "behind the painting".to_string(),
+            custom_fields: Vec::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            has_secondary_password: true,
+            entry_key_wrapped: Some(wrapped),
+            entry_key_nonce: Some(nonce),
+            entry_key_salt: Some(salt),
+            entry_key_kdf: Some(TEST_PARAMS),
+            encrypted_secret: Some(secret),
+            encrypted_secret_nonce: Some(secret_nonce),
+            mask_metadata: true,
+            clipboard_timeout_override: None,
+            archived: false,
+        });
+        vault
+    }
+
+    #[test]
+    fn masked_entry_needs_its_secondary_password() {
+        let vault = masked_vault();
+        let leaks = |text: &str| {
+            ["Cold Storage", "0xf39f", "painting"].iter().any(|s| text.contains(s))
+        };
+
+        let mut asked = String::new();
+        let result = show(&vault, "1", false, |name| {
+            asked = name.to_string();
+            Ok(Zeroizing::new("wrong".to_string()))
+        });
+        assert_eq!(asked, MASKED_NAME);
+        let err = result.unwrap_err();
+        assert!(!leaks(&format!("{} {:?}", err, err)));
+
+        let cancelled = show(&vault, "1", false, |_| Err(CryptoKeeperError::Cancelled));
+        assert!(matches!(cancelled, Err(CryptoKeeperError::Cancelled)));
+    }
+}
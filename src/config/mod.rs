@@ -2,4 +2,4 @@ pub mod model;
 pub mod storage;
 
 pub use model::{Config, RecoveryConfig};
-pub use storage::{load_config, save_config, config_path};
+pub use storage::{load_config, save_config, config_path, CONFIG_ENV};
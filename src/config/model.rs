@@ -1,8 +1,14 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::crypto::kdf::KdfParams;
+use crate::error::CryptoKeeperError;
+use crate::vault::model::{Entry, SecretType};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
-    /// Path to vault file (default: ~/.cryptokeeper/vault.ck)
+    /// Path to vault file (default: see `vault::storage::vault_dir`)
     #[serde(default = "default_vault_path")]
     pub vault_path: String,
 
@@ -10,6 +16,47 @@ pub struct Config {
     #[serde(default = "default_clipboard_timeout")]
     pub clipboard_timeout_secs: u64,
 
+    /// Seconds an idle revealed secret stays on screen before returning to the
+    /// dashboard (default: 30, 0 disables)
+    #[serde(default = "default_reveal_timeout")]
+    pub reveal_timeout_secs: u64,
+
+    /// Seconds without a keypress before the unlocked TUI is covered by a
+    /// blank screen; any key brings it back (default: 0, off)
+    #[serde(default)]
+    pub blank_after_secs: u64,
+
+    /// Cover the unlocked TUI as soon as the terminal window loses focus, on
+    /// terminals that report focus changes (default: false)
+    #[serde(default)]
+    pub blank_on_focus_loss: bool,
+
+    /// Warn after unlocking when key derivation took less than this many
+    /// milliseconds, a sign the vault's Argon2 costs are weak for this machine
+    /// (default: 100, 0 disables)
+    #[serde(default = "default_fast_unlock_warn_ms")]
+    pub fast_unlock_warn_ms: u64,
+
+    /// Largest vault, in MB, that is decrypted and loaded; anything bigger is
+    /// refused as corrupt or hostile (default: 64)
+    #[serde(default = "default_max_vault_size_mb")]
+    pub max_vault_size_mb: u64,
+
+    /// Ask before copying a private key or seed phrase to the clipboard
+    /// (passwords are copied without asking; default: false)
+    #[serde(default)]
+    pub confirm_secret_copy: bool,
+
+    /// Never touch the system clipboard; copy actions explain how to reveal
+    /// the secret instead (default: false)
+    #[serde(default)]
+    pub disable_clipboard: bool,
+
+    /// Warn and ask before copying in an SSH session, where the clipboard is
+    /// the remote host's (default: true)
+    #[serde(default = "default_true")]
+    pub warn_remote_clipboard: bool,
+
     /// Whether the first-run wizard has been completed
     #[serde(default)]
     pub first_run_complete: bool,
@@ -17,32 +64,369 @@ pub struct Config {
     /// Password recovery configuration (None if not set up)
     #[serde(default)]
     pub recovery: Option<RecoveryConfig>,
+
+    /// Keep `recovery` in `recovery.json` beside the config file instead of in
+    /// it, so a copied or synced config does not reveal that recovery is set up
+    /// or carry the answer hash (default: false)
+    #[serde(default)]
+    pub recovery_in_separate_file: bool,
+
+    /// Consecutive wrong recovery answers since the last success or cooldown
+    #[serde(default)]
+    pub recovery_failed_attempts: u32,
+
+    /// Recovery is refused until this time after too many wrong answers
+    #[serde(default)]
+    pub recovery_locked_until: Option<DateTime<Utc>>,
+
+    /// Character used to mask hidden secrets (default: •)
+    #[serde(default = "default_mask_char")]
+    pub mask_char: char,
+
+    /// Trailing characters of a hidden private key or password to show for
+    /// identification; never applied to seed phrases (default: 0, off)
+    #[serde(default)]
+    pub reveal_last_chars: usize,
+
+    /// Secret type preselected when adding an entry (default: Private Key)
+    #[serde(default = "default_secret_type")]
+    pub default_secret_type: SecretType,
+
+    /// Network preselected when adding an entry (default: Ethereum)
+    #[serde(default = "default_network")]
+    pub default_network: String,
+
+    /// Prefix networks in entry lists with a glyph such as Ξ or ₿; turn off
+    /// for terminals without the symbols (default: true)
+    #[serde(default = "default_true")]
+    pub network_glyphs: bool,
+
+    /// Lock the derived key and decrypted secrets in RAM so they are never
+    /// swapped to disk (may need a raised memlock limit; default: false)
+    #[serde(default)]
+    pub lock_memory: bool,
+
+    /// Directory backups are exported to when none is given (None: always ask)
+    #[serde(default)]
+    pub default_export_dir: Option<String>,
+
+    /// User-defined networks offered in the network selector
+    #[serde(default)]
+    pub custom_networks: Vec<CustomNetwork>,
+
+    /// Columns of the entry list, in order, for both `list` and the dashboard
+    /// (None: each keeps its built-in layout)
+    #[serde(default)]
+    pub list_columns: Option<Vec<ListColumn>>,
+
+    /// Name of the entry last opened from the dashboard, selected again after
+    /// the next unlock if it still exists
+    #[serde(default)]
+    pub last_selected_entry: Option<String>,
+
+    /// Argon2 cost for wrapping the keys of secondary-password entries; stored with
+    /// each entry, so changing it only affects entries created afterwards
+    #[serde(default = "default_entry_key_kdf")]
+    pub entry_key_kdf: KdfParams,
+
+    /// Rules new master passwords must meet
+    #[serde(default)]
+    pub password_policy: PasswordPolicy,
 }
 
 fn default_vault_path() -> String {
-    let home = std::env::var("HOME")
-        .or_else(|_| std::env::var("USERPROFILE"))
-        .unwrap_or_else(|_| ".".to_string());
-    format!("{}/.cryptokeeper/vault.ck", home)
+    crate::vault::storage::vault_path().display().to_string()
+}
+
+fn default_entry_key_kdf() -> KdfParams {
+    KdfParams::ENTRY_KEY_DEFAULT
 }
 
 fn default_clipboard_timeout() -> u64 {
     10
 }
 
+fn default_reveal_timeout() -> u64 {
+    30
+}
+
+fn default_fast_unlock_warn_ms() -> u64 {
+    100
+}
+
+fn default_max_vault_size_mb() -> u64 {
+    64
+}
+
+fn default_mask_char() -> char {
+    '\u{2022}'
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_secret_type() -> SecretType {
+    SecretType::PrivateKey
+}
+
+fn default_network() -> String {
+    "Ethereum".to_string()
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             vault_path: default_vault_path(),
             clipboard_timeout_secs: default_clipboard_timeout(),
+            reveal_timeout_secs: default_reveal_timeout(),
+            blank_after_secs: 0,
+            blank_on_focus_loss: false,
+            fast_unlock_warn_ms: default_fast_unlock_warn_ms(),
+            max_vault_size_mb: default_max_vault_size_mb(),
+            confirm_secret_copy: false,
+            disable_clipboard: false,
+            warn_remote_clipboard: true,
             first_run_complete: false,
             recovery: None,
+            recovery_in_separate_file: false,
+            recovery_failed_attempts: 0,
+            recovery_locked_until: None,
+            mask_char: default_mask_char(),
+            reveal_last_chars: 0,
+            default_secret_type: default_secret_type(),
+            default_network: default_network(),
+            network_glyphs: true,
+            lock_memory: false,
+            default_export_dir: None,
+            custom_networks: Vec::new(),
+            list_columns: None,
+            last_selected_entry: None,
+            entry_key_kdf: default_entry_key_kdf(),
+            password_policy: PasswordPolicy::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Seconds until recovery may be attempted again, if it is locked.
+    pub fn recovery_cooldown_secs(&self, now: DateTime<Utc>) -> Option<u64> {
+        let remaining = (self.recovery_locked_until? - now).num_milliseconds();
+        if remaining <= 0 {
+            return None;
+        }
+        Some((remaining as u64).div_ceil(1000))
+    }
+
+    /// Count a wrong recovery answer. Returns true when this one started a
+    /// cooldown.
+    pub fn record_recovery_failure(&mut self, now: DateTime<Utc>) -> bool {
+        self.recovery_failed_attempts += 1;
+        if self.recovery_failed_attempts < MAX_RECOVERY_ATTEMPTS {
+            return false;
+        }
+        self.recovery_failed_attempts = 0;
+        self.recovery_locked_until =
+            Some(now + chrono::Duration::seconds(RECOVERY_COOLDOWN_SECS as i64));
+        true
+    }
+
+    pub fn clear_recovery_failures(&mut self) {
+        self.recovery_failed_attempts = 0;
+        self.recovery_locked_until = None;
+    }
+
+    /// Seconds a copied secret of `entry` stays on the clipboard: the entry's
+    /// own override when set, otherwise `clipboard_timeout_secs`.
+    pub fn clipboard_timeout_for(&self, entry: &Entry) -> u64 {
+        entry
+            .clipboard_timeout_override
+            .unwrap_or(self.clipboard_timeout_secs)
+    }
+}
+
+/// Requirements for a new master password, checked by init, the wizard,
+/// password changes and recovery.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PasswordPolicy {
+    /// Minimum length in characters (default: 8)
+    #[serde(default = "default_min_password_length")]
+    pub min_length: usize,
+
+    /// Require at least three of: lowercase, uppercase, digits, symbols
+    /// (default: false)
+    #[serde(default)]
+    pub require_mix: bool,
+}
+
+fn default_min_password_length() -> usize {
+    8
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: default_min_password_length(),
+            require_mix: false,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Check a new password, explaining which rule it breaks.
+    pub fn check(&self, password: &str) -> Result<(), CryptoKeeperError> {
+        if password.is_empty() {
+            return Err(CryptoKeeperError::EmptyPassword);
+        }
+        if password.chars().count() < self.min_length {
+            return Err(CryptoKeeperError::WeakPassword(format!(
+                "Password must be at least {} characters.",
+                self.min_length
+            )));
+        }
+        if self.require_mix {
+            let classes = [
+                password.chars().any(|c| c.is_lowercase()),
+                password.chars().any(|c| c.is_uppercase()),
+                password.chars().any(|c| c.is_numeric()),
+                password.chars().any(|c| !c.is_alphanumeric()),
+            ];
+            if classes.iter().filter(|&&present| present).count() < 3 {
+                return Err(CryptoKeeperError::WeakPassword(
+                    "Password must mix at least three of: lowercase, uppercase, digits, symbols."
+                        .into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A user-defined network, e.g. an EVM chain that reuses Ethereum derivation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomNetwork {
+    pub name: String,
+
+    /// Derivation code path for this network's keys (None: no address derivation)
+    #[serde(default)]
+    pub engine: Option<DerivationEngine>,
+}
+
+/// Built-in address derivation a custom network can reuse.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DerivationEngine {
+    Ethereum,
+    Bitcoin,
+    Solana,
+}
+
+impl DerivationEngine {
+    /// Name of the built-in network whose derivation this engine uses.
+    pub fn network(&self) -> &'static str {
+        match self {
+            DerivationEngine::Ethereum => "Ethereum",
+            DerivationEngine::Bitcoin => "Bitcoin",
+            DerivationEngine::Solana => "Solana",
         }
     }
 }
 
+impl FromStr for DerivationEngine {
+    type Err = CryptoKeeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "ethereum" | "eth" | "evm" => Ok(DerivationEngine::Ethereum),
+            "bitcoin" | "btc" => Ok(DerivationEngine::Bitcoin),
+            "solana" | "sol" => Ok(DerivationEngine::Solana),
+            other => Err(CryptoKeeperError::ConfigError(format!(
+                "Unknown derivation engine '{}'. Use ethereum, bitcoin, or solana.",
+                other
+            ))),
+        }
+    }
+}
+
+/// A column the entry list can show. The index column is always first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ListColumn {
+    Name,
+    Type,
+    Network,
+    Username,
+    /// Public address, or the username of an entry without one
+    Address,
+    Url,
+    /// Public address, or the URL of a password entry
+    AddressOrUrl,
+    Notes,
+}
+
+impl ListColumn {
+    /// Name used in the config file and `config --columns`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            ListColumn::Name => "name",
+            ListColumn::Type => "type",
+            ListColumn::Network => "network",
+            ListColumn::Username => "username",
+            ListColumn::Address => "address",
+            ListColumn::Url => "url",
+            ListColumn::AddressOrUrl => "address-or-url",
+            ListColumn::Notes => "notes",
+        }
+    }
+}
+
+impl FromStr for ListColumn {
+    type Err = CryptoKeeperError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "name" => Ok(ListColumn::Name),
+            "type" => Ok(ListColumn::Type),
+            "network" => Ok(ListColumn::Network),
+            "username" | "user" => Ok(ListColumn::Username),
+            "address" => Ok(ListColumn::Address),
+            "url" => Ok(ListColumn::Url),
+            "address-or-url" => Ok(ListColumn::AddressOrUrl),
+            "notes" => Ok(ListColumn::Notes),
+            other => Err(CryptoKeeperError::ConfigError(format!(
+                "Unknown column '{}'. Use name, type, network, username, address, url, address-or-url, or notes.",
+                other
+            ))),
+        }
+    }
+}
+
+/// Parse a comma-separated column list such as "name,network,address".
+/// Repeated columns keep their first position. A blank list gives None, which
+/// restores the built-in layouts.
+pub fn parse_list_columns(spec: &str) -> Result<Option<Vec<ListColumn>>, CryptoKeeperError> {
+    if spec.trim().is_empty() {
+        return Ok(None);
+    }
+    let mut columns = Vec::new();
+    for part in spec.split(',').filter(|p| !p.trim().is_empty()) {
+        let column: ListColumn = part.parse()?;
+        if !columns.contains(&column) {
+            columns.push(column);
+        }
+    }
+    if columns.is_empty() {
+        return Err(CryptoKeeperError::ConfigError("Column list cannot be empty.".into()));
+    }
+    Ok(Some(columns))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecoveryConfig {
+    /// Argon2 cost of both the answer hash and the blob key
+    #[serde(default = "legacy_recovery_kdf")]
+    pub kdf: KdfParams,
+
     /// Index of the preset recovery question (0, 1, or 2)
     pub question_index: u8,
 
@@ -62,23 +446,94 @@ pub struct RecoveryConfig {
     pub master_key_blob_salt: Vec<u8>,
 }
 
+fn legacy_recovery_kdf() -> KdfParams {
+    crate::crypto::recovery::LEGACY_KDF
+}
+
+impl RecoveryConfig {
+    /// Set up recovery of `master_key` with a normalized answer, at the current
+    /// recovery KDF cost.
+    pub fn new(
+        question_index: u8,
+        answer: &str,
+        master_key: &[u8; 32],
+    ) -> crate::error::Result<Self> {
+        use crate::crypto::{kdf, recovery};
+
+        let kdf_params = recovery::kdf_params();
+        let answer_salt = kdf::generate_salt();
+        let answer_hash = recovery::hash_answer(answer, &answer_salt, kdf_params)?;
+        let (blob, nonce, blob_salt) =
+            recovery::create_recovery_blob(master_key, answer, kdf_params)?;
+        Ok(Self {
+            kdf: kdf_params,
+            question_index,
+            answer_hash,
+            answer_salt: answer_salt.to_vec(),
+            master_key_blob: blob,
+            master_key_blob_nonce: nonce,
+            master_key_blob_salt: blob_salt,
+        })
+    }
+}
+
 pub const RECOVERY_QUESTIONS: [&str; 3] = [
     "What was the name of your first pet?",
     "What city were you born in?",
     "What was your childhood nickname?",
 ];
 
+/// Wrong recovery answers allowed before recovery is locked.
+pub const MAX_RECOVERY_ATTEMPTS: u32 = 5;
+
+/// How long recovery stays locked after too many wrong answers.
+pub const RECOVERY_COOLDOWN_SECS: u64 = 15 * 60;
+
+/// Pause after each wrong recovery answer before another is accepted.
+pub const RECOVERY_RETRY_DELAY_SECS: u64 = 2;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn entry_clipboard_timeout_overrides_config() {
+        let mut entry: Entry = serde_json::from_str(
+            r#"{"name":"cold","secret":"s","secret_type":"PrivateKey","network":"Bitcoin","notes":"",
+                "created_at":"2024-01-01T00:00:00Z","updated_at":"2024-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        let config = Config {
+            clipboard_timeout_secs: 30,
+            ..Config::default()
+        };
+        assert_eq!(entry.clipboard_timeout_override, None);
+        assert_eq!(config.clipboard_timeout_for(&entry), 30);
+
+        entry.clipboard_timeout_override = Some(3);
+        assert_eq!(config.clipboard_timeout_for(&entry), 3);
+    }
+
     #[test]
     fn default_config_values() {
         let config = Config::default();
         assert_eq!(config.clipboard_timeout_secs, 10);
+        assert_eq!(config.reveal_timeout_secs, 30);
         assert!(!config.first_run_complete);
         assert!(config.recovery.is_none());
-        assert!(config.vault_path.ends_with(".cryptokeeper/vault.ck"));
+        assert!(config.vault_path.ends_with("vault.ck"));
+        assert_eq!(config.default_secret_type, SecretType::PrivateKey);
+        assert_eq!(config.default_network, "Ethereum");
+        assert!(!config.lock_memory);
+        assert!(!config.confirm_secret_copy);
+        assert!(!config.disable_clipboard);
+        assert!(config.warn_remote_clipboard);
+        assert!(!config.blank_on_focus_loss);
+        assert_eq!(config.entry_key_kdf, KdfParams::ENTRY_KEY_DEFAULT);
+        assert!(config.network_glyphs);
+        assert_eq!(config.mask_char, '\u{2022}');
+        assert_eq!(config.reveal_last_chars, 0);
+        assert_eq!(config.password_policy, PasswordPolicy::default());
     }
 
     #[test]
@@ -86,14 +541,70 @@ mod tests {
         let config = Config {
             vault_path: "/custom/path/vault.ck".to_string(),
             clipboard_timeout_secs: 30,
+            reveal_timeout_secs: 0,
+            blank_after_secs: 300,
+            blank_on_focus_loss: true,
+            fast_unlock_warn_ms: 250,
+            max_vault_size_mb: 8,
+            confirm_secret_copy: true,
+            disable_clipboard: true,
+            warn_remote_clipboard: false,
             first_run_complete: true,
             recovery: None,
+            recovery_in_separate_file: false,
+            recovery_failed_attempts: 2,
+            recovery_locked_until: None,
+            mask_char: '*',
+            reveal_last_chars: 4,
+            default_secret_type: SecretType::SeedPhrase,
+            default_network: "Solana".to_string(),
+            network_glyphs: false,
+            lock_memory: true,
+            default_export_dir: Some("/backups".to_string()),
+            list_columns: Some(vec![ListColumn::Name, ListColumn::Notes]),
+            last_selected_entry: Some("Cold Wallet".to_string()),
+            custom_networks: vec![CustomNetwork {
+                name: "Polygon".to_string(),
+                engine: Some(DerivationEngine::Ethereum),
+            }],
+            entry_key_kdf: KdfParams {
+                m_cost: 8192,
+                t_cost: 1,
+                p_cost: 1,
+            },
+            password_policy: PasswordPolicy {
+                min_length: 12,
+                require_mix: true,
+            },
         };
         let json = serde_json::to_string(&config).unwrap();
         let loaded: Config = serde_json::from_str(&json).unwrap();
         assert_eq!(loaded.vault_path, "/custom/path/vault.ck");
         assert_eq!(loaded.clipboard_timeout_secs, 30);
+        assert_eq!(loaded.reveal_timeout_secs, 0);
+        assert_eq!(loaded.blank_after_secs, 300);
+        assert!(loaded.blank_on_focus_loss);
+        assert_eq!(loaded.fast_unlock_warn_ms, 250);
+        assert_eq!(loaded.max_vault_size_mb, 8);
+        assert!(loaded.confirm_secret_copy);
+        assert!(loaded.disable_clipboard);
+        assert!(!loaded.warn_remote_clipboard);
         assert!(loaded.first_run_complete);
+        assert_eq!(loaded.recovery_failed_attempts, 2);
+        assert_eq!(loaded.mask_char, '*');
+        assert_eq!(loaded.reveal_last_chars, 4);
+        assert_eq!(loaded.default_secret_type, SecretType::SeedPhrase);
+        assert_eq!(loaded.default_network, "Solana");
+        assert!(loaded.lock_memory);
+        assert!(!loaded.network_glyphs);
+        assert_eq!(loaded.default_export_dir.as_deref(), Some("/backups"));
+        assert_eq!(loaded.entry_key_kdf.m_cost, 8192);
+        assert_eq!(loaded.custom_networks.len(), 1);
+        assert_eq!(loaded.custom_networks[0].engine, Some(DerivationEngine::Ethereum));
+        assert_eq!(loaded.list_columns, Some(vec![ListColumn::Name, ListColumn::Notes]));
+        assert_eq!(loaded.last_selected_entry.as_deref(), Some("Cold Wallet"));
+        assert_eq!(loaded.password_policy.min_length, 12);
+        assert!(loaded.password_policy.require_mix);
     }
 
     #[test]
@@ -101,13 +612,56 @@ mod tests {
         let json = r#"{}"#;
         let config: Config = serde_json::from_str(json).unwrap();
         assert_eq!(config.clipboard_timeout_secs, 10);
+        assert_eq!(config.max_vault_size_mb, 64);
         assert!(!config.first_run_complete);
         assert!(config.recovery.is_none());
+        assert_eq!(config.default_secret_type, SecretType::PrivateKey);
+        assert_eq!(config.default_network, "Ethereum");
+        assert!(config.network_glyphs);
+    }
+
+    #[test]
+    fn custom_networks_from_json() {
+        let json = r#"{"custom_networks":[{"name":"Polygon","engine":"ethereum"},{"name":"Kaspa"}]}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.custom_networks,
+            vec![
+                CustomNetwork {
+                    name: "Polygon".to_string(),
+                    engine: Some(DerivationEngine::Ethereum),
+                },
+                CustomNetwork {
+                    name: "Kaspa".to_string(),
+                    engine: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn derivation_engine_parse() {
+        assert_eq!("EVM".parse::<DerivationEngine>().unwrap(), DerivationEngine::Ethereum);
+        assert_eq!("btc".parse::<DerivationEngine>().unwrap(), DerivationEngine::Bitcoin);
+        assert_eq!("Solana".parse::<DerivationEngine>().unwrap(), DerivationEngine::Solana);
+        assert!("dogecoin".parse::<DerivationEngine>().is_err());
+    }
+
+    #[test]
+    fn list_columns_parse() {
+        assert_eq!(
+            parse_list_columns("Name, url,notes,name").unwrap(),
+            Some(vec![ListColumn::Name, ListColumn::Url, ListColumn::Notes])
+        );
+        assert_eq!(parse_list_columns(" ").unwrap(), None);
+        assert!(parse_list_columns(" , ").is_err());
+        assert!(parse_list_columns("name,balance").is_err());
     }
 
     #[test]
     fn recovery_config_roundtrip() {
         let recovery = RecoveryConfig {
+            kdf: crate::crypto::recovery::kdf_params(),
             question_index: 1,
             answer_hash: vec![1, 2, 3],
             answer_salt: vec![4, 5, 6],
@@ -124,5 +678,75 @@ mod tests {
         let r = loaded.recovery.unwrap();
         assert_eq!(r.question_index, 1);
         assert_eq!(r.answer_hash, vec![1, 2, 3]);
+        assert_eq!(r.kdf, crate::crypto::recovery::kdf_params());
+    }
+
+    #[test]
+    fn recovery_without_stored_params_uses_legacy_cost() {
+        let json = r#"{"recovery": {"question_index": 0, "answer_hash": [1], "answer_salt": [2],
+            "master_key_blob": [3], "master_key_blob_nonce": [4], "master_key_blob_salt": [5]}}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(config.recovery.unwrap().kdf, crate::crypto::recovery::LEGACY_KDF);
+        assert!(!config.recovery_in_separate_file);
+    }
+
+    #[test]
+    fn recovery_config_new_verifies_its_answer() {
+        use crate::crypto::recovery;
+
+        let key = [0x5Au8; 32];
+        let r = RecoveryConfig::new(2, "fluffy", &key).unwrap();
+        assert_eq!(r.question_index, 2);
+        assert!(recovery::verify_answer("fluffy", &r.answer_salt, &r.answer_hash, r.kdf).unwrap());
+        let recovered = recovery::decrypt_recovery_blob(
+            &r.master_key_blob,
+            &r.master_key_blob_nonce,
+            &r.master_key_blob_salt,
+            "fluffy",
+            r.kdf,
+        )
+        .unwrap();
+        assert_eq!(*recovered, key);
+    }
+
+    #[test]
+    fn recovery_locks_after_max_attempts() {
+        let mut config = Config::default();
+        let now = Utc::now();
+        for _ in 1..MAX_RECOVERY_ATTEMPTS {
+            assert!(!config.record_recovery_failure(now));
+        }
+        assert_eq!(config.recovery_cooldown_secs(now), None);
+        assert!(config.record_recovery_failure(now));
+        assert_eq!(config.recovery_cooldown_secs(now), Some(RECOVERY_COOLDOWN_SECS));
+        assert_eq!(config.recovery_failed_attempts, 0);
+
+        let later = now + chrono::Duration::seconds(RECOVERY_COOLDOWN_SECS as i64);
+        assert_eq!(config.recovery_cooldown_secs(later), None);
+
+        config.clear_recovery_failures();
+        assert!(config.recovery_locked_until.is_none());
+    }
+
+    #[test]
+    fn password_policy_checks() {
+        let policy = PasswordPolicy::default();
+        assert!(matches!(policy.check(""), Err(CryptoKeeperError::EmptyPassword)));
+        assert!(policy.check("short").is_err());
+        assert!(policy.check("longenough").is_ok());
+
+        let strict = PasswordPolicy {
+            min_length: 8,
+            require_mix: true,
+        };
+        assert!(strict.check("alllowercase").is_err());
+        assert!(strict.check("lower and 123").is_ok());
+        assert!(strict.check("Upper-lower").is_ok());
+
+        let relaxed = PasswordPolicy {
+            min_length: 0,
+            require_mix: false,
+        };
+        assert!(relaxed.check("x").is_ok());
     }
 }
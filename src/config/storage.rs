@@ -1,12 +1,39 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::config::model::Config;
+use crate::config::model::{Config, RecoveryConfig};
 use crate::error::{CryptoKeeperError, Result};
+use crate::vault::storage::{ensure_private_dir, legacy_dir, resolve_dir, xdg_dir};
 
-/// Get the config file path (~/.cryptokeeper/config.json).
+/// Set by `--config`; may also be exported to use another config file on every run.
+pub const CONFIG_ENV: &str = "CRYPTOKEEPER_CONFIG";
+
+/// Get the config file path: `CRYPTOKEEPER_CONFIG` if set, otherwise
+/// `config.json` in a directory resolved like `vault_dir`, except the XDG
+/// location is `$XDG_CONFIG_HOME/cryptokeeper` (default `~/.config/cryptokeeper`):
+/// `CRYPTOKEEPER_VAULT_DIR`, then an existing `~/.cryptokeeper`, then XDG.
 pub fn config_path() -> PathBuf {
-    crate::vault::storage::vault_dir().join("config.json")
+    resolve_config_path(std::env::var_os(CONFIG_ENV).map(PathBuf::from), || {
+        resolve_dir(
+            std::env::var_os("CRYPTOKEEPER_VAULT_DIR").map(PathBuf::from),
+            legacy_dir(),
+            xdg_dir("XDG_CONFIG_HOME", ".config"),
+        )
+        .join("config.json")
+    })
+}
+
+/// An explicit, non-empty config path wins over the default location.
+fn resolve_config_path(explicit: Option<PathBuf>, default: impl FnOnce() -> PathBuf) -> PathBuf {
+    explicit
+        .filter(|path| !path.as_os_str().is_empty())
+        .unwrap_or_else(default)
+}
+
+/// Where recovery is kept when `recovery_in_separate_file` is set: beside the
+/// config file at `path`.
+pub fn recovery_path(path: &Path) -> PathBuf {
+    path.with_file_name("recovery.json")
 }
 
 /// Load config from a specific path. Returns default if file doesn't exist.
@@ -15,8 +42,17 @@ pub fn load_config_from(path: &Path) -> Result<Config> {
         return Ok(Config::default());
     }
     let data = fs::read_to_string(path)?;
-    let config: Config =
+    let mut config: Config =
         serde_json::from_str(&data).map_err(|e| CryptoKeeperError::ConfigError(e.to_string()))?;
+
+    let recovery_path = recovery_path(path);
+    if config.recovery_in_separate_file && config.recovery.is_none() && recovery_path.exists() {
+        let data = fs::read_to_string(&recovery_path)?;
+        let recovery: RecoveryConfig = serde_json::from_str(&data).map_err(|e| {
+            CryptoKeeperError::ConfigError(format!("{}: {}", recovery_path.display(), e))
+        })?;
+        config.recovery = Some(recovery);
+    }
     Ok(config)
 }
 
@@ -25,22 +61,50 @@ pub fn load_config() -> Result<Config> {
     load_config_from(&config_path())
 }
 
-/// Save config to a specific path atomically with 0600 permissions.
+/// Save config to a specific path atomically with 0600 permissions. With
+/// `recovery_in_separate_file`, recovery goes to `recovery_path` instead and
+/// the config file is written without it; otherwise a leftover recovery file
+/// is removed once the config holds its contents.
 pub fn save_config_to(config: &Config, path: &Path) -> Result<()> {
     if let Some(parent) = path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent)?;
+        ensure_private_dir(parent)?;
+    }
+
+    let recovery_path = recovery_path(path);
+    let json = if config.recovery_in_separate_file {
+        match &config.recovery {
+            Some(recovery) => {
+                let json = serde_json::to_string_pretty(recovery)
+                    .map_err(|e| CryptoKeeperError::ConfigError(e.to_string()))?;
+                write_private(&recovery_path, json.as_bytes())?;
+            }
+            None if recovery_path.exists() => fs::remove_file(&recovery_path)?,
+            None => {}
         }
+        let without_recovery = Config {
+            recovery: None,
+            ..config.clone()
+        };
+        serde_json::to_string_pretty(&without_recovery)
+    } else {
+        serde_json::to_string_pretty(config)
     }
+    .map_err(|e| CryptoKeeperError::ConfigError(e.to_string()))?;
 
-    let json = serde_json::to_string_pretty(config)
-        .map_err(|e| CryptoKeeperError::ConfigError(e.to_string()))?;
+    write_private(path, json.as_bytes())?;
+    if !config.recovery_in_separate_file && recovery_path.exists() {
+        fs::remove_file(&recovery_path)?;
+    }
+
+    Ok(())
+}
 
+/// Write through a 0600 temp file renamed over `path`.
+fn write_private(path: &Path, data: &[u8]) -> Result<()> {
     let temp_path = path.with_extension("tmp");
-    fs::write(&temp_path, json.as_bytes())?;
+    fs::write(&temp_path, data)?;
     set_config_permissions(&temp_path)?;
     fs::rename(&temp_path, path)?;
-
     Ok(())
 }
 
@@ -75,6 +139,18 @@ mod tests {
         assert_eq!(config.clipboard_timeout_secs, 10);
     }
 
+    #[test]
+    fn explicit_config_path_overrides_default() {
+        let default = || PathBuf::from("/home/user/.config/cryptokeeper/config.json");
+        assert_eq!(
+            resolve_config_path(Some(PathBuf::from("/tmp/work.json")), default),
+            PathBuf::from("/tmp/work.json")
+        );
+        // An empty variable counts as unset
+        assert_eq!(resolve_config_path(Some(PathBuf::new()), default), default());
+        assert_eq!(resolve_config_path(None, default), default());
+    }
+
     #[test]
     fn save_and_load_config_roundtrip() {
         let dir = TempDir::new().unwrap();
@@ -85,6 +161,7 @@ mod tests {
             clipboard_timeout_secs: 20,
             first_run_complete: true,
             recovery: None,
+            ..Config::default()
         };
         save_config_to(&config, &path).unwrap();
 
@@ -93,4 +170,35 @@ mod tests {
         assert_eq!(loaded.clipboard_timeout_secs, 20);
         assert!(loaded.first_run_complete);
     }
+
+    #[test]
+    fn recovery_moves_between_config_and_its_own_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.json");
+        let mut config = Config {
+            recovery: Some(RecoveryConfig::new(1, "fluffy", &[3u8; 32]).unwrap()),
+            recovery_in_separate_file: true,
+            ..Config::default()
+        };
+        save_config_to(&config, &path).unwrap();
+
+        let recovery_file = recovery_path(&path);
+        assert!(!fs::read_to_string(&path).unwrap().contains("answer_hash"));
+        assert!(fs::read_to_string(&recovery_file).unwrap().contains("answer_hash"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&recovery_file).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let loaded = load_config_from(&path).unwrap().recovery.unwrap();
+        assert_eq!(loaded.answer_hash, config.recovery.as_ref().unwrap().answer_hash);
+
+        // Turning the option off folds recovery back into the config
+        config.recovery_in_separate_file = false;
+        save_config_to(&config, &path).unwrap();
+        assert!(!recovery_file.exists());
+        assert!(fs::read_to_string(&path).unwrap().contains("answer_hash"));
+        assert!(load_config_from(&path).unwrap().recovery.is_some());
+    }
 }
@@ -1,5 +1,15 @@
+use crate::config::model::CustomNetwork;
+#[cfg(any(
+    feature = "derive-eth",
+    feature = "derive-btc",
+    feature = "derive-sol",
+    feature = "derive-tron",
+    feature = "derive-cosmos",
+    feature = "derive-xrp"
+))]
+use crate::crypto::secure::Redacted;
 use crate::error::{CryptoKeeperError, Result};
-use crate::vault::model::SecretType;
+use crate::vault::model::{Entry, SecretType};
 
 /// Derive a public address from a secret (private key or seed phrase).
 /// Returns Ok(None) for unsupported network/type combos.
@@ -7,39 +17,562 @@ pub fn derive_address(
     secret: &str,
     secret_type: &SecretType,
     network: &str,
+) -> Result<Option<String>> {
+    derive_address_on_path(secret, secret_type, network, None)
+}
+
+/// `derive_address` for `entry` on `network` (its network after
+/// `resolve_network`), at the entry's own account and address index.
+pub fn derive_entry_address(entry: &Entry, network: &str) -> Result<Option<String>> {
+    let path = match entry.secret_type {
+        SecretType::SeedPhrase => {
+            DerivationPath::for_account(network, entry.derivation_account, entry.derivation_index)?
+        }
+        _ => None,
+    };
+    derive_address_on_path(&entry.secret, &entry.secret_type, network, path)
+}
+
+/// `derive_address` for a seed phrase at `path`, or at the network's default
+/// path when None. The path must suit the network's curve: build it with
+/// `DerivationPath::for_network`. Private keys ignore it.
+#[cfg_attr(
+    not(any(
+        feature = "derive-eth",
+        feature = "derive-btc",
+        feature = "derive-sol",
+        feature = "derive-tron",
+        feature = "derive-cosmos",
+        feature = "derive-xrp"
+    )),
+    allow(unused_variables)
+)]
+pub fn derive_address_on_path(
+    secret: &str,
+    secret_type: &SecretType,
+    network: &str,
+    path: Option<DerivationPath>,
 ) -> Result<Option<String>> {
     let network_lower = network.to_lowercase();
 
     match (secret_type, network_lower.as_str()) {
         #[cfg(feature = "derive-eth")]
-        (SecretType::PrivateKey, "ethereum" | "eth") => derive_eth_from_privkey(secret).map(Some),
+        (SecretType::PrivateKey, n) if is_evm_network(n) => derive_eth_from_privkey(secret).map(Some),
 
         #[cfg(feature = "derive-eth")]
-        (SecretType::SeedPhrase, "ethereum" | "eth") => derive_eth_from_seed(secret).map(Some),
+        (SecretType::SeedPhrase, n) if is_evm_network(n) => derive_eth_from_seed(secret, &path.unwrap_or(ETH_SEED_PATH)).map(Some),
 
         #[cfg(feature = "derive-btc")]
         (SecretType::PrivateKey, "bitcoin" | "btc") => derive_btc_from_privkey(secret).map(Some),
 
         #[cfg(feature = "derive-btc")]
-        (SecretType::SeedPhrase, "bitcoin" | "btc") => derive_btc_from_seed(secret).map(Some),
+        (SecretType::SeedPhrase, "bitcoin" | "btc") => derive_btc_from_seed(secret, &path.unwrap_or(BTC_SEED_PATH)).map(Some),
+
+        #[cfg(feature = "derive-tron")]
+        (SecretType::PrivateKey, "tron" | "trx") => derive_tron_from_privkey(secret).map(Some),
+
+        #[cfg(feature = "derive-tron")]
+        (SecretType::SeedPhrase, "tron" | "trx") => derive_tron_from_seed(secret, &path.unwrap_or(TRON_SEED_PATH)).map(Some),
+
+        #[cfg(feature = "derive-cosmos")]
+        (SecretType::PrivateKey, n) if cosmos_prefix(n).is_some() => {
+            derive_cosmos_from_privkey(secret, cosmos_prefix(n).unwrap_or_default()).map(Some)
+        }
+
+        #[cfg(feature = "derive-cosmos")]
+        (SecretType::SeedPhrase, n) if cosmos_prefix(n).is_some() => {
+            let path = path.unwrap_or(COSMOS_SEED_PATH);
+            derive_cosmos_from_seed(secret, cosmos_prefix(n).unwrap_or_default(), &path).map(Some)
+        }
+
+        #[cfg(feature = "derive-xrp")]
+        (SecretType::PrivateKey, "ripple" | "xrp") => derive_xrp_from_privkey(secret).map(Some),
+
+        #[cfg(feature = "derive-xrp")]
+        (SecretType::SeedPhrase, "ripple" | "xrp") => derive_xrp_from_seed(secret, &path.unwrap_or(XRP_SEED_PATH)).map(Some),
 
         #[cfg(feature = "derive-sol")]
         (SecretType::PrivateKey, "solana" | "sol") => derive_sol_from_privkey(secret).map(Some),
 
         #[cfg(feature = "derive-sol")]
-        (SecretType::SeedPhrase, "solana" | "sol") => derive_sol_from_seed(secret).map(Some),
+        (SecretType::SeedPhrase, "solana" | "sol") => derive_sol_from_seed(secret, &path.unwrap_or(SOL_SEED_PATH)).map(Some),
 
         _ => Ok(None),
     }
 }
 
-// ─── Ethereum ────────────────────────────────────────────────────────
+/// One network's result in a `derive_all` report.
+#[derive(Debug, PartialEq)]
+pub enum ChainAddress {
+    Derived(String),
+    /// This build was compiled without the network's `derive-*` feature
+    NotCompiled,
+    /// The secret does not parse for this network (e.g. a Solana keypair on Bitcoin)
+    Failed(String),
+}
 
+/// The built-in networks `derive_all` reports on, each with whether this build
+/// can derive it. EVM chains share Ethereum's address and Cosmos chains other
+/// than the hub differ only in prefix, so each appears once.
+pub fn report_networks() -> [(&'static str, bool); 6] {
+    [
+        ("Ethereum", cfg!(feature = "derive-eth")),
+        ("Bitcoin", cfg!(feature = "derive-btc")),
+        ("Solana", cfg!(feature = "derive-sol")),
+        ("Tron", cfg!(feature = "derive-tron")),
+        ("Cosmos", cfg!(feature = "derive-cosmos")),
+        ("Ripple", cfg!(feature = "derive-xrp")),
+    ]
+}
+
+/// Derive the address of one secret on every network in `report_networks`.
+/// Combinations `derive_address` does not support are left out, and passwords
+/// get an empty report.
+pub fn derive_all(secret: &str, secret_type: &SecretType) -> Vec<(&'static str, ChainAddress)> {
+    if *secret_type == SecretType::Password {
+        return Vec::new();
+    }
+    report_networks()
+        .into_iter()
+        .filter_map(|(network, compiled)| {
+            if !compiled {
+                return Some((network, ChainAddress::NotCompiled));
+            }
+            match derive_address(secret, secret_type, network) {
+                Ok(Some(address)) => Some((network, ChainAddress::Derived(address))),
+                Ok(None) => None,
+                Err(e) => Some((network, ChainAddress::Failed(e.to_string()))),
+            }
+        })
+        .collect()
+}
+
+const HARDENED: u32 = 0x8000_0000;
+
+/// A seed phrase derivation path, built from its BIP44 levels rather than
+/// written out: `m/purpose'/coin_type'/account'/change/index`. Start from
+/// `for_network`, which fills in the purpose and coin type the network's
+/// wallets use, then pick the account and address with the builder methods.
+/// Every level must be below 2^31; the hardened flag is added where needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DerivationPath {
+    pub purpose: u32,
+    pub coin_type: u32,
+    pub account: u32,
+    pub change: u32,
+    pub index: u32,
+    /// Ed25519 (SLIP-10) derives hardened levels only, and Solana wallets stop
+    /// at the change level: `m/44'/501'/account'/change'`, with no index
+    hardened_only: bool,
+}
+
+impl DerivationPath {
+    /// Account 0, change 0, index 0 under `purpose` and `coin_type`.
+    #[cfg(any(
+        feature = "derive-eth",
+        feature = "derive-btc",
+        feature = "derive-sol",
+        feature = "derive-tron",
+        feature = "derive-cosmos",
+        feature = "derive-xrp"
+    ))]
+    pub const fn bip44(purpose: u32, coin_type: u32) -> Self {
+        Self {
+            purpose,
+            coin_type,
+            account: 0,
+            change: 0,
+            index: 0,
+            hardened_only: false,
+        }
+    }
+
+    /// `m/purpose'/coin_type'/account'/change'`, all hardened, for Ed25519.
+    #[cfg(feature = "derive-sol")]
+    const fn hardened(purpose: u32, coin_type: u32) -> Self {
+        Self {
+            hardened_only: true,
+            ..Self::bip44(purpose, coin_type)
+        }
+    }
+
+    /// The default path `derive_address` takes for a seed phrase on `network`,
+    /// or None when this build does not derive seeds there.
+    #[cfg_attr(
+        not(any(
+            feature = "derive-eth",
+            feature = "derive-btc",
+            feature = "derive-sol",
+            feature = "derive-tron",
+            feature = "derive-cosmos",
+            feature = "derive-xrp"
+        )),
+        allow(unused_variables)
+    )]
+    pub fn for_network(network: &str) -> Option<Self> {
+        let network = network.to_lowercase();
+
+        #[cfg(feature = "derive-eth")]
+        if is_evm_network(&network) {
+            return Some(ETH_SEED_PATH);
+        }
+        #[cfg(feature = "derive-btc")]
+        if matches!(network.as_str(), "bitcoin" | "btc") {
+            return Some(BTC_SEED_PATH);
+        }
+        #[cfg(feature = "derive-tron")]
+        if matches!(network.as_str(), "tron" | "trx") {
+            return Some(TRON_SEED_PATH);
+        }
+        #[cfg(feature = "derive-cosmos")]
+        if cosmos_prefix(&network).is_some() {
+            return Some(COSMOS_SEED_PATH);
+        }
+        #[cfg(feature = "derive-xrp")]
+        if matches!(network.as_str(), "ripple" | "xrp") {
+            return Some(XRP_SEED_PATH);
+        }
+        #[cfg(feature = "derive-sol")]
+        if matches!(network.as_str(), "solana" | "sol") {
+            return Some(SOL_SEED_PATH);
+        }
+        None
+    }
+
+    /// `for_network` at `account` and address `index`. Fails when `index` is
+    /// not 0 and the network's path has no index level.
+    pub fn for_account(network: &str, account: u32, index: u32) -> Result<Option<Self>> {
+        let Some(path) = Self::for_network(network).map(|p| p.account(account)) else {
+            return Ok(None);
+        };
+        if index == 0 {
+            return Ok(Some(path));
+        }
+        path.index(index).map(Some).ok_or_else(|| {
+            CryptoKeeperError::DerivationFailed(format!("{} paths have no address index", network))
+        })
+    }
+
+    pub fn account(self, account: u32) -> Self {
+        Self { account, ..self }
+    }
+
+    /// The path at address `index`, or None when it has no index level
+    /// (see `has_index`).
+    pub fn index(self, index: u32) -> Option<Self> {
+        self.has_index().then_some(Self { index, ..self })
+    }
+
+    /// Whether the path has an address index level. Solana's does not, so
+    /// `index` is not part of it.
+    pub fn has_index(&self) -> bool {
+        !self.hardened_only
+    }
+
+    /// The path as the child numbers BIP32 and SLIP-10 derivation walk.
+    pub fn components(&self) -> Vec<u32> {
+        let mut path = vec![
+            HARDENED | self.purpose,
+            HARDENED | self.coin_type,
+            HARDENED | self.account,
+        ];
+        if self.hardened_only {
+            path.push(HARDENED | self.change);
+        } else {
+            path.extend([self.change, self.index]);
+        }
+        path
+    }
+}
+
+/// The usual `m/44'/60'/0'/0/0` notation.
+impl std::fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("m")?;
+        for level in self.components() {
+            if level & HARDENED != 0 {
+                write!(f, "/{}'", level & !HARDENED)?;
+            } else {
+                write!(f, "/{}", level)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// m/44'/60'/account'/0/0, as MetaMask and Ledger Live use.
 #[cfg(feature = "derive-eth")]
+const ETH_SEED_PATH: DerivationPath = DerivationPath::bip44(44, 60);
+
+/// m/84'/0'/account'/0/0, native segwit.
+#[cfg(feature = "derive-btc")]
+const BTC_SEED_PATH: DerivationPath = DerivationPath::bip44(84, 0);
+
+/// m/44'/195'/account'/0/0 (TronLink).
+#[cfg(feature = "derive-tron")]
+const TRON_SEED_PATH: DerivationPath = DerivationPath::bip44(44, 195);
+
+/// m/44'/118'/account'/0/0 (Keplr, Cosmos SDK).
+#[cfg(feature = "derive-cosmos")]
+const COSMOS_SEED_PATH: DerivationPath = DerivationPath::bip44(44, 118);
+
+/// m/44'/144'/account'/0/0 (Xaman, Ledger).
+#[cfg(feature = "derive-xrp")]
+const XRP_SEED_PATH: DerivationPath = DerivationPath::bip44(44, 144);
+
+/// m/44'/501'/account'/0', matching Phantom, Solflare and other Solana wallets.
+#[cfg(feature = "derive-sol")]
+const SOL_SEED_PATH: DerivationPath = DerivationPath::hardened(44, 501);
+
+/// EVM-compatible chains. They share Ethereum's secp256k1/Keccak address scheme,
+/// and common wallets (MetaMask, Rabby) derive them all at coin type 60, so they
+/// take the Ethereum code path unchanged.
+pub const EVM_NETWORKS: [&str; 6] = ["Polygon", "BSC", "Avalanche", "Arbitrum", "Optimism", "Base"];
+
+/// Whether `network` (any case, or a common ticker alias) uses Ethereum-style addresses.
+pub fn is_evm_network(network: &str) -> bool {
+    let network = network.to_lowercase();
+    matches!(
+        network.as_str(),
+        "ethereum" | "eth" | "matic" | "bnb" | "avax" | "arb" | "op"
+    ) || EVM_NETWORKS.iter().any(|n| n.eq_ignore_ascii_case(&network))
+}
+
+/// Map a user-defined network onto the built-in network whose derivation it uses,
+/// so e.g. a custom "Polygon" with the Ethereum engine takes the ETH code path.
+/// Names without a custom engine pass through unchanged.
+pub fn resolve_network<'a>(network: &'a str, custom_networks: &[CustomNetwork]) -> &'a str {
+    custom_networks
+        .iter()
+        .find(|n| n.name.eq_ignore_ascii_case(network))
+        .and_then(|n| n.engine)
+        .map_or(network, |engine| engine.network())
+}
+
+/// Re-derive an entry's public address from its current secret. Returns the
+/// previously stored address if the newly derived one differs from it, so a
+/// caller can offer to keep it when `address_is_manual` was set.
+/// Entries behind a secondary password and networks without derivation are
+/// left untouched.
+pub fn refresh_address(
+    entry: &mut Entry,
+    custom_networks: &[CustomNetwork],
+) -> Result<Option<String>> {
+    if entry.has_secondary_password || entry.secret_type == SecretType::Password {
+        return Ok(None);
+    }
+    let network = resolve_network(&entry.network, custom_networks);
+    let Some(derived) = derive_entry_address(entry, network)? else {
+        return Ok(None);
+    };
+    let previous = entry.public_address.replace(derived.clone());
+    entry.address_is_manual = false;
+    Ok(previous.filter(|old| !old.eq_ignore_ascii_case(&derived)))
+}
+
+/// Parse a private key the way derivation does for `network` and run
+/// `check_key_sanity` on it, or for Solana check that a keypair's two halves
+/// belong together. Keys that do not parse, and other networks, pass;
+/// derivation reports malformed keys itself.
+#[cfg_attr(
+    not(any(
+        feature = "derive-eth",
+        feature = "derive-btc",
+        feature = "derive-sol",
+        feature = "derive-cosmos",
+        feature = "derive-xrp"
+    )),
+    allow(unused_variables)
+)]
+pub fn check_private_key(secret: &str, network: &str) -> Result<()> {
+    let network = network.to_lowercase();
+
+    #[cfg(feature = "derive-eth")]
+    if is_evm_network(&network) || matches!(network.as_str(), "tron" | "trx") {
+        if let Ok(bytes) = parse_hex_key(secret) {
+            return check_key_sanity(&bytes);
+        }
+    }
+
+    #[cfg(feature = "derive-cosmos")]
+    if cosmos_prefix(&network).is_some() {
+        if let Ok(bytes) = parse_hex_key(secret) {
+            return check_key_sanity(&bytes);
+        }
+    }
+
+    #[cfg(feature = "derive-xrp")]
+    if matches!(network.as_str(), "ripple" | "xrp") {
+        if let Ok(bytes) = parse_hex_key(secret) {
+            return check_key_sanity(&bytes);
+        }
+    }
+
+    #[cfg(feature = "derive-btc")]
+    if matches!(network.as_str(), "bitcoin" | "btc") {
+        if let Ok(key) = bitcoin::key::PrivateKey::from_wif(secret.trim()) {
+            return check_key_sanity(&key.inner.secret_bytes());
+        }
+    }
+
+    #[cfg(feature = "derive-sol")]
+    if matches!(network.as_str(), "solana" | "sol") {
+        if let Ok(bytes) = sol_key_bytes(secret) {
+            if !sol_signing_key(&bytes).1 {
+                return Err(CryptoKeeperError::SuspiciousKey(
+                    "the keypair's public half does not match its secret key, so it may be corrupted"
+                        .into(),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// secp256k1 group order n; valid private keys are in [1, n).
+#[cfg(any(
+    feature = "derive-eth",
+    feature = "derive-btc",
+    feature = "derive-cosmos",
+    feature = "derive-xrp"
+))]
+const SECP256K1_ORDER: [u8; 32] = [
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE,
+    0xBA, 0xAE, 0xDC, 0xE6, 0xAF, 0x48, 0xA0, 0x3B, 0xBF, 0xD2, 0x5E, 0x8C, 0xD0, 0x36, 0x41, 0x41,
+];
+
+/// Reject secp256k1 private keys that are out of range or have so little
+/// entropy that they are placeholders or already swept by bots: zero, one
+/// repeated byte, a run of sequential bytes, or fewer than 8 distinct bytes.
+#[cfg(any(
+    feature = "derive-eth",
+    feature = "derive-btc",
+    feature = "derive-cosmos",
+    feature = "derive-xrp"
+))]
+pub fn check_key_sanity(bytes: &[u8; 32]) -> Result<()> {
+    let suspicious = |reason: &str| Err(CryptoKeeperError::SuspiciousKey(reason.to_string()));
+
+    if bytes.iter().all(|b| *b == 0) {
+        return suspicious("the key is zero");
+    }
+    if *bytes >= SECP256K1_ORDER {
+        return suspicious("the key is not below the secp256k1 curve order");
+    }
+    if bytes.windows(2).all(|w| w[0] == w[1]) {
+        return suspicious("the key repeats a single byte");
+    }
+    if bytes.windows(2).all(|w| w[1] == w[0].wrapping_add(1))
+        || bytes.windows(2).all(|w| w[1] == w[0].wrapping_sub(1))
+    {
+        return suspicious("the key is a sequence of consecutive bytes");
+    }
+    let mut seen = [false; 256];
+    for b in bytes {
+        seen[*b as usize] = true;
+    }
+    if seen.iter().filter(|s| **s).count() < 8 {
+        return suspicious("the key has too few distinct bytes to be random");
+    }
+    Ok(())
+}
+
+/// Error for secret input that failed to parse. Parser messages can quote the
+/// offending characters of the key or phrase, so the cause is redacted.
+#[cfg(any(
+    feature = "derive-eth",
+    feature = "derive-btc",
+    feature = "derive-sol",
+    feature = "derive-tron",
+    feature = "derive-cosmos",
+    feature = "derive-xrp"
+))]
+fn invalid_secret<E>(what: &'static str) -> impl FnOnce(E) -> CryptoKeeperError {
+    move |err| CryptoKeeperError::DerivationFailed(format!("{}: {}", what, Redacted(err)))
+}
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Guess which network a pasted private key belongs to from its encoding alone.
+/// 32-byte hex → Ethereum, base58 WIF → Bitcoin, base58 32/64-byte key or JSON
+/// byte array → Solana. Returns None when the format is not recognized.
+pub fn guess_network(secret: &str) -> Option<&'static str> {
+    let trimmed = secret.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    let hex_str = trimmed.strip_prefix("0x").unwrap_or(trimmed);
+    if hex_str.len() == 64 && hex_str.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Some("Ethereum");
+    }
+
+    if trimmed.starts_with('[') && trimmed.ends_with(']') {
+        return Some("Solana");
+    }
+
+    if !trimmed.chars().all(|c| BASE58_ALPHABET.contains(c)) {
+        return None;
+    }
+
+    match trimmed.len() {
+        // WIF: uncompressed starts with 5 (mainnet) / 9 (testnet), compressed K/L / c
+        51 | 52 if trimmed.starts_with(['5', '9', 'K', 'L', 'c']) => Some("Bitcoin"),
+        // Base58 of a 32-byte secret or 64-byte keypair
+        43 | 44 | 87 | 88 => Some("Solana"),
+        _ => None,
+    }
+}
+
+/// Words of a seed phrase missing from the BIP39 English list, each with its
+/// index in the phrase and the closest listed word. Words with no listed word
+/// within two edits are left out; a phrase whose words all exist (even with a
+/// bad checksum) gives an empty list.
+pub fn suggest_mnemonic_fixes(phrase: &str) -> Vec<(usize, String)> {
+    let language = bip39::Language::English;
+    phrase
+        .split_whitespace()
+        .enumerate()
+        .filter_map(|(i, word)| {
+            let word = word.to_lowercase();
+            if language.find_word(&word).is_some() {
+                return None;
+            }
+            language
+                .word_list()
+                .iter()
+                .map(|candidate| (edit_distance(&word, candidate), *candidate))
+                .filter(|(distance, _)| *distance <= 2)
+                .min_by_key(|(distance, _)| *distance)
+                .map(|(_, candidate)| (i, candidate.to_string()))
+        })
+        .collect()
+}
+
+/// Levenshtein distance between two words.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitute.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+// ─── Ethereum ────────────────────────────────────────────────────────
+
+#[cfg(any(feature = "derive-eth", feature = "derive-cosmos", feature = "derive-xrp"))]
 fn parse_hex_key(secret: &str) -> Result<[u8; 32]> {
     let hex_str = secret.trim().strip_prefix("0x").unwrap_or(secret.trim());
     let bytes = hex::decode(hex_str)
-        .map_err(|e| CryptoKeeperError::DerivationFailed(format!("Invalid hex key: {}", e)))?;
+        .map_err(invalid_secret("Invalid hex key"))?;
     if bytes.len() != 32 {
         return Err(CryptoKeeperError::DerivationFailed(format!(
             "Expected 32 bytes, got {}",
@@ -51,8 +584,9 @@ fn parse_hex_key(secret: &str) -> Result<[u8; 32]> {
     Ok(arr)
 }
 
+/// Last 20 bytes of the Keccak-256 of an uncompressed secp256k1 public key.
 #[cfg(feature = "derive-eth")]
-fn eth_address_from_pubkey_bytes(uncompressed: &[u8]) -> String {
+fn keccak_address_bytes(uncompressed: &[u8]) -> [u8; 20] {
     use sha3::Digest;
     // Skip the 0x04 prefix byte for uncompressed key
     let pubkey_bytes = if uncompressed.len() == 65 && uncompressed[0] == 0x04 {
@@ -61,8 +595,14 @@ fn eth_address_from_pubkey_bytes(uncompressed: &[u8]) -> String {
         uncompressed
     };
     let hash = sha3::Keccak256::digest(pubkey_bytes);
-    let addr_bytes = &hash[12..];
-    format!("0x{}", hex::encode(addr_bytes))
+    let mut addr = [0u8; 20];
+    addr.copy_from_slice(&hash[12..]);
+    addr
+}
+
+#[cfg(feature = "derive-eth")]
+fn eth_address_from_pubkey_bytes(uncompressed: &[u8]) -> String {
+    format!("0x{}", hex::encode(keccak_address_bytes(uncompressed)))
 }
 
 #[cfg(feature = "derive-eth")]
@@ -70,28 +610,20 @@ fn derive_eth_from_privkey(secret: &str) -> Result<String> {
     use k256::ecdsa::SigningKey;
     let key_bytes = parse_hex_key(secret)?;
     let signing_key = SigningKey::from_bytes((&key_bytes).into())
-        .map_err(|e| CryptoKeeperError::DerivationFailed(format!("Invalid ETH private key: {}", e)))?;
+        .map_err(invalid_secret("Invalid ETH private key"))?;
     let verifying_key = signing_key.verifying_key();
     let point = verifying_key.to_encoded_point(false);
     Ok(eth_address_from_pubkey_bytes(point.as_bytes()))
 }
 
 #[cfg(feature = "derive-eth")]
-fn derive_eth_from_seed(secret: &str) -> Result<String> {
+fn derive_eth_from_seed(secret: &str, path: &DerivationPath) -> Result<String> {
     use k256::ecdsa::SigningKey;
     let mnemonic = bip39::Mnemonic::parse(secret.trim())
-        .map_err(|e| CryptoKeeperError::DerivationFailed(format!("Invalid mnemonic: {}", e)))?;
+        .map_err(invalid_secret("Invalid mnemonic"))?;
     let seed = mnemonic.to_seed("");
 
-    // BIP32 derivation: m/44'/60'/0'/0/0
-    // Simple HMAC-SHA512 based derivation
-    let key_bytes = bip32_derive_secp256k1(&seed, &[
-        0x8000002C, // 44'
-        0x8000003C, // 60'
-        0x80000000, // 0'
-        0x00000000, // 0
-        0x00000000, // 0
-    ])?;
+    let key_bytes = bip32_derive_secp256k1(&seed, &path.components())?;
 
     let signing_key = SigningKey::from_bytes((&key_bytes).into())
         .map_err(|e| CryptoKeeperError::DerivationFailed(format!("BIP32 key error: {}", e)))?;
@@ -100,6 +632,134 @@ fn derive_eth_from_seed(secret: &str) -> Result<String> {
     Ok(eth_address_from_pubkey_bytes(point.as_bytes()))
 }
 
+// ─── Tron ────────────────────────────────────────────────────────────
+
+/// Tron address: 0x41 prefix + Keccak address bytes, Base58Check-encoded.
+#[cfg(feature = "derive-tron")]
+fn tron_address_from_pubkey_bytes(uncompressed: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    let mut payload = Vec::with_capacity(25);
+    payload.push(0x41);
+    payload.extend_from_slice(&keccak_address_bytes(uncompressed));
+    let checksum = Sha256::digest(Sha256::digest(&payload));
+    payload.extend_from_slice(&checksum[..4]);
+    bs58::encode(payload).into_string()
+}
+
+#[cfg(feature = "derive-tron")]
+fn derive_tron_from_privkey(secret: &str) -> Result<String> {
+    use k256::ecdsa::SigningKey;
+    let key_bytes = parse_hex_key(secret)?;
+    let signing_key = SigningKey::from_bytes((&key_bytes).into())
+        .map_err(invalid_secret("Invalid TRX private key"))?;
+    let point = signing_key.verifying_key().to_encoded_point(false);
+    Ok(tron_address_from_pubkey_bytes(point.as_bytes()))
+}
+
+#[cfg(feature = "derive-tron")]
+fn derive_tron_from_seed(secret: &str, path: &DerivationPath) -> Result<String> {
+    use k256::ecdsa::SigningKey;
+    let mnemonic = bip39::Mnemonic::parse(secret.trim())
+        .map_err(invalid_secret("Invalid mnemonic"))?;
+    let seed = mnemonic.to_seed("");
+
+    let key_bytes = bip32_derive_secp256k1(&seed, &path.components())?;
+
+    let signing_key = SigningKey::from_bytes((&key_bytes).into())
+        .map_err(|e| CryptoKeeperError::DerivationFailed(format!("BIP32 key error: {}", e)))?;
+    let point = signing_key.verifying_key().to_encoded_point(false);
+    Ok(tron_address_from_pubkey_bytes(point.as_bytes()))
+}
+
+// ─── Cosmos ──────────────────────────────────────────────────────────
+
+/// Cosmos-SDK chains and their bech32 account prefix. They all derive at coin
+/// type 118, so another chain only needs its prefix listed here.
+#[cfg(feature = "derive-cosmos")]
+pub const COSMOS_CHAINS: [(&str, &str); 3] = [("Cosmos", "cosmos"), ("Osmosis", "osmo"), ("Juno", "juno")];
+
+/// Bech32 prefix for a Cosmos-SDK network (any case, or its ticker), if it is one.
+#[cfg(feature = "derive-cosmos")]
+pub fn cosmos_prefix(network: &str) -> Option<&'static str> {
+    match network.to_lowercase().as_str() {
+        "atom" => Some("cosmos"),
+        "osmo" => Some("osmo"),
+        network => COSMOS_CHAINS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(network))
+            .map(|(_, prefix)| *prefix),
+    }
+}
+
+/// Cosmos account address: RIPEMD160(SHA256(compressed pubkey)), bech32-encoded
+/// under the chain's prefix.
+#[cfg(feature = "derive-cosmos")]
+fn cosmos_address_from_pubkey(compressed: &[u8; 33], hrp: &str) -> Result<String> {
+    use bitcoin_hashes::{hash160, Hash};
+    let hrp = bech32::Hrp::parse(hrp)
+        .map_err(|e| CryptoKeeperError::DerivationFailed(format!("Invalid bech32 prefix: {}", e)))?;
+    let account = hash160::Hash::hash(compressed);
+    bech32::encode::<bech32::Bech32>(hrp, account.as_byte_array())
+        .map_err(|e| CryptoKeeperError::DerivationFailed(format!("Bech32 error: {}", e)))
+}
+
+#[cfg(feature = "derive-cosmos")]
+fn derive_cosmos_from_privkey(secret: &str, hrp: &str) -> Result<String> {
+    let key_bytes = parse_hex_key(secret)?;
+    let pubkey = secp256k1_pubkey_compressed(&key_bytes)?;
+    cosmos_address_from_pubkey(&pubkey, hrp)
+}
+
+#[cfg(feature = "derive-cosmos")]
+fn derive_cosmos_from_seed(secret: &str, hrp: &str, path: &DerivationPath) -> Result<String> {
+    let mnemonic = bip39::Mnemonic::parse(secret.trim())
+        .map_err(invalid_secret("Invalid mnemonic"))?;
+    let seed = mnemonic.to_seed("");
+
+    let key_bytes = bip32_derive_secp256k1(&seed, &path.components())?;
+
+    let pubkey = secp256k1_pubkey_compressed(&key_bytes)?;
+    cosmos_address_from_pubkey(&pubkey, hrp)
+}
+
+// ─── Ripple ──────────────────────────────────────────────────────────
+
+/// XRP classic address: 0x00 type prefix + RIPEMD160(SHA256(compressed pubkey)),
+/// Base58Check-encoded with Ripple's alphabet.
+#[cfg(feature = "derive-xrp")]
+fn xrp_address_from_pubkey(compressed: &[u8; 33]) -> String {
+    use bitcoin_hashes::{hash160, Hash};
+    use sha2::{Digest, Sha256};
+    let mut payload = Vec::with_capacity(25);
+    payload.push(0x00);
+    payload.extend_from_slice(hash160::Hash::hash(compressed).as_byte_array());
+    let checksum = Sha256::digest(Sha256::digest(&payload));
+    payload.extend_from_slice(&checksum[..4]);
+    bs58::encode(payload)
+        .with_alphabet(bs58::Alphabet::RIPPLE)
+        .into_string()
+}
+
+/// Hex secp256k1 keys only; family seeds (`s…`) are not parsed.
+#[cfg(feature = "derive-xrp")]
+fn derive_xrp_from_privkey(secret: &str) -> Result<String> {
+    let key_bytes = parse_hex_key(secret)?;
+    let pubkey = secp256k1_pubkey_compressed(&key_bytes)?;
+    Ok(xrp_address_from_pubkey(&pubkey))
+}
+
+#[cfg(feature = "derive-xrp")]
+fn derive_xrp_from_seed(secret: &str, path: &DerivationPath) -> Result<String> {
+    let mnemonic = bip39::Mnemonic::parse(secret.trim())
+        .map_err(invalid_secret("Invalid mnemonic"))?;
+    let seed = mnemonic.to_seed("");
+
+    let key_bytes = bip32_derive_secp256k1(&seed, &path.components())?;
+
+    let pubkey = secp256k1_pubkey_compressed(&key_bytes)?;
+    Ok(xrp_address_from_pubkey(&pubkey))
+}
+
 // ─── Bitcoin ─────────────────────────────────────────────────────────
 
 #[cfg(feature = "derive-btc")]
@@ -109,7 +769,7 @@ fn derive_btc_from_privkey(secret: &str) -> Result<String> {
     use std::str::FromStr;
 
     let privkey = PrivateKey::from_wif(secret.trim())
-        .map_err(|e| CryptoKeeperError::DerivationFailed(format!("Invalid WIF key: {}", e)))?;
+        .map_err(invalid_secret("Invalid WIF key"))?;
 
     let secp = bitcoin::secp256k1::Secp256k1::new();
     let pubkey = privkey.public_key(&secp);
@@ -121,26 +781,19 @@ fn derive_btc_from_privkey(secret: &str) -> Result<String> {
 }
 
 #[cfg(feature = "derive-btc")]
-fn derive_btc_from_seed(secret: &str) -> Result<String> {
+fn derive_btc_from_seed(secret: &str, path: &DerivationPath) -> Result<String> {
     use bitcoin::{Address, CompressedPublicKey, Network};
     use std::str::FromStr;
 
     let mnemonic = bip39::Mnemonic::parse(secret.trim())
-        .map_err(|e| CryptoKeeperError::DerivationFailed(format!("Invalid mnemonic: {}", e)))?;
+        .map_err(invalid_secret("Invalid mnemonic"))?;
     let seed = mnemonic.to_seed("");
 
-    // BIP32 derivation: m/84'/0'/0'/0/0 for native segwit
-    let key_bytes = bip32_derive_secp256k1(&seed, &[
-        0x80000054, // 84'
-        0x80000000, // 0'
-        0x80000000, // 0'
-        0x00000000, // 0
-        0x00000000, // 0
-    ])?;
+    let key_bytes = bip32_derive_secp256k1(&seed, &path.components())?;
 
     let secp = bitcoin::secp256k1::Secp256k1::new();
     let secret_key = bitcoin::secp256k1::SecretKey::from_slice(&key_bytes)
-        .map_err(|e| CryptoKeeperError::DerivationFailed(format!("Invalid derived key: {}", e)))?;
+        .map_err(invalid_secret("Invalid derived key"))?;
     let pubkey = bitcoin::secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
     let compressed = CompressedPublicKey::from_str(&pubkey.to_string())
         .map_err(|e| CryptoKeeperError::DerivationFailed(format!("Compressed key error: {}", e)))?;
@@ -151,50 +804,56 @@ fn derive_btc_from_seed(secret: &str) -> Result<String> {
 
 // ─── Solana ──────────────────────────────────────────────────────────
 
+/// Bytes of a pasted Solana private key: a 32-byte secret or a 64-byte
+/// keypair (secret then public half), as base58, hex with or without `0x`, or
+/// a JSON array. Whitespace around the key and line breaks inside it, as left
+/// by copying a wrapped key out of a terminal, are ignored.
 #[cfg(feature = "derive-sol")]
-fn derive_sol_from_privkey(secret: &str) -> Result<String> {
-    use ed25519_dalek::SigningKey;
+fn sol_key_bytes(secret: &str) -> Result<zeroize::Zeroizing<Vec<u8>>> {
+    use zeroize::Zeroizing;
 
     let trimmed = secret.trim();
 
-    // Try base58-encoded keypair first (Solana CLI format: 64 bytes)
-    if let Ok(bytes) = bs58::decode(trimmed).into_vec() {
-        if bytes.len() == 64 {
-            let mut key_bytes = [0u8; 32];
-            key_bytes.copy_from_slice(&bytes[..32]);
-            let signing_key = SigningKey::from_bytes(&key_bytes);
-            let pubkey = signing_key.verifying_key();
-            return Ok(bs58::encode(pubkey.as_bytes()).into_string());
-        } else if bytes.len() == 32 {
-            let mut key_bytes = [0u8; 32];
-            key_bytes.copy_from_slice(&bytes);
-            let signing_key = SigningKey::from_bytes(&key_bytes);
-            let pubkey = signing_key.verifying_key();
-            return Ok(bs58::encode(pubkey.as_bytes()).into_string());
+    // JSON array format [1,2,3,...] (Solana CLI keypair file)
+    if trimmed.starts_with('[') {
+        if let Ok(bytes) = serde_json::from_str::<Vec<u8>>(trimmed) {
+            let bytes = Zeroizing::new(bytes);
+            if bytes.len() == 32 || bytes.len() == 64 {
+                return Ok(bytes);
+            }
         }
     }
 
-    // Try hex-encoded key
-    if let Ok(bytes) = hex::decode(trimmed.strip_prefix("0x").unwrap_or(trimmed)) {
+    let compact: Zeroizing<String> =
+        Zeroizing::new(trimmed.chars().filter(|c| !c.is_whitespace()).collect());
+    let unprefixed = compact
+        .strip_prefix("0x")
+        .or_else(|| compact.strip_prefix("0X"));
+
+    // Hex: anything prefixed, or exactly 32 or 64 bytes' worth of hex digits.
+    // Base58 of 32 or 64 bytes is never 64 or 128 characters long.
+    let hex_digits = unprefixed.unwrap_or(&compact);
+    if unprefixed.is_some()
+        || (matches!(hex_digits.len(), 64 | 128)
+            && hex_digits.chars().all(|c| c.is_ascii_hexdigit()))
+    {
+        let bytes = Zeroizing::new(
+            hex::decode(hex_digits).map_err(invalid_secret("Invalid hex key"))?,
+        );
         if bytes.len() == 32 || bytes.len() == 64 {
-            let mut key_bytes = [0u8; 32];
-            key_bytes.copy_from_slice(&bytes[..32]);
-            let signing_key = SigningKey::from_bytes(&key_bytes);
-            let pubkey = signing_key.verifying_key();
-            return Ok(bs58::encode(pubkey.as_bytes()).into_string());
+            return Ok(bytes);
         }
+        return Err(CryptoKeeperError::DerivationFailed(format!(
+            "Expected 32 or 64 bytes, got {}",
+            bytes.len()
+        )));
     }
 
-    // Try JSON array format [1,2,3,...] (Solana CLI keypair file)
-    if trimmed.starts_with('[') {
-        if let Ok(bytes) = serde_json::from_str::<Vec<u8>>(trimmed) {
-            if bytes.len() >= 32 {
-                let mut key_bytes = [0u8; 32];
-                key_bytes.copy_from_slice(&bytes[..32]);
-                let signing_key = SigningKey::from_bytes(&key_bytes);
-                let pubkey = signing_key.verifying_key();
-                return Ok(bs58::encode(pubkey.as_bytes()).into_string());
-            }
+    // Base58: the 88-character keypair wallets export, or a 32-byte secret
+    if let Ok(bytes) = bs58::decode(compact.as_str()).into_vec() {
+        let bytes = Zeroizing::new(bytes);
+        if bytes.len() == 32 || bytes.len() == 64 {
+            return Ok(bytes);
         }
     }
 
@@ -203,22 +862,68 @@ fn derive_sol_from_privkey(secret: &str) -> Result<String> {
     ))
 }
 
+/// Signing key from the secret half of `bytes`, and whether a public half, if
+/// present, is the one that secret produces.
+#[cfg(feature = "derive-sol")]
+fn sol_signing_key(bytes: &[u8]) -> (ed25519_dalek::SigningKey, bool) {
+    let mut key_bytes = zeroize::Zeroizing::new([0u8; 32]);
+    key_bytes.copy_from_slice(&bytes[..32]);
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&key_bytes);
+    let consistent =
+        bytes.len() == 32 || signing_key.verifying_key().as_bytes()[..] == bytes[32..];
+    (signing_key, consistent)
+}
+
+/// The address comes from the secret half, which is what signs; a keypair
+/// whose public half disagrees is flagged by `check_private_key`.
 #[cfg(feature = "derive-sol")]
-fn derive_sol_from_seed(secret: &str) -> Result<String> {
+fn derive_sol_from_privkey(secret: &str) -> Result<String> {
+    let bytes = sol_key_bytes(secret)?;
+    let (signing_key, _) = sol_signing_key(&bytes);
+    Ok(bs58::encode(signing_key.verifying_key().as_bytes()).into_string())
+}
+
+/// Private key of a Solana CLI keypair file (`solana-keygen` writes the 64
+/// keypair bytes as a JSON array), base58-encoded the way wallets import it.
+/// The file's public half must match its secret half.
+#[cfg(feature = "derive-sol")]
+pub fn solana_keypair_from_json(json: &str) -> Result<zeroize::Zeroizing<String>> {
+    use zeroize::Zeroizing;
+
+    let bytes: Zeroizing<Vec<u8>> = Zeroizing::new(
+        serde_json::from_str(json.trim()).map_err(invalid_secret("Invalid keypair file"))?,
+    );
+    if bytes.len() != 64 {
+        return Err(CryptoKeeperError::DerivationFailed(
+            "A Solana keypair file holds 64 bytes".into(),
+        ));
+    }
+    let (_, consistent) = sol_signing_key(&bytes);
+    if !consistent {
+        return Err(CryptoKeeperError::DerivationFailed(
+            "The keypair file's public key does not match its secret key".into(),
+        ));
+    }
+    Ok(Zeroizing::new(bs58::encode(&bytes[..]).into_string()))
+}
+
+#[cfg(not(feature = "derive-sol"))]
+pub fn solana_keypair_from_json(_json: &str) -> Result<zeroize::Zeroizing<String>> {
+    Err(CryptoKeeperError::DerivationFailed(
+        "this build has no Solana support".into(),
+    ))
+}
+
+#[cfg(feature = "derive-sol")]
+fn derive_sol_from_seed(secret: &str, path: &DerivationPath) -> Result<String> {
     use ed25519_dalek::SigningKey;
 
     let mnemonic = bip39::Mnemonic::parse(secret.trim())
-        .map_err(|e| CryptoKeeperError::DerivationFailed(format!("Invalid mnemonic: {}", e)))?;
+        .map_err(invalid_secret("Invalid mnemonic"))?;
     let seed = mnemonic.to_seed("");
 
-    // SLIP-10 / BIP44-Ed25519 derivation: m/44'/501'/0'/0'
-    // This matches Phantom, Solflare, and other standard Solana wallets.
-    let key_bytes = slip10_derive_ed25519(&seed, &[
-        0x8000002C, // 44'
-        0x800001F5, // 501'
-        0x80000000, // 0'
-        0x80000000, // 0'
-    ])?;
+    // SLIP-10 Ed25519 derivation, hardened at every level
+    let key_bytes = slip10_derive_ed25519(&seed, &path.components())?;
 
     let signing_key = SigningKey::from_bytes(&key_bytes);
     let pubkey = signing_key.verifying_key();
@@ -273,7 +978,12 @@ fn slip10_derive_ed25519(seed: &[u8], path: &[u32]) -> Result<[u8; 32]> {
 
 /// Minimal BIP32 derivation for secp256k1 keys.
 /// Uses HMAC-SHA512 as specified in BIP32.
-#[cfg(any(feature = "derive-eth", feature = "derive-btc"))]
+#[cfg(any(
+    feature = "derive-eth",
+    feature = "derive-btc",
+    feature = "derive-cosmos",
+    feature = "derive-xrp"
+))]
 fn bip32_derive_secp256k1(seed: &[u8], path: &[u32]) -> Result<[u8; 32]> {
     use hmac::{Hmac, Mac};
     use sha2::Sha512;
@@ -318,11 +1028,16 @@ fn bip32_derive_secp256k1(seed: &[u8], path: &[u32]) -> Result<[u8; 32]> {
     Ok(key)
 }
 
-#[cfg(any(feature = "derive-eth", feature = "derive-btc"))]
+#[cfg(any(
+    feature = "derive-eth",
+    feature = "derive-btc",
+    feature = "derive-cosmos",
+    feature = "derive-xrp"
+))]
 fn secp256k1_pubkey_compressed(key: &[u8; 32]) -> Result<[u8; 33]> {
     use k256::ecdsa::SigningKey;
     let signing_key = SigningKey::from_bytes(key.into())
-        .map_err(|e| CryptoKeeperError::DerivationFailed(format!("Invalid key: {}", e)))?;
+        .map_err(invalid_secret("Invalid key"))?;
     let verifying_key = signing_key.verifying_key();
     let point = verifying_key.to_encoded_point(true);
     let bytes = point.as_bytes();
@@ -331,7 +1046,12 @@ fn secp256k1_pubkey_compressed(key: &[u8; 32]) -> Result<[u8; 33]> {
     Ok(result)
 }
 
-#[cfg(any(feature = "derive-eth", feature = "derive-btc"))]
+#[cfg(any(
+    feature = "derive-eth",
+    feature = "derive-btc",
+    feature = "derive-cosmos",
+    feature = "derive-xrp"
+))]
 fn secp256k1_add_scalars(parent: &[u8; 32], tweak: &[u8]) -> Result<[u8; 32]> {
     use k256::elliptic_curve::ops::Reduce;
     use k256::Scalar;
@@ -362,6 +1082,239 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn resolve_network_uses_custom_engine() {
+        use crate::config::model::DerivationEngine;
+
+        let custom = vec![
+            CustomNetwork {
+                name: "Polygon".to_string(),
+                engine: Some(DerivationEngine::Ethereum),
+            },
+            CustomNetwork {
+                name: "Kaspa".to_string(),
+                engine: None,
+            },
+        ];
+        assert_eq!(resolve_network("polygon", &custom), "Ethereum");
+        assert_eq!(resolve_network("Kaspa", &custom), "Kaspa");
+        assert_eq!(resolve_network("Bitcoin", &custom), "Bitcoin");
+    }
+
+    #[test]
+    fn derive_all_covers_every_report_network() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let report = derive_all(mnemonic, &SecretType::SeedPhrase);
+        assert_eq!(report.len(), report_networks().len());
+        for ((network, compiled), (reported, address)) in report_networks().iter().zip(&report) {
+            assert_eq!(network, reported);
+            match address {
+                ChainAddress::Derived(address) => {
+                    let single = derive_address(mnemonic, &SecretType::SeedPhrase, network).unwrap();
+                    assert_eq!(single.as_ref(), Some(address));
+                }
+                other => assert!(!compiled, "{}: {:?}", network, other),
+            }
+        }
+
+        assert!(derive_all("hunter2", &SecretType::Password).is_empty());
+    }
+
+    #[cfg(feature = "derive-eth")]
+    #[test]
+    fn evm_networks_share_eth_address() {
+        let privkey = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let eth_key = derive_address(privkey, &SecretType::PrivateKey, "Ethereum").unwrap();
+        let eth_seed = derive_address(mnemonic, &SecretType::SeedPhrase, "Ethereum").unwrap();
+        assert!(eth_seed.is_some());
+
+        for network in EVM_NETWORKS {
+            let key_addr = derive_address(privkey, &SecretType::PrivateKey, network).unwrap();
+            assert_eq!(key_addr, eth_key, "private key on {}", network);
+            let seed_addr = derive_address(mnemonic, &SecretType::SeedPhrase, network).unwrap();
+            assert_eq!(seed_addr, eth_seed, "seed phrase on {}", network);
+        }
+    }
+
+    #[cfg(feature = "derive-eth")]
+    #[test]
+    fn custom_evm_network_derives_eth_address() {
+        use crate::config::model::DerivationEngine;
+
+        let custom = vec![CustomNetwork {
+            name: "Polygon".to_string(),
+            engine: Some(DerivationEngine::Ethereum),
+        }];
+        let privkey = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let network = resolve_network("Polygon", &custom);
+        let addr = derive_address(privkey, &SecretType::PrivateKey, network).unwrap();
+        assert_eq!(addr.as_deref(), Some("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"));
+    }
+
+    #[cfg(feature = "derive-eth")]
+    #[test]
+    fn refresh_address_follows_new_key() {
+        use chrono::Utc;
+
+        let mut entry = Entry {
+            name: "Hot wallet".to_string(),
+            secret: "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string(),
+            secret_type: SecretType::PrivateKey,
+            network: "Ethereum".to_string(),
+            public_address: Some("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".to_string()),
+            address_is_manual: false,
+            derivation_account: 0,
+            derivation_index: 0,
+            username: None,
+            url: None,
+            notes: String::new(),
+            custom_fields: Vec::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            has_secondary_password: false,
+            entry_key_wrapped: None,
+            entry_key_nonce: None,
+            entry_key_salt: None,
+            entry_key_kdf: None,
+            encrypted_secret: None,
+            encrypted_secret_nonce: None,
+            mask_metadata: false,
+            clipboard_timeout_override: None,
+            archived: false,
+        };
+
+        // Unchanged key: same address, nothing to warn about
+        assert_eq!(refresh_address(&mut entry, &[]).unwrap(), None);
+
+        entry.secret =
+            "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d".to_string();
+        entry.address_is_manual = true;
+        let previous = refresh_address(&mut entry, &[]).unwrap();
+        assert!(!entry.address_is_manual);
+        assert_eq!(previous.as_deref(), Some("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"));
+        assert_eq!(
+            entry.public_address.as_deref(),
+            Some("0x70997970c51812dc3a010c7d01b50e0d17dc79c8")
+        );
+    }
+
+    #[cfg(feature = "derive-eth")]
+    #[test]
+    fn derivation_error_does_not_echo_key() {
+        // Valid-looking key with a stray character the hex parser would quote
+        let privkey = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ffzq";
+        let err = derive_address(privkey, &SecretType::PrivateKey, "Ethereum").unwrap_err();
+        let message = format!("{} {:?}", err, err);
+        assert!(!message.contains("ac0974bec39a"));
+        assert!(!message.contains('z'));
+        assert!(!message.contains('q'));
+        assert!(message.contains("[REDACTED]"));
+    }
+
+    #[test]
+    fn seed_paths_put_the_account_third() {
+        let shown = |network: &str, account| {
+            DerivationPath::for_network(network).map(|p| p.account(account).to_string())
+        };
+        assert_eq!(
+            shown("Polygon", 3).as_deref(),
+            cfg!(feature = "derive-eth").then_some("m/44'/60'/3'/0/0")
+        );
+        assert_eq!(
+            shown("sol", 2).as_deref(),
+            cfg!(feature = "derive-sol").then_some("m/44'/501'/2'/0'")
+        );
+        assert_eq!(
+            shown("btc", 0).as_deref(),
+            cfg!(feature = "derive-btc").then_some("m/84'/0'/0'/0/0")
+        );
+        assert!(DerivationPath::for_network("Other").is_none());
+    }
+
+    #[cfg(feature = "derive-eth")]
+    #[test]
+    fn derivation_path_builder_sets_each_level() {
+        let mut path = DerivationPath::bip44(44, 60).account(1).index(7).unwrap();
+        path.change = 1;
+        assert_eq!(path.to_string(), "m/44'/60'/1'/1/7");
+        assert_eq!(
+            path.components(),
+            vec![HARDENED | 44, HARDENED | 60, HARDENED | 1, 1, 7]
+        );
+        assert!(path.has_index());
+    }
+
+    #[cfg(feature = "derive-sol")]
+    #[test]
+    fn ed25519_paths_have_no_index() {
+        let path = DerivationPath::hardened(44, 501).account(2);
+        assert_eq!(path.to_string(), "m/44'/501'/2'/0'");
+        assert!(!path.has_index());
+        assert_eq!(path.index(9), None);
+    }
+
+    #[cfg(feature = "derive-eth")]
+    #[test]
+    fn address_index_changes_the_address() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let seed = SecretType::SeedPhrase;
+        let at = |path| derive_address_on_path(phrase, &seed, "Ethereum", Some(path)).unwrap();
+        let default = DerivationPath::for_network("Ethereum").unwrap();
+        assert_eq!(at(default), derive_address(phrase, &seed, "Ethereum").unwrap());
+        // MetaMask's second address for this phrase is index 1, not account 1
+        assert_eq!(
+            at(default.index(1).unwrap()).as_deref(),
+            Some("0x6fac4d18c912343bf86fa7049364dd4e424ab9c0")
+        );
+        assert_ne!(at(default.index(1).unwrap()), at(default.account(1)));
+    }
+
+    #[cfg(feature = "derive-eth")]
+    #[test]
+    fn account_zero_is_the_default_address() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let seed = SecretType::SeedPhrase;
+        let at = |account| {
+            let path = DerivationPath::for_network("Ethereum").map(|p| p.account(account));
+            derive_address_on_path(phrase, &seed, "Ethereum", path).unwrap()
+        };
+        assert_eq!(at(0), derive_address(phrase, &seed, "Ethereum").unwrap());
+        assert_ne!(at(0), at(1));
+    }
+
+    #[test]
+    fn mnemonic_typos_get_nearest_word() {
+        let phrase = "abandon abando abandon abandon abandon abandon abandon abandon abandon abandon abandon aboutt";
+        assert_eq!(
+            suggest_mnemonic_fixes(phrase),
+            vec![(1, "abandon".to_string()), (11, "about".to_string())]
+        );
+        assert!(suggest_mnemonic_fixes("abandon ABOUT zoo").is_empty());
+        assert!(suggest_mnemonic_fixes("abandon qqqqqqqqq").is_empty());
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn guess_network_from_key_format() {
+        assert_eq!(
+            guess_network("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"),
+            Some("Ethereum")
+        );
+        assert_eq!(
+            guess_network("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn"),
+            Some("Bitcoin")
+        );
+        // base58 of [1u8; 32]
+        assert_eq!(
+            guess_network("4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi"),
+            Some("Solana")
+        );
+        assert_eq!(guess_network("[1,2,3]"), Some("Solana"));
+        assert_eq!(guess_network("hunter2"), None);
+        assert_eq!(guess_network(""), None);
+    }
+
     #[cfg(feature = "derive-eth")]
     #[test]
     fn eth_privkey_derivation() {
@@ -376,6 +1329,24 @@ mod tests {
         assert_eq!(addr, "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266");
     }
 
+    #[cfg(feature = "derive-tron")]
+    #[test]
+    fn tron_privkey_derivation() {
+        // Private key 1: Ethereum address 0x7e5f4552091a69125d5dfcb7b8c2659029395bdf
+        let privkey = "0000000000000000000000000000000000000000000000000000000000000001";
+        let addr = derive_address(privkey, &SecretType::PrivateKey, "Tron").unwrap().unwrap();
+        assert_eq!(addr, "TMVQGm1qAQYVdetCeGRRkTWYYrLXuHK2HC");
+    }
+
+    #[cfg(feature = "derive-tron")]
+    #[test]
+    fn tron_seed_derivation() {
+        // TronLink's address for the standard BIP39 test mnemonic at m/44'/195'/0'/0/0
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let addr = derive_address(mnemonic, &SecretType::SeedPhrase, "Tron").unwrap().unwrap();
+        assert_eq!(addr, "TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH");
+    }
+
     #[cfg(feature = "derive-sol")]
     #[test]
     fn sol_privkey_derivation() {
@@ -389,6 +1360,84 @@ mod tests {
         assert!(bs58::decode(&addr).into_vec().is_ok());
     }
 
+    #[cfg(feature = "derive-sol")]
+    #[test]
+    fn sol_keypair_file_imports_as_base58() {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let keypair = signing_key.to_keypair_bytes();
+        let json = serde_json::to_string(&keypair.to_vec()).unwrap();
+
+        let secret = solana_keypair_from_json(&format!("{}\n", json)).unwrap();
+        assert_eq!(*secret, bs58::encode(&keypair[..]).into_string());
+        let from_file = derive_address(&secret, &SecretType::PrivateKey, "Solana").unwrap();
+        let from_array = derive_address(&json, &SecretType::PrivateKey, "Solana").unwrap();
+        assert_eq!(from_file, from_array);
+
+        // A public half from another key, a short array and non-JSON are refused
+        let mut mismatched = keypair.to_vec();
+        mismatched[63] ^= 1;
+        let json = serde_json::to_string(&mismatched).unwrap();
+        assert!(solana_keypair_from_json(&json).is_err());
+        assert!(solana_keypair_from_json("[1, 2, 3]").is_err());
+        assert!(solana_keypair_from_json("not json").is_err());
+    }
+
+    #[cfg(feature = "derive-sol")]
+    #[test]
+    fn sol_privkey_tolerates_whitespace_and_hex_prefixes() {
+        let keypair = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]).to_keypair_bytes();
+        let base58 = bs58::encode(&keypair[..]).into_string();
+        let expected = derive_address(&base58, &SecretType::PrivateKey, "Solana").unwrap();
+
+        // Pasted with a trailing newline, indented, or wrapped across lines
+        let (head, tail) = base58.split_at(44);
+        for pasted in [
+            format!("{}\n", base58),
+            format!(" \t{}\r\n", base58),
+            format!("{}\n{}", head, tail),
+        ] {
+            assert_eq!(derive_address(&pasted, &SecretType::PrivateKey, "Solana").unwrap(), expected);
+        }
+
+        // Hex of the keypair or its secret half, with either prefix or none
+        let hex_pair = hex::encode(keypair);
+        let hex_secret = hex::encode(&keypair[..32]);
+        for pasted in [
+            hex_pair.clone(),
+            format!("0x{}", hex_pair),
+            format!("  0X{}\n", hex_pair.to_uppercase()),
+            hex_secret.clone(),
+            format!("0x{}", hex_secret),
+        ] {
+            assert_eq!(derive_address(&pasted, &SecretType::PrivateKey, "Solana").unwrap(), expected);
+        }
+
+        assert!(check_private_key(&format!("\n{}\n", base58), "Solana").is_ok());
+        assert!(derive_address("0x1234", &SecretType::PrivateKey, "Solana").is_err());
+    }
+
+    #[cfg(feature = "derive-sol")]
+    #[test]
+    fn sol_keypair_with_mismatched_public_half_is_flagged() {
+        let keypair = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]).to_keypair_bytes();
+        let secret = bs58::encode(&keypair[..32]).into_string();
+        let expected = derive_address(&secret, &SecretType::PrivateKey, "Solana").unwrap();
+
+        let mut corrupted = keypair;
+        corrupted[40] ^= 0x80;
+        let base58 = bs58::encode(&corrupted[..]).into_string();
+        assert!(matches!(
+            check_private_key(&base58, "Solana"),
+            Err(CryptoKeeperError::SuspiciousKey(_))
+        ));
+        assert!(matches!(
+            check_private_key(&hex::encode(corrupted), "sol"),
+            Err(CryptoKeeperError::SuspiciousKey(_))
+        ));
+        // The address still comes from the secret half
+        assert_eq!(derive_address(&base58, &SecretType::PrivateKey, "Solana").unwrap(), expected);
+    }
+
     #[cfg(feature = "derive-sol")]
     #[test]
     fn sol_seed_phantom_derivation() {
@@ -403,6 +1452,74 @@ mod tests {
         assert_eq!(addr, "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
     }
 
+    #[cfg(feature = "derive-cosmos")]
+    #[test]
+    fn cosmos_seed_derivation() {
+        // Keplr's address for the standard BIP39 test mnemonic at m/44'/118'/0'/0/0
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let addr = derive_address(mnemonic, &SecretType::SeedPhrase, "Cosmos").unwrap().unwrap();
+        assert_eq!(addr, "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4");
+
+        // Other Cosmos-SDK chains share the account and change only the prefix
+        let osmo = derive_address(mnemonic, &SecretType::SeedPhrase, "Osmosis").unwrap().unwrap();
+        assert!(osmo.starts_with("osmo1"));
+        let (_, cosmos_data) = bech32::decode(&addr).unwrap();
+        let (_, osmo_data) = bech32::decode(&osmo).unwrap();
+        assert_eq!(cosmos_data, osmo_data);
+    }
+
+    #[cfg(feature = "derive-cosmos")]
+    #[test]
+    fn cosmos_privkey_derivation() {
+        // Private key 1 has HASH160 751e76e8199196d454941c45d1b3a323f1433bd6
+        let privkey = "0000000000000000000000000000000000000000000000000000000000000001";
+        let addr = derive_address(privkey, &SecretType::PrivateKey, "atom").unwrap().unwrap();
+        let (hrp, data) = bech32::decode(&addr).unwrap();
+        assert_eq!(hrp.as_str(), "cosmos");
+        assert_eq!(hex::encode(data), "751e76e8199196d454941c45d1b3a323f1433bd6");
+    }
+
+    #[cfg(feature = "derive-xrp")]
+    #[test]
+    fn xrp_privkey_derivation() {
+        // Private key 1: Bitcoin P2PKH 1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH in Ripple's alphabet
+        let privkey = "0000000000000000000000000000000000000000000000000000000000000001";
+        let addr = derive_address(privkey, &SecretType::PrivateKey, "Ripple").unwrap().unwrap();
+        assert_eq!(addr, "rBgGZ9tc4him9KBzD8fKFiQz3fSZpaSwMH");
+    }
+
+    #[cfg(feature = "derive-xrp")]
+    #[test]
+    fn xrp_seed_derivation() {
+        // xrpl.js's address for the standard BIP39 test mnemonic at m/44'/144'/0'/0/0
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let addr = derive_address(mnemonic, &SecretType::SeedPhrase, "XRP").unwrap().unwrap();
+        assert_eq!(addr, "rHsMGQEkVNJmpGWs8XUBoTBiAAbwxZN5v3");
+    }
+
+    #[cfg(feature = "derive-eth")]
+    #[test]
+    fn key_sanity_flags_placeholder_keys() {
+        let mut key = [0u8; 32];
+        assert!(check_key_sanity(&key).is_err());
+        key[31] = 1;
+        assert!(check_key_sanity(&key).is_err());
+        assert!(check_key_sanity(&[0x11; 32]).is_err());
+        assert!(check_key_sanity(&SECP256K1_ORDER).is_err());
+        let sequential: Vec<u8> = (1..=32).collect();
+        assert!(check_key_sanity(&sequential.try_into().unwrap()).is_err());
+
+        let real = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        assert!(check_private_key(real, "Ethereum").is_ok());
+        let one = "0000000000000000000000000000000000000000000000000000000000000001";
+        assert!(matches!(
+            check_private_key(one, "Polygon"),
+            Err(CryptoKeeperError::SuspiciousKey(_))
+        ));
+        // Only secp256k1 networks are checked for weak keys
+        assert!(check_private_key(one, "Solana").is_ok());
+    }
+
     #[cfg(feature = "derive-btc")]
     #[test]
     fn btc_privkey_derivation() {
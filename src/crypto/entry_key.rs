@@ -1,16 +1,9 @@
 use zeroize::Zeroizing;
 
-use crate::crypto::{cipher, kdf};
+use crate::crypto::kdf::{self, KdfParams};
+use crate::crypto::cipher;
 use crate::error::{CryptoKeeperError, Result};
-
-/// Argon2 params for per-entry key wrapping (lighter than vault KDF).
-fn entry_key_params() -> (u32, u32, u32) {
-    if cfg!(test) {
-        (1024, 1, 1)
-    } else {
-        (16384, 2, 1) // 16 MB, 2 iterations, 1 lane
-    }
-}
+use crate::vault::model::Entry;
 
 /// Generate a random 32-byte per-entry encryption key.
 pub fn generate_entry_key() -> Zeroizing<[u8; 32]> {
@@ -39,37 +32,52 @@ pub fn decrypt_secret(
     nonce_arr[..n].copy_from_slice(&nonce[..n]);
     let plaintext = cipher::decrypt(entry_key, &nonce_arr, ciphertext)
         .map_err(|_| CryptoKeeperError::SecondaryPasswordWrong)?;
-    let s = String::from_utf8(plaintext.to_vec())
+    // Validated in place: `String::from_utf8` would take an unwiped copy that
+    // its error hands back and drops
+    let s = std::str::from_utf8(&plaintext)
         .map_err(|_| CryptoKeeperError::Encryption("Invalid UTF-8 in decrypted secret".into()))?;
-    Ok(Zeroizing::new(s))
+    Ok(Zeroizing::new(s.to_owned()))
 }
 
 /// Wrap (encrypt) a per-entry key under a view password using Argon2 + XChaCha20.
-/// Returns (wrapped_key, nonce, salt).
+/// Returns (wrapped_key, nonce, salt); `params` must be stored with them for unwrapping.
 pub fn wrap_entry_key(
     entry_key: &[u8; 32],
     view_password: &str,
+    params: KdfParams,
 ) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
     let salt = kdf::generate_salt();
-    let (m, t, p) = entry_key_params();
-    let wrapping_key = kdf::derive_key(view_password.as_bytes(), &salt, m, t, p)?;
+    let wrapping_key = kdf::derive_key(
+        view_password.as_bytes(),
+        &salt,
+        params.m_cost,
+        params.t_cost,
+        params.p_cost,
+    )?;
     let nonce = cipher::generate_nonce();
     let wrapped = cipher::encrypt(&*wrapping_key, &nonce, entry_key)?;
     Ok((wrapped, nonce.to_vec(), salt.to_vec()))
 }
 
-/// Unwrap (decrypt) a per-entry key using a view password.
+/// Unwrap (decrypt) a per-entry key using a view password and the parameters
+/// it was wrapped with.
 pub fn unwrap_entry_key(
     wrapped: &[u8],
     nonce: &[u8],
     salt: &[u8],
+    params: KdfParams,
     view_password: &str,
 ) -> Result<Zeroizing<[u8; 32]>> {
     let mut salt_arr = [0u8; 32];
     let s = salt.len().min(32);
     salt_arr[..s].copy_from_slice(&salt[..s]);
-    let (m, t, p) = entry_key_params();
-    let wrapping_key = kdf::derive_key(view_password.as_bytes(), &salt_arr, m, t, p)?;
+    let wrapping_key = kdf::derive_key(
+        view_password.as_bytes(),
+        &salt_arr,
+        params.m_cost,
+        params.t_cost,
+        params.p_cost,
+    )?;
     let mut nonce_arr = [0u8; 24];
     let n = nonce.len().min(24);
     nonce_arr[..n].copy_from_slice(&nonce[..n]);
@@ -83,10 +91,35 @@ pub fn unwrap_entry_key(
     Ok(key)
 }
 
+/// Decrypt the secret of an entry protected by a secondary (view) password.
+pub fn decrypt_entry(entry: &Entry, view_password: &str) -> Result<Zeroizing<String>> {
+    let wrapped = entry.entry_key_wrapped.as_ref()
+        .ok_or(CryptoKeeperError::SecondaryPasswordRequired)?;
+    let nonce = entry.entry_key_nonce.as_ref()
+        .ok_or(CryptoKeeperError::SecondaryPasswordRequired)?;
+    let salt = entry.entry_key_salt.as_ref()
+        .ok_or(CryptoKeeperError::SecondaryPasswordRequired)?;
+    let ct = entry.encrypted_secret.as_ref()
+        .ok_or(CryptoKeeperError::SecondaryPasswordRequired)?;
+    let ct_nonce = entry.encrypted_secret_nonce.as_ref()
+        .ok_or(CryptoKeeperError::SecondaryPasswordRequired)?;
+
+    let params = entry.entry_key_kdf.unwrap_or(KdfParams::ENTRY_KEY_DEFAULT);
+    let entry_key = unwrap_entry_key(wrapped, nonce, salt, params, view_password)?;
+    decrypt_secret(&entry_key, ct, ct_nonce)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Reduced params for test speed
+    const TEST_PARAMS: KdfParams = KdfParams {
+        m_cost: 1024,
+        t_cost: 1,
+        p_cost: 1,
+    };
+
     #[test]
     fn test_generate_entry_key_unique() {
         let k1 = generate_entry_key();
@@ -116,16 +149,37 @@ mod tests {
     fn test_wrap_unwrap_entry_key_roundtrip() {
         let entry_key = generate_entry_key();
         let password = "viewpass123";
-        let (wrapped, nonce, salt) = wrap_entry_key(&entry_key, password).unwrap();
-        let unwrapped = unwrap_entry_key(&wrapped, &nonce, &salt, password).unwrap();
+        let (wrapped, nonce, salt) = wrap_entry_key(&entry_key, password, TEST_PARAMS).unwrap();
+        let unwrapped = unwrap_entry_key(&wrapped, &nonce, &salt, TEST_PARAMS, password).unwrap();
         assert_eq!(*unwrapped, *entry_key);
     }
 
+    #[test]
+    fn test_wrap_unwrap_with_differing_params() {
+        let heavier = KdfParams {
+            m_cost: 2048,
+            t_cost: 2,
+            ..TEST_PARAMS
+        };
+        let key_a = generate_entry_key();
+        let key_b = generate_entry_key();
+        let (wrapped_a, nonce_a, salt_a) = wrap_entry_key(&key_a, "pass", TEST_PARAMS).unwrap();
+        let (wrapped_b, nonce_b, salt_b) = wrap_entry_key(&key_b, "pass", heavier).unwrap();
+
+        // Each entry unwraps with the params stored alongside it
+        let a = unwrap_entry_key(&wrapped_a, &nonce_a, &salt_a, TEST_PARAMS, "pass").unwrap();
+        let b = unwrap_entry_key(&wrapped_b, &nonce_b, &salt_b, heavier, "pass").unwrap();
+        assert_eq!(*a, *key_a);
+        assert_eq!(*b, *key_b);
+
+        assert!(unwrap_entry_key(&wrapped_b, &nonce_b, &salt_b, TEST_PARAMS, "pass").is_err());
+    }
+
     #[test]
     fn test_unwrap_entry_key_wrong_password() {
         let entry_key = generate_entry_key();
-        let (wrapped, nonce, salt) = wrap_entry_key(&entry_key, "correct").unwrap();
-        let result = unwrap_entry_key(&wrapped, &nonce, &salt, "wrong");
+        let (wrapped, nonce, salt) = wrap_entry_key(&entry_key, "correct", TEST_PARAMS).unwrap();
+        let result = unwrap_entry_key(&wrapped, &nonce, &salt, TEST_PARAMS, "wrong");
         assert!(result.is_err());
     }
 
@@ -140,11 +194,12 @@ mod tests {
 
         // Wrap entry key with view password
         let (wrapped_key, key_nonce, key_salt) =
-            wrap_entry_key(&entry_key, view_password).unwrap();
+            wrap_entry_key(&entry_key, view_password, TEST_PARAMS).unwrap();
 
         // Later: unwrap entry key with view password
         let recovered_key =
-            unwrap_entry_key(&wrapped_key, &key_nonce, &key_salt, view_password).unwrap();
+            unwrap_entry_key(&wrapped_key, &key_nonce, &key_salt, TEST_PARAMS, view_password)
+                .unwrap();
 
         // Decrypt secret with recovered entry key
         let decrypted = decrypt_secret(&recovered_key, &encrypted_secret, &secret_nonce).unwrap();
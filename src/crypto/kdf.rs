@@ -1,4 +1,5 @@
 use argon2::{Algorithm, Argon2, Params, Version};
+use serde::{Deserialize, Serialize};
 use zeroize::Zeroizing;
 
 use crate::error::{CryptoKeeperError, Result};
@@ -7,18 +8,104 @@ pub const DEFAULT_M_COST: u32 = 65536; // 64 MB
 pub const DEFAULT_T_COST: u32 = 3;     // 3 iterations
 pub const DEFAULT_P_COST: u32 = 4;     // 4 parallel lanes
 
-/// Derive a 32-byte key from password and salt using Argon2id.
+/// Argon2id cost parameters stored alongside data they protect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory in KiB
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl KdfParams {
+    /// Per-entry key wrapping: lighter than the vault KDF since secondary
+    /// passwords are typed often (16 MB, 2 iterations, 1 lane). Entries wrapped
+    /// before the parameters were stored used these.
+    pub const ENTRY_KEY_DEFAULT: KdfParams = KdfParams {
+        m_cost: 16384,
+        t_cost: 2,
+        p_cost: 1,
+    };
+}
+
+/// Argon2 variant and version a key was derived with. V3 vault and backup
+/// headers store them; older headers and everything else imply
+/// `KdfAlgorithm::LEGACY`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfAlgorithm {
+    pub algorithm: Algorithm,
+    pub version: Version,
+}
+
+impl KdfAlgorithm {
+    /// What everything without the variant bytes was derived with: V1 and V2
+    /// vaults, older backups, entry keys and recovery data. Never changes.
+    pub const LEGACY: KdfAlgorithm = KdfAlgorithm {
+        algorithm: Algorithm::Argon2id,
+        version: Version::V0x13,
+    };
+
+    /// What new vaults and backups are written with, and recorded in their
+    /// headers. Safe to change, unlike `LEGACY`.
+    pub const DEFAULT: KdfAlgorithm = KdfAlgorithm {
+        algorithm: Algorithm::Argon2id,
+        version: Version::V0x13,
+    };
+
+    /// Header encoding: variant (0 = Argon2d, 1 = Argon2i, 2 = Argon2id), then
+    /// the version byte (0x10 or 0x13).
+    pub fn to_bytes(self) -> [u8; 2] {
+        let algorithm = match self.algorithm {
+            Algorithm::Argon2d => 0,
+            Algorithm::Argon2i => 1,
+            Algorithm::Argon2id => 2,
+        };
+        [algorithm, self.version as u8]
+    }
+
+    pub fn from_bytes(bytes: [u8; 2]) -> Result<Self> {
+        let algorithm = match bytes[0] {
+            0 => Algorithm::Argon2d,
+            1 => Algorithm::Argon2i,
+            2 => Algorithm::Argon2id,
+            other => {
+                return Err(CryptoKeeperError::Encryption(format!(
+                    "Unknown Argon2 variant {other}"
+                )))
+            }
+        };
+        let version = Version::try_from(u32::from(bytes[1]))
+            .map_err(|_| CryptoKeeperError::Encryption(format!("Unknown Argon2 version {:#x}", bytes[1])))?;
+        Ok(Self { algorithm, version })
+    }
+}
+
+/// Derive a 32-byte key from password and salt with `KdfAlgorithm::LEGACY`,
+/// for data that does not record its Argon2 variant.
 pub fn derive_key(
     password: &[u8],
     salt: &[u8; 32],
     m_cost: u32,
     t_cost: u32,
     p_cost: u32,
+) -> Result<Zeroizing<[u8; 32]>> {
+    derive_key_with(password, salt, m_cost, t_cost, p_cost, KdfAlgorithm::LEGACY)
+}
+
+/// Derive a 32-byte key with an explicit Argon2 variant and version, as
+/// recorded in the header of the data being opened or written.
+pub fn derive_key_with(
+    password: &[u8],
+    salt: &[u8; 32],
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    kdf_algorithm: KdfAlgorithm,
 ) -> Result<Zeroizing<[u8; 32]>> {
     let params = Params::new(m_cost, t_cost, p_cost, Some(32))
         .map_err(|e| CryptoKeeperError::Encryption(format!("Argon2 params error: {e}")))?;
 
-    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+    let argon2 = Argon2::new(kdf_algorithm.algorithm, kdf_algorithm.version, params);
 
     let mut key = Zeroizing::new([0u8; 32]);
     argon2
@@ -67,6 +154,24 @@ mod tests {
         assert_ne!(&*key1, &*key2);
     }
 
+    #[test]
+    fn test_kdf_algorithm_bytes() {
+        let other = KdfAlgorithm {
+            algorithm: Algorithm::Argon2i,
+            version: Version::V0x10,
+        };
+        assert_eq!(KdfAlgorithm::LEGACY.to_bytes(), [2, 0x13]);
+        assert_eq!(KdfAlgorithm::from_bytes(other.to_bytes()).unwrap(), other);
+        assert!(KdfAlgorithm::from_bytes([3, 0x13]).is_err());
+        assert!(KdfAlgorithm::from_bytes([2, 0x12]).is_err());
+
+        // The variant changes the key
+        let salt = [7u8; 32];
+        let default = derive_key_with(b"pw", &salt, 1024, 1, 1, KdfAlgorithm::DEFAULT).unwrap();
+        let other = derive_key_with(b"pw", &salt, 1024, 1, 1, other).unwrap();
+        assert_ne!(&*default, &*other);
+    }
+
     #[test]
     fn test_generate_salt_unique() {
         let salt1 = generate_salt();
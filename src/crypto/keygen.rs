@@ -0,0 +1,119 @@
+use zeroize::Zeroizing;
+
+use crate::error::{CryptoKeeperError, Result};
+
+/// Word count of generated seed phrases.
+pub const MNEMONIC_WORDS: usize = 24;
+
+/// Generate a new English BIP39 seed phrase.
+pub fn generate_mnemonic() -> Result<Zeroizing<String>> {
+    let mnemonic = bip39::Mnemonic::generate(MNEMONIC_WORDS)
+        .map_err(|e| CryptoKeeperError::DerivationFailed(e.to_string()))?;
+    Ok(Zeroizing::new(mnemonic.to_string()))
+}
+
+/// Generate a private key for `network` (a built-in network after
+/// `resolve_network`), encoded the way its wallets export one: WIF for
+/// Bitcoin, a base58 keypair for Solana and hex for the other secp256k1
+/// chains. Returns Ok(None) when this build cannot derive `network` addresses.
+#[cfg_attr(
+    not(any(
+        feature = "derive-eth",
+        feature = "derive-btc",
+        feature = "derive-sol",
+        feature = "derive-cosmos",
+        feature = "derive-xrp"
+    )),
+    allow(unused_variables)
+)]
+pub fn generate_private_key(network: &str) -> Result<Option<Zeroizing<String>>> {
+    let network = network.to_lowercase();
+
+    #[cfg(feature = "derive-btc")]
+    if matches!(network.as_str(), "bitcoin" | "btc") {
+        let key = bitcoin::secp256k1::SecretKey::from_slice(&secp256k1_key()[..])
+            .map_err(|e| CryptoKeeperError::DerivationFailed(e.to_string()))?;
+        let wif = bitcoin::PrivateKey::new(key, bitcoin::Network::Bitcoin).to_wif();
+        return Ok(Some(Zeroizing::new(wif)));
+    }
+
+    #[cfg(feature = "derive-sol")]
+    if matches!(network.as_str(), "solana" | "sol") {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&random_key());
+        let keypair = Zeroizing::new(signing_key.to_keypair_bytes());
+        return Ok(Some(Zeroizing::new(bs58::encode(&keypair[..]).into_string())));
+    }
+
+    #[cfg(any(feature = "derive-eth", feature = "derive-cosmos", feature = "derive-xrp"))]
+    {
+        use crate::crypto::derive::derive_address;
+        use crate::vault::model::SecretType;
+
+        let key = Zeroizing::new(hex::encode(&secp256k1_key()[..]));
+        if derive_address(&key, &SecretType::PrivateKey, &network)?.is_some() {
+            return Ok(Some(key));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Random bytes for a new key.
+#[cfg(any(
+    feature = "derive-eth",
+    feature = "derive-btc",
+    feature = "derive-sol",
+    feature = "derive-cosmos",
+    feature = "derive-xrp"
+))]
+fn random_key() -> Zeroizing<[u8; 32]> {
+    use rand::RngCore;
+    let mut key = Zeroizing::new([0u8; 32]);
+    rand::thread_rng().fill_bytes(key.as_mut());
+    key
+}
+
+/// Random secp256k1 private key that passes `check_key_sanity`. Rejections
+/// are astronomically rare, but one must never be handed out.
+#[cfg(any(
+    feature = "derive-eth",
+    feature = "derive-btc",
+    feature = "derive-cosmos",
+    feature = "derive-xrp"
+))]
+fn secp256k1_key() -> Zeroizing<[u8; 32]> {
+    loop {
+        let key = random_key();
+        if crate::crypto::derive::check_key_sanity(&key).is_ok() {
+            return key;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::derive::derive_address;
+    use crate::vault::model::SecretType;
+
+    #[test]
+    fn generated_mnemonic_is_valid() {
+        let phrase = generate_mnemonic().unwrap();
+        assert_eq!(phrase.split_whitespace().count(), MNEMONIC_WORDS);
+        assert!(bip39::Mnemonic::parse(phrase.as_str()).is_ok());
+        assert_ne!(*phrase, *generate_mnemonic().unwrap());
+    }
+
+    #[test]
+    fn generated_keys_derive_on_their_network() {
+        for (network, compiled) in crate::crypto::derive::report_networks() {
+            let key = generate_private_key(network).unwrap();
+            assert_eq!(key.is_some(), compiled, "{}", network);
+            if let Some(key) = key {
+                let address = derive_address(&key, &SecretType::PrivateKey, network).unwrap();
+                assert!(address.is_some(), "{}", network);
+            }
+        }
+        assert!(generate_private_key("Other").unwrap().is_none());
+    }
+}
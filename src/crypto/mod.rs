@@ -2,5 +2,6 @@ pub mod cipher;
 pub mod derive;
 pub mod entry_key;
 pub mod kdf;
+pub mod keygen;
 pub mod recovery;
 pub mod secure;
@@ -1,6 +1,8 @@
+use subtle::ConstantTimeEq;
 use zeroize::Zeroizing;
 
-use crate::crypto::{cipher, kdf};
+use crate::crypto::kdf::{self, KdfParams};
+use crate::crypto::cipher;
 use crate::error::{CryptoKeeperError, Result};
 
 pub const MIN_ANSWER_LENGTH: usize = 3;
@@ -15,39 +17,88 @@ pub fn normalize_answer(answer: &str) -> String {
         .join(" ")
 }
 
-/// Argon2 params for recovery (lighter than vault KDF for interactive use).
-fn recovery_params() -> (u32, u32, u32) {
+/// Message shown while recovery is locked after too many wrong answers.
+pub fn cooldown_message(secs: u64) -> String {
+    format!(
+        "Too many wrong answers. Try again in {}m {:02}s.",
+        secs / 60,
+        secs % 60
+    )
+}
+
+/// Argon2 params recovery was set up with before they were stored alongside it
+/// (16 MB, 2 iterations, 1 lane).
+pub const LEGACY_KDF: KdfParams = KdfParams {
+    m_cost: 16384,
+    t_cost: 2,
+    p_cost: 1,
+};
+
+/// Argon2 params for new recovery setups. An answer is short and guessable, and
+/// anyone holding the config can try guesses offline, so this costs more than
+/// unlocking the vault; it is only paid when recovering (128 MB, 4 iterations,
+/// 4 lanes).
+pub fn kdf_params() -> KdfParams {
     if cfg!(test) {
-        (1024, 1, 1)
+        KdfParams {
+            m_cost: 1024,
+            t_cost: 1,
+            p_cost: 1,
+        }
     } else {
-        (16384, 2, 1) // 16 MB, 2 iterations, 1 lane
+        KdfParams {
+            m_cost: 131072,
+            t_cost: 4,
+            p_cost: 4,
+        }
     }
 }
 
-/// Hash a normalized answer with Argon2 for verification.
-pub fn hash_answer(answer: &str, salt: &[u8]) -> Result<Vec<u8>> {
+/// Argon2 key for an answer. Normalizes first so setup and verify always
+/// agree, even if a caller passes the raw input.
+fn answer_key(answer: &str, salt: &[u8], params: KdfParams) -> Result<Zeroizing<[u8; 32]>> {
     let mut salt_arr = [0u8; 32];
     let copy_len = salt.len().min(32);
     salt_arr[..copy_len].copy_from_slice(&salt[..copy_len]);
-    let (m, t, p) = recovery_params();
-    let key = kdf::derive_key(answer.as_bytes(), &salt_arr, m, t, p)?;
-    Ok(key.to_vec())
+    let normalized = Zeroizing::new(normalize_answer(answer));
+    kdf::derive_key(
+        normalized.as_bytes(),
+        &salt_arr,
+        params.m_cost,
+        params.t_cost,
+        params.p_cost,
+    )
 }
 
-/// Verify a normalized answer against a stored hash.
-pub fn verify_answer(answer: &str, salt: &[u8], expected_hash: &[u8]) -> Result<bool> {
-    let hash = hash_answer(answer, salt)?;
-    Ok(hash == expected_hash)
+/// Hash an answer with Argon2 for verification.
+pub fn hash_answer(answer: &str, salt: &[u8], params: KdfParams) -> Result<Vec<u8>> {
+    Ok(answer_key(answer, salt, params)?.to_vec())
+}
+
+/// Verify an answer against a stored hash in constant time.
+pub fn verify_answer(
+    answer: &str,
+    salt: &[u8],
+    expected_hash: &[u8],
+    params: KdfParams,
+) -> Result<bool> {
+    let hash = answer_key(answer, salt, params)?;
+    Ok(hash_matches(&*hash, expected_hash))
+}
+
+/// Constant-time comparison; slices of different lengths never match.
+fn hash_matches(hash: &[u8], expected: &[u8]) -> bool {
+    hash.ct_eq(expected).into()
 }
 
 /// Encrypt the master key under a recovery-answer-derived key.
 pub fn create_recovery_blob(
     master_key: &[u8; 32],
     answer: &str,
+    params: KdfParams,
 ) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
     let salt = kdf::generate_salt();
-    let (m, t, p) = recovery_params();
-    let recovery_key = kdf::derive_key(answer.as_bytes(), &salt, m, t, p)?;
+    let recovery_key = answer_key(answer, &salt, params)?;
     let nonce = cipher::generate_nonce();
     let blob = cipher::encrypt(&*recovery_key, &nonce, master_key)?;
     Ok((blob, nonce.to_vec(), salt.to_vec()))
@@ -59,12 +110,9 @@ pub fn decrypt_recovery_blob(
     nonce: &[u8],
     salt: &[u8],
     answer: &str,
+    params: KdfParams,
 ) -> Result<Zeroizing<[u8; 32]>> {
-    let mut salt_arr = [0u8; 32];
-    let copy_len = salt.len().min(32);
-    salt_arr[..copy_len].copy_from_slice(&salt[..copy_len]);
-    let (m, t, p) = recovery_params();
-    let recovery_key = kdf::derive_key(answer.as_bytes(), &salt_arr, m, t, p)?;
+    let recovery_key = answer_key(answer, salt, params)?;
     let mut nonce_arr = [0u8; 24];
     let nonce_len = nonce.len().min(24);
     nonce_arr[..nonce_len].copy_from_slice(&nonce[..nonce_len]);
@@ -96,25 +144,54 @@ mod tests {
     #[test]
     fn test_hash_and_verify() {
         let salt = vec![42u8; 32];
-        let hash = hash_answer("fluffy", &salt).unwrap();
-        assert!(verify_answer("fluffy", &salt, &hash).unwrap());
-        assert!(!verify_answer("wrong", &salt, &hash).unwrap());
+        let hash = hash_answer("fluffy", &salt, kdf_params()).unwrap();
+        assert!(verify_answer("fluffy", &salt, &hash, kdf_params()).unwrap());
+        assert!(!verify_answer("wrong", &salt, &hash, kdf_params()).unwrap());
+    }
+
+    #[test]
+    fn test_verify_uses_the_stored_params() {
+        let salt = vec![42u8; 32];
+        let stronger = KdfParams {
+            t_cost: 2,
+            ..kdf_params()
+        };
+        let hash = hash_answer("fluffy", &salt, stronger).unwrap();
+        assert!(verify_answer("fluffy", &salt, &hash, stronger).unwrap());
+        assert!(!verify_answer("fluffy", &salt, &hash, kdf_params()).unwrap());
+    }
+
+    #[test]
+    fn test_verify_normalizes_case_and_whitespace() {
+        let salt = vec![7u8; 32];
+        let hash = hash_answer("New York City", &salt, kdf_params()).unwrap();
+        assert!(verify_answer("new york city", &salt, &hash, kdf_params()).unwrap());
+        assert!(verify_answer("  NEW  york\tCity ", &salt, &hash, kdf_params()).unwrap());
+        assert!(!verify_answer("new yorkcity", &salt, &hash, kdf_params()).unwrap());
+    }
+
+    #[test]
+    fn test_hash_matches_is_length_safe() {
+        assert!(hash_matches(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!hash_matches(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!hash_matches(&[1, 2, 3], &[1, 2]));
+        assert!(!hash_matches(&[1, 2, 3], &[]));
     }
 
     #[test]
     fn test_recovery_blob_roundtrip() {
         let master_key = [0xABu8; 32];
         let answer = "fluffy";
-        let (blob, nonce, salt) = create_recovery_blob(&master_key, answer).unwrap();
-        let recovered = decrypt_recovery_blob(&blob, &nonce, &salt, answer).unwrap();
+        let (blob, nonce, salt) = create_recovery_blob(&master_key, answer, kdf_params()).unwrap();
+        let recovered = decrypt_recovery_blob(&blob, &nonce, &salt, answer, kdf_params()).unwrap();
         assert_eq!(*recovered, master_key);
     }
 
     #[test]
     fn test_recovery_blob_wrong_answer() {
         let master_key = [0xABu8; 32];
-        let (blob, nonce, salt) = create_recovery_blob(&master_key, "fluffy").unwrap();
-        let result = decrypt_recovery_blob(&blob, &nonce, &salt, "wrong");
+        let (blob, nonce, salt) = create_recovery_blob(&master_key, "fluffy", kdf_params()).unwrap();
+        let result = decrypt_recovery_blob(&blob, &nonce, &salt, "wrong", kdf_params());
         assert!(result.is_err());
     }
 }
@@ -1,3 +1,7 @@
+use std::ops::Deref;
+
+use zeroize::{Zeroize, Zeroizing};
+
 /// Process hardening: disable core dumps and ptrace on supported platforms.
 pub fn harden_process() {
     #[cfg(target_os = "linux")]
@@ -35,26 +39,130 @@ pub fn harden_process() {
 
 /// Lock a memory region to prevent it from being swapped to disk.
 /// Safety: ptr must be valid for len bytes.
-#[allow(dead_code)]
 #[cfg(unix)]
 pub fn mlock(ptr: *const u8, len: usize) -> bool {
     unsafe { libc::mlock(ptr as *const libc::c_void, len) == 0 }
 }
 
-#[cfg(not(unix))]
+#[cfg(windows)]
+pub fn mlock(ptr: *const u8, len: usize) -> bool {
+    unsafe { win::VirtualLock(ptr as *const core::ffi::c_void, len) != 0 }
+}
+
+#[cfg(not(any(unix, windows)))]
 pub fn mlock(_ptr: *const u8, _len: usize) -> bool {
     false
 }
 
 /// Unlock a previously locked memory region.
 /// Safety: ptr must be valid for len bytes and previously locked.
-#[allow(dead_code)]
 #[cfg(unix)]
 pub fn munlock(ptr: *const u8, len: usize) -> bool {
     unsafe { libc::munlock(ptr as *const libc::c_void, len) == 0 }
 }
 
-#[cfg(not(unix))]
+#[cfg(windows)]
+pub fn munlock(ptr: *const u8, len: usize) -> bool {
+    unsafe { win::VirtualUnlock(ptr as *const core::ffi::c_void, len) != 0 }
+}
+
+#[cfg(not(any(unix, windows)))]
 pub fn munlock(_ptr: *const u8, _len: usize) -> bool {
     false
 }
+
+#[cfg(windows)]
+mod win {
+    #[link(name = "kernel32")]
+    extern "system" {
+        pub fn VirtualLock(address: *const core::ffi::c_void, size: usize) -> i32;
+        pub fn VirtualUnlock(address: *const core::ffi::c_void, size: usize) -> i32;
+    }
+}
+
+/// Lock the bytes of a decrypted secret in RAM. The lock is left in place when the
+/// string is freed: pages may be shared with other locked secrets, and unlocking
+/// one would unlock them all. Returns false if the OS refused (e.g. memlock limit).
+pub fn lock_secret(secret: &str) -> bool {
+    secret.is_empty() || mlock(secret.as_ptr(), secret.len())
+}
+
+/// A derived key kept on the heap at a fixed address so it can be locked in RAM.
+/// Locking is best-effort: if the OS refuses, the key is still usable, just swappable.
+pub struct LockedKey {
+    key: Box<Zeroizing<[u8; 32]>>,
+    locked: bool,
+}
+
+impl LockedKey {
+    pub fn new(key: Zeroizing<[u8; 32]>, lock: bool) -> Self {
+        let key = Box::new(key);
+        let locked = lock && mlock(key.as_ptr(), key.len());
+        Self { key, locked }
+    }
+
+    /// Whether the key's memory is actually locked.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+}
+
+impl Deref for LockedKey {
+    type Target = [u8; 32];
+
+    fn deref(&self) -> &[u8; 32] {
+        &self.key
+    }
+}
+
+impl Drop for LockedKey {
+    fn drop(&mut self) {
+        // Wipe before unlocking so the key never sits in swappable memory.
+        self.key.zeroize();
+        if self.locked {
+            munlock(self.key.as_ptr(), self.key.len());
+        }
+    }
+}
+
+/// Wrapper for values that may contain secret material (parse errors quoting
+/// key characters, decrypted bytes). Formatting it never prints the contents,
+/// so it is safe inside error messages, logs and panics. Only derivation
+/// errors use it.
+#[cfg(any(
+    feature = "derive-eth",
+    feature = "derive-btc",
+    feature = "derive-sol",
+    feature = "derive-tron",
+    feature = "derive-cosmos",
+    feature = "derive-xrp"
+))]
+pub struct Redacted<T>(pub T);
+
+#[cfg(any(
+    feature = "derive-eth",
+    feature = "derive-btc",
+    feature = "derive-sol",
+    feature = "derive-tron",
+    feature = "derive-cosmos",
+    feature = "derive-xrp"
+))]
+impl<T> std::fmt::Debug for Redacted<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+#[cfg(any(
+    feature = "derive-eth",
+    feature = "derive-btc",
+    feature = "derive-sol",
+    feature = "derive-tron",
+    feature = "derive-cosmos",
+    feature = "derive-xrp"
+))]
+impl<T> std::fmt::Display for Redacted<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
@@ -14,9 +14,18 @@ pub enum CryptoKeeperError {
     #[error("Invalid vault file — corrupted or wrong format.")]
     InvalidVaultFormat,
 
+    #[error("Invalid vault file — {0}.")]
+    InvalidVaultContents(String),
+
+    #[error("The decrypted vault would be {0} MB, over the {1} MB limit. If the file is genuine, raise max_vault_size_mb in the config.")]
+    VaultTooLarge(u64, u64),
+
     #[error("Entry '{0}' not found. Use `cryptokeeper list` to see entries with their index numbers.")]
     EntryNotFound(String),
 
+    #[error("'{0}' is both an entry number and the name of another entry. Use the number of the one you mean (see `cryptokeeper list`).")]
+    AmbiguousEntry(String),
+
     #[error("Entry '{0}' already exists.")]
     EntryAlreadyExists(String),
 
@@ -29,12 +38,18 @@ pub enum CryptoKeeperError {
     #[error("Password cannot be empty.")]
     EmptyPassword,
 
+    #[error("{0}")]
+    WeakPassword(String),
+
     #[error("Operation cancelled.")]
     Cancelled,
 
     #[error("Clipboard error: {0}")]
     Clipboard(String),
 
+    #[error("Clipboard access is disabled (--no-clipboard or disable_clipboard in config). Use `view --show-secret` to see the secret.")]
+    ClipboardDisabled,
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -56,11 +71,54 @@ pub enum CryptoKeeperError {
     #[error("Recovery failed: {0}")]
     RecoveryFailed(String),
 
+    #[cfg(any(
+        feature = "derive-eth",
+        feature = "derive-btc",
+        feature = "derive-sol",
+        feature = "derive-cosmos",
+        feature = "derive-xrp"
+    ))]
+    #[error("Suspicious private key: {0}")]
+    SuspiciousKey(String),
+
     #[error("Address derivation failed: {0}")]
     DerivationFailed(String),
 
+    #[error("{0}")]
+    InvalidCommand(String),
+
     #[error("Config error: {0}")]
     ConfigError(String),
+
+    #[error("Vault is open in another CryptoKeeper process (PID {0}).")]
+    VaultLocked(u32),
+
+    #[error("The vault file was changed on disk since it was opened.")]
+    VaultModified,
+
+    #[error("Cannot write to the vault directory {0}. Fix its permissions or set CRYPTOKEEPER_VAULT_DIR to a writable directory.")]
+    VaultDirNotWritable(String),
+}
+
+impl CryptoKeeperError {
+    /// Process exit status for this error, so scripts can tell common outcomes
+    /// apart. 2 is left to clap for usage errors; anything not listed is 1.
+    ///
+    /// | Code | Meaning |
+    /// |---|---|
+    /// | 3 | Entry not found |
+    /// | 4 | Wrong master or secondary password |
+    /// | 5 | Cancelled at a prompt |
+    /// | 6 | Vault not found |
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CryptoKeeperError::EntryNotFound(_) => 3,
+            CryptoKeeperError::DecryptionFailed | CryptoKeeperError::SecondaryPasswordWrong => 4,
+            CryptoKeeperError::Cancelled => 5,
+            CryptoKeeperError::VaultNotFound => 6,
+            _ => 1,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, CryptoKeeperError>;
@@ -0,0 +1,55 @@
+//! Leveled diagnostics on stderr, turned on with `-v` (info) or `-vv` (debug)
+//! and silent by default. Messages say what the program is doing: paths, sizes,
+//! counts and timings. They must never include a password, key, secret or any
+//! other decrypted value.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use colored::Colorize;
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// Set while the TUI owns the terminal, where stderr output would garble the screen
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy)]
+pub enum Level {
+    /// Progress of slow steps (`-v`)
+    Info = 1,
+    /// Paths, sizes and timings (`-vv`)
+    Debug = 2,
+}
+
+/// Set from the number of `-v` flags.
+pub fn set_verbosity(count: u8) {
+    VERBOSITY.store(count, Ordering::Relaxed);
+}
+
+pub fn set_suspended(suspended: bool) {
+    SUSPENDED.store(suspended, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    !SUSPENDED.load(Ordering::Relaxed) && VERBOSITY.load(Ordering::Relaxed) >= level as u8
+}
+
+pub fn write(level: Level, args: fmt::Arguments) {
+    if enabled(level) {
+        eprintln!("{}", format!("  {}", args).dimmed());
+    }
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => {
+        $crate::logging::write($crate::logging::Level::Info, format_args!($($arg)*))
+    };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::logging::write($crate::logging::Level::Debug, format_args!($($arg)*))
+    };
+}
+
+pub(crate) use {debug, info};
@@ -4,11 +4,12 @@ mod commands;
 mod config;
 mod crypto;
 mod error;
+mod logging;
 mod repl;
 mod ui;
 mod vault;
 
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 
 use cli::{Cli, Commands};
 use crypto::secure;
@@ -17,10 +18,27 @@ fn main() {
     secure::harden_process();
 
     let cli = Cli::parse();
+    logging::set_verbosity(cli.verbose);
+    // Checked here rather than by clap, whose check would also refuse global
+    // flags such as --config written before the subcommand
+    if cli.one_shot.is_some() && cli.command.is_some() {
+        Cli::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "-c/--command cannot be used with a subcommand",
+            )
+            .exit();
+    }
+
+    // Everything that reads or writes the config goes through config_path()
+    if let Some(path) = &cli.config_file {
+        std::env::set_var(config::CONFIG_ENV, path);
+    }
 
     // In REPL mode, the REPL handles its own header display after auth.
     // In CLI mode, clear screen and show header immediately.
-    if cli.command.is_some() {
+    // Completion helpers must print nothing but their results.
+    if cli.command.is_some() && !matches!(cli.command, Some(Commands::CompleteNames)) {
         ui::setup_app_theme(true);
     }
 
@@ -35,35 +53,190 @@ fn main() {
         }
     }
 
+    logging::debug!("Config: {}", config::config_path().display());
+    logging::debug!("Vault: {}", vault::storage::vault_path().display());
+
+    // Copy paths check this alongside the config's disable_clipboard
+    if cli.no_clipboard {
+        std::env::set_var(clipboard::NO_CLIPBOARD_ENV, "1");
+    }
+
+    // Catch an unwritable vault directory before any prompts, not at save time.
+    // The interactive session always writes (lock file, saves).
+    if cli.command.as_ref().is_none_or(Commands::writes_vault) {
+        if let Err(e) = vault::storage::check_vault_dir_writable() {
+            ui::borders::print_error(&e.to_string() as &str);
+            std::process::exit(e.exit_code());
+        }
+    }
+
+    // Warn about a vault readable by other users, e.g. one copied in from elsewhere,
+    // and about a save that was interrupted before it replaced the vault.
+    if !matches!(cli.command, Some(Commands::CompleteNames)) {
+        warn_loose_permissions();
+        check_orphaned_temp();
+    }
+
     let result = match cli.command {
-        None => repl::run(),
+        None => match cli.one_shot {
+            Some(ref line) => repl::run_once(line),
+            None => repl::run(),
+        },
         Some(cmd) => match cmd {
             Commands::Init => commands::init::run(),
             Commands::Add => commands::add::run(),
-            Commands::List { ref filter } => commands::list::run(filter.as_deref()),
-            Commands::View { ref name } => commands::view::run(name),
+            Commands::List {
+                ref filter,
+                archived,
+            } => commands::list::run(filter.as_deref(), archived),
+            Commands::View {
+                ref name,
+                show_secret,
+            } => commands::view::run(name, show_secret),
             Commands::Edit { ref name } => commands::edit::run(name),
             Commands::Rename {
                 ref old_name,
                 ref new_name,
             } => commands::rename::run(old_name, new_name),
             Commands::Delete { ref name } => commands::delete::run(name),
-            Commands::Copy { ref name } => commands::copy::run(name),
-            Commands::Search { ref query } => commands::search::run(query),
-            Commands::Export { ref directory } => commands::export::run(directory),
-            Commands::Import { ref file } => commands::import::run(file),
+            Commands::Copy {
+                ref name,
+                print_fd: None,
+            } => commands::copy::run(name),
+            Commands::Copy {
+                ref name,
+                print_fd: Some(fd),
+            } => commands::copy::run_print_fd(name, fd),
+            Commands::Search { ref query, deep } => commands::search::run(query, deep),
+            Commands::Export {
+                ref directory,
+                armor,
+            } => match directory {
+                _ if armor => commands::export::run_armor(),
+                Some(directory) => commands::export::run(directory),
+                None => commands::export::run_default(),
+            },
+            Commands::ExportEntry {
+                ref name,
+                ref directory,
+            } => commands::export::run_entry(name, directory),
+            Commands::Import {
+                ref file,
+                from_vault,
+            } => commands::import::run(file, from_vault),
             Commands::Passwd => commands::passwd::run(),
             Commands::Recover => commands::recover::run(),
             Commands::Config {
                 show,
                 ref clipboard_timeout,
-            } => commands::config_cmd::run(show, *clipboard_timeout),
-            Commands::Derive { ref name } => commands::derive::run(name),
+                ref add_network,
+                ref remove_network,
+                ref export_dir,
+                ref columns,
+            } => commands::config_cmd::run(
+                show,
+                *clipboard_timeout,
+                add_network.as_deref(),
+                remove_network.as_deref(),
+                export_dir.as_deref(),
+                columns.as_deref(),
+            ),
+            Commands::Stats { timings } => commands::stats::run(timings),
+            Commands::Derive {
+                ref name,
+                all,
+                account,
+                index,
+            } => commands::derive::run(name, all, account, index),
+            Commands::Rederive { ref network } => commands::rederive::run(network.as_deref()),
+            Commands::CompleteNames => commands::complete::run_names(),
         },
     };
 
     if let Err(e) = result {
         ui::borders::print_error(&e.to_string() as &str);
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
+    }
+}
+
+/// Report a `vault.tmp` left by an interrupted save and, on a terminal, offer
+/// to restore or delete it.
+fn check_orphaned_temp() {
+    let Some(orphan) = vault::storage::orphaned_vault_temp() else {
+        return;
+    };
+    let describe = |entries: Option<usize>| match entries {
+        Some(n) => format!("a vault with {} entries", n),
+        None => "nothing readable".to_string(),
+    };
+    ui::borders::print_warning(&format!(
+        "{} was left by a save that did not finish. It holds {}; the vault holds {}.",
+        orphan.path.display(),
+        describe(orphan.temp_entries),
+        describe(orphan.vault_entries)
+    ));
+    if !ui::is_interactive() {
+        return;
+    }
+
+    // Restoring only makes sense when the leftover is a vault and the real one isn't
+    let can_restore = orphan.temp_entries.is_some() && orphan.vault_entries.is_none();
+    let mut options = vec!["Delete the leftover file", "Leave it for now"];
+    if can_restore {
+        options.insert(0, "Restore it as the vault");
+    }
+    let choice = dialoguer::Select::new()
+        .with_prompt("What should happen to it?")
+        .items(&options)
+        .default(0)
+        .interact()
+        .ok()
+        .map(|i| options[i]);
+    let result = match choice {
+        Some("Restore it as the vault") => orphan.restore().map(|kept| {
+            let mut message = "Restored the unfinished save as the vault.".to_string();
+            if let Some(kept) = kept {
+                message.push_str(&format!(" The old file was kept as {}.", kept.display()));
+            }
+            ui::borders::print_success(&message);
+        }),
+        Some("Delete the leftover file") => orphan.discard(),
+        _ => Ok(()),
+    };
+    if let Err(e) = result {
+        ui::borders::print_error(&format!("{}: {}", orphan.path.display(), e));
+    }
+}
+
+/// Print a warning for each vault path with permissions broader than owner-only
+/// and, on a terminal, offer to tighten them.
+fn warn_loose_permissions() {
+    let loose = vault::storage::loose_vault_permissions();
+    if loose.is_empty() {
+        return;
+    }
+    for item in &loose {
+        ui::borders::print_warning(&format!(
+            "{} has mode {:o}; other users may be able to read it (expected {:o})",
+            item.path.display(),
+            item.mode,
+            item.wanted_mode()
+        ));
+    }
+    if !ui::is_interactive() {
+        return;
+    }
+    let fix = dialoguer::Confirm::new()
+        .with_prompt("Restrict vault permissions to your user?")
+        .default(false)
+        .interact()
+        .unwrap_or(false);
+    if !fix {
+        return;
+    }
+    for item in &loose {
+        if let Err(e) = item.fix() {
+            ui::borders::print_error(&format!("{}: {}", item.path.display(), e));
+        }
     }
 }
@@ -1,10 +1,198 @@
-use crate::error::Result;
+use clap::Parser;
+use dialoguer::Confirm;
+
+use crate::cli::{Cli, Commands};
+use crate::commands;
+use crate::error::{CryptoKeeperError, Result};
+use crate::logging;
 use crate::ui;
+use crate::vault::lock;
+use crate::vault::model::VaultData;
+use crate::vault::storage;
 
 pub fn run() -> Result<()> {
-    let app = ui::app::App::new()?;
+    // Held for the whole session; dropping it removes the lock file.
+    let (_lock, read_only) = match lock::acquire() {
+        Ok(lock) => (Some(lock), false),
+        Err(CryptoKeeperError::VaultLocked(pid)) => {
+            ui::borders::print_error(&format!(
+                "The vault is already open in another CryptoKeeper process (PID {}).",
+                pid
+            ));
+            let open = Confirm::new()
+                .with_prompt("Open read-only?")
+                .default(true)
+                .interact()
+                .map_err(|e| CryptoKeeperError::Io(std::io::Error::other(e)))?;
+            if !open {
+                return Err(CryptoKeeperError::Cancelled);
+            }
+            (None, true)
+        }
+        Err(e) => return Err(e),
+    };
+
+    let app = ui::app::App::new(read_only)?;
     let mut terminal = ui::terminal::init()?;
     let result = app.run(&mut terminal);
+    // The title showed the entry count while unlocked
+    ui::theme::set_title(ui::theme::APP_TITLE);
     ui::terminal::restore()?;
     result
 }
+
+/// Unlock once, run a single command line such as `list password`, and exit.
+/// The vault is saved only if the command changed it.
+pub fn run_once(line: &str) -> Result<()> {
+    let command = parse_command(line)?;
+    let (mut vault, password) = storage::prompt_and_unlock()?;
+    if dispatch(&mut vault, &command)? {
+        logging::info!("Saving vault...");
+        crate::commands::save_vault(&vault, password.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Parse a command line with the same grammar as the `cryptokeeper` subcommands.
+fn parse_command(line: &str) -> Result<Commands> {
+    let words = split_words(line)?;
+    let cli = Cli::try_parse_from(std::iter::once("cryptokeeper".to_string()).chain(words))
+        .map_err(|e| {
+            let message = e.to_string();
+            CryptoKeeperError::InvalidCommand(message.trim_start_matches("error: ").trim_end().into())
+        })?;
+    cli.command
+        .ok_or_else(|| CryptoKeeperError::InvalidCommand("No command given.".into()))
+}
+
+/// Split on whitespace, keeping single- or double-quoted text together.
+/// Outside single quotes, a backslash before a quote or another backslash
+/// escapes it, so `"say \"hi\""` is one word. Other backslashes are kept,
+/// leaving Windows paths intact.
+fn split_words(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        let escaped = c == '\\' && quote != Some('\'') && matches!(chars.peek(), Some('"' | '\'' | '\\'));
+        match quote {
+            _ if escaped => {
+                current.extend(chars.next());
+                in_word = true;
+            }
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_word = true;
+            }
+            None if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err(CryptoKeeperError::InvalidCommand("Unterminated quote in command.".into()));
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+/// Run a command against an unlocked vault. Returns true if the vault was modified.
+fn dispatch(vault: &mut VaultData, command: &Commands) -> Result<bool> {
+    match command {
+        Commands::List { filter, archived } => {
+            commands::list::run_with_vault(vault, filter.as_deref(), *archived)?
+        }
+        Commands::View { name, show_secret } => {
+            commands::view::run_with_vault(vault, name, *show_secret)?
+        }
+        Commands::Copy { name, print_fd: None } => {
+            commands::copy::run_with_vault(vault, name, true)?
+        }
+        Commands::Copy {
+            name,
+            print_fd: Some(fd),
+        } => commands::copy::print_fd_with_vault(vault, name, *fd)?,
+        Commands::Search { query, deep: false } => commands::search::run_with_vault(vault, query)?,
+        Commands::Search { query, deep: true } => commands::search::run_deep_with_vault(vault, query)?,
+        Commands::Stats { .. } => commands::stats::run_with_vault(vault)?,
+        Commands::Export {
+            directory: Some(directory),
+            armor: false,
+        } => commands::export::run_with_vault(vault, directory)?,
+        Commands::Add => {
+            // Every path that adds nothing returns an error
+            commands::add::run_with_vault(vault)?;
+            return Ok(true);
+        }
+        Commands::Edit { name } => return commands::edit::run_with_vault(vault, name),
+        Commands::Rename { old_name, new_name } => {
+            return commands::rename::run_with_vault(vault, old_name, new_name);
+        }
+        Commands::Delete { name } => {
+            commands::delete::run_with_vault(vault, name)?;
+            return Ok(true);
+        }
+        Commands::Import { file, from_vault } => {
+            return commands::import::run_with_vault(vault, file, *from_vault);
+        }
+        _ => {
+            return Err(CryptoKeeperError::InvalidCommand(
+                "This command cannot run with -c; run it as `cryptokeeper <command>` instead."
+                    .into(),
+            ))
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(line: &str) -> Vec<String> {
+        split_words(line).unwrap()
+    }
+
+    #[test]
+    fn quoted_arguments_stay_together() {
+        assert_eq!(words("rename 'Old Name'  \"New Name\""), ["rename", "Old Name", "New Name"]);
+        assert_eq!(words("view a\"b c\"d"), ["view", "ab cd"]);
+        assert_eq!(words("view ''"), ["view", ""]);
+    }
+
+    #[test]
+    fn escaped_quotes_and_backslashes() {
+        assert_eq!(words(r#"rename a "say \"hi\"""#), ["rename", "a", "say \"hi\""]);
+        assert_eq!(words(r"view it\'s"), ["view", "it's"]);
+        assert_eq!(words(r"export C:\backups\\x"), ["export", r"C:\backups\x"]);
+        // Single quotes take everything literally
+        assert_eq!(words(r"view 'a\b'"), ["view", r"a\b"]);
+    }
+
+    #[test]
+    fn unterminated_quote_is_an_error() {
+        assert!(split_words("view 'Cold Wallet").is_err());
+        assert!(split_words(r#"view "a\""#).is_err());
+    }
+
+    #[test]
+    fn empty_input() {
+        assert!(words("").is_empty());
+        assert!(words("   ").is_empty());
+        assert!(matches!(parse_command("  "), Err(CryptoKeeperError::InvalidCommand(_))));
+        assert!(matches!(parse_command("list"), Ok(Commands::List { .. })));
+    }
+}
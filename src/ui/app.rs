@@ -1,33 +1,149 @@
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
 use ratatui::Frame;
+use std::collections::HashSet;
+use std::sync::mpsc;
 use std::time::{Duration, Instant};
 use zeroize::Zeroizing;
 
 use crate::config::model::Config;
+use crate::crypto::kdf::KdfAlgorithm;
+use crate::crypto::secure::{self, LockedKey};
 use crate::error::{CryptoKeeperError, Result};
+use crate::ui::columns::{configured_columns, DASHBOARD_COLUMNS};
 use crate::ui::terminal::Tui;
-use crate::vault::model::{Entry, VaultData};
+use crate::ui::theme::{self, Mask};
+use crate::vault::model::{Entry, EntryMeta, SecretType, VaultData};
 use crate::vault::storage;
 
 use super::screens::{
     add_entry::AddEntryScreen, confirm::ConfirmScreen, edit_entry::EditEntryScreen,
+    import_preview::{ImportPreviewAction, ImportPreviewScreen},
     input::InputScreen, login::LoginScreen, recovery::RecoveryScreen,
     recovery_setup::RecoverySetupScreen, settings::SettingsScreen,
     view_entry::ViewEntryScreen, view_password::ViewPasswordScreen,
     wizard::{WizardScreen, WizardAction},
 };
-use super::widgets::dashboard::Dashboard;
+use super::widgets::{dashboard::Dashboard, help_overlay::HelpOverlay};
 
 pub struct Session {
     pub vault: VaultData,
     password: Zeroizing<String>,
-    key: Zeroizing<[u8; 32]>,
+    key: LockedKey,
     salt: [u8; 32],
+    /// Argon2 variant `key` was derived with
+    kdf_algorithm: KdfAlgorithm,
+    /// Vault file fingerprint as of the last read or write
+    fingerprint: Option<storage::Fingerprint>,
+    /// Lock the key and decrypted secrets in RAM (from `Config::lock_memory`)
+    lock_memory: bool,
+    /// When the vault was decrypted, shown in the dashboard status bar
+    unlocked_at: chrono::DateTime<chrono::Local>,
+    /// Format version the last save upgraded the vault file from, until reported
+    upgraded_from: Option<u32>,
+    /// Masked entries whose secondary password was entered, listed in full
+    revealed: HashSet<String>,
 }
 
 impl Session {
-    pub fn save(&self) -> Result<()> {
-        storage::save_vault_with_key(&self.vault, &*self.key, &self.salt)
+    fn new(
+        vault: VaultData,
+        password: Zeroizing<String>,
+        key: Zeroizing<[u8; 32]>,
+        salt: [u8; 32],
+        kdf_algorithm: KdfAlgorithm,
+        lock_memory: bool,
+    ) -> Self {
+        let session = Self {
+            vault,
+            password,
+            key: LockedKey::new(key, lock_memory),
+            salt,
+            kdf_algorithm,
+            fingerprint: storage::fingerprint(&storage::vault_path()).ok(),
+            lock_memory,
+            unlocked_at: chrono::Local::now(),
+            upgraded_from: None,
+            revealed: HashSet::new(),
+        };
+        session.lock_secrets();
+        session
+    }
+
+    /// Dashboard listing, with masked entries hidden until revealed.
+    fn listing(&self) -> Vec<EntryMeta> {
+        self.vault.metadata_revealing(|e| self.revealed.contains(&e.name))
+    }
+
+    /// True if `entry` is masked and its secondary password hasn't been entered.
+    fn is_hidden(&self, entry: &Entry) -> bool {
+        entry.mask_metadata && !self.revealed.contains(&entry.name)
+    }
+
+    /// Best-effort mlock of every decrypted secret currently in the vault.
+    fn lock_secrets(&self) {
+        if self.lock_memory {
+            for entry in &self.vault.entries {
+                secure::lock_secret(&entry.secret);
+            }
+        }
+    }
+
+    /// True if memory locking was requested but the OS refused it.
+    fn memory_lock_failed(&self) -> bool {
+        self.lock_memory && !self.key.is_locked()
+    }
+
+    /// Save the vault, refusing with `VaultModified` if the file changed on disk
+    /// since this session last read or wrote it.
+    pub fn save(&mut self) -> Result<()> {
+        self.ensure_unmodified()?;
+        self.force_save()
+    }
+
+    /// Save the vault even if the file changed on disk.
+    fn force_save(&mut self) -> Result<()> {
+        if let Some(from) = storage::save_vault_with_key(&self.vault, &*self.key, &self.salt, self.kdf_algorithm)? {
+            self.upgraded_from = Some(from);
+        }
+        self.fingerprint = storage::fingerprint(&storage::vault_path()).ok();
+        Ok(())
+    }
+
+    /// Discard in-memory changes and re-read the vault from disk.
+    fn reload(&mut self) -> Result<()> {
+        let raw = std::fs::read(storage::vault_path())?;
+        self.vault = storage::read_vault_with_key(&self.key, &raw)?;
+        self.fingerprint = storage::fingerprint(&storage::vault_path()).ok();
+        self.lock_secrets();
+        Ok(())
+    }
+
+    /// Re-encrypt the vault under a new master password and cache the new key.
+    fn change_password(&mut self, password: Zeroizing<String>) -> Result<()> {
+        self.ensure_unmodified()?;
+        let salt = crate::crypto::kdf::generate_salt();
+        let key = crate::crypto::kdf::derive_key_with(
+            password.as_bytes(),
+            &salt,
+            crate::crypto::kdf::DEFAULT_M_COST,
+            crate::crypto::kdf::DEFAULT_T_COST,
+            crate::crypto::kdf::DEFAULT_P_COST,
+            KdfAlgorithm::DEFAULT,
+        )?;
+        self.key = LockedKey::new(key, self.lock_memory);
+        self.salt = salt;
+        self.kdf_algorithm = KdfAlgorithm::DEFAULT;
+        self.password = password;
+        self.force_save()
+    }
+
+    fn ensure_unmodified(&self) -> Result<()> {
+        match self.fingerprint {
+            Some(expected) if storage::fingerprint(&storage::vault_path()).ok() != Some(expected) => {
+                Err(CryptoKeeperError::VaultModified)
+            }
+            _ => Ok(()),
+        }
     }
 }
 
@@ -43,6 +159,28 @@ pub struct App {
     pending_view_entry_idx: Option<usize>,
     /// Entry index pending secondary password verification for copy
     pending_copy_entry_idx: Option<usize>,
+    /// In-flight background unlock, the password it was started with and when
+    pending_unlock: Option<(mpsc::Receiver<storage::UnlockResult>, Zeroizing<String>, Instant)>,
+    /// Another process holds the vault lock; changes are refused
+    read_only: bool,
+    /// Entry whose secret is on the clipboard and when it was copied (never the value)
+    last_copied: Option<(String, Instant)>,
+    /// Time of the last keypress, for hiding an idle revealed secret
+    last_input: Instant,
+    /// The current screen holds input that has not been saved
+    dirty: bool,
+    /// Screen a confirmation was raised over, restored if the user answers No
+    confirm_from_view: Option<Box<AppView>>,
+    /// Key list for the current screen, drawn over it until closed
+    help_overlay: Option<HelpOverlay>,
+    /// Terminal title last written, so it is only re-sent when it changes
+    window_title: String,
+    /// The cover is up because the window lost focus, so regaining it lifts it
+    blanked_by_focus: bool,
+    /// The dashboard lists archived entries instead of the ones in use
+    show_archived: bool,
+    /// Entry last viewed, not yet written to `config.last_selected_entry`
+    pending_last_selected: Option<String>,
 }
 
 pub enum AppView {
@@ -59,15 +197,24 @@ pub enum AppView {
     RecoverySetup(RecoverySetupScreen),
     Message { title: String, message: String, is_error: bool },
     Help,
-    CopyCountdown { entry_name: String, seconds_left: u8 },
+    /// Remaining time is read from `clipboard_clear_time` on every draw
+    CopyCountdown { entry_name: String },
     Search(String),
     Input(InputScreen, InputPurpose),
+    /// A decrypted backup's entries, listed before any are imported
+    ImportPreview(ImportPreviewScreen),
+    /// Idle cover over the unlocked vault; the covered screen comes back on any key
+    Blanked(Box<AppView>),
 }
 
 #[derive(Clone)]
 pub enum InputPurpose {
     ExportPath,
     ExportPassword,
+    /// Directory for a single-entry export of the named entry
+    ExportEntryPath(String),
+    /// Backup password for a single-entry export of the named entry
+    ExportEntryPassword(String),
     ImportPath,
     ImportPassword,
     ChangePassword,
@@ -75,18 +222,18 @@ pub enum InputPurpose {
 }
 
 impl App {
-    pub fn new() -> Result<Self> {
+    pub fn new(read_only: bool) -> Result<Self> {
         let config = crate::config::load_config()?;
 
         let view = if !config.first_run_complete && !storage::vault_exists() {
-            AppView::Wizard(WizardScreen::new())
+            AppView::Wizard(WizardScreen::new(config.password_policy.clone()))
         } else if !storage::vault_exists() {
             return Err(CryptoKeeperError::Io(std::io::Error::new(
                 std::io::ErrorKind::NotFound,
                 "No vault found. Run `cryptokeeper init` to create one.",
             )));
         } else {
-            AppView::Login(LoginScreen::new())
+            AppView::Login(LoginScreen::new(config.recovery.is_some()))
         };
 
         Ok(Self {
@@ -99,11 +246,23 @@ impl App {
             pending_new_password: None,
             pending_view_entry_idx: None,
             pending_copy_entry_idx: None,
+            pending_unlock: None,
+            read_only,
+            last_copied: None,
+            last_input: Instant::now(),
+            dirty: false,
+            confirm_from_view: None,
+            help_overlay: None,
+            window_title: String::new(),
+            blanked_by_focus: false,
+            show_archived: false,
+            pending_last_selected: None,
         })
     }
 
     pub fn run(mut self, terminal: &mut Tui) -> Result<()> {
         loop {
+            self.sync_window_title();
             terminal.draw(|frame| self.render(frame))?;
 
             if self.should_quit {
@@ -114,40 +273,73 @@ impl App {
                 if Instant::now() >= clear_time {
                     self.clear_clipboard()?;
                     self.clipboard_clear_time = None;
-                    self.view = AppView::Dashboard(Dashboard::new(
-                        self.session.as_ref().unwrap().vault.metadata(),
-                    ));
+                    self.last_copied = None;
+                    if matches!(self.view, AppView::CopyCountdown { .. }) {
+                        self.return_to_dashboard();
+                    }
                 }
             }
 
+            if self.reveal_expired() {
+                self.return_to_dashboard();
+            }
+
+            if self.blank_due() {
+                self.blank();
+            }
+
+            self.poll_unlock()?;
+
+            // Idle polls time out well inside a second, so the countdown redraws
+            // several times per displayed tick even without key events.
             if event::poll(Duration::from_millis(100))? {
-                if let Event::Key(key) = event::read()? {
-                    if key.kind != KeyEventKind::Release {
-                        self.handle_key(key.code, key.modifiers)?;
+                let result = match event::read()? {
+                    Event::Key(key) if key.kind != KeyEventKind::Release => {
+                        Some(self.handle_key(key.code, key.modifiers))
                     }
-                }
-            } else if let AppView::CopyCountdown { entry_name, seconds_left } = &self.view {
-                if let Some(clear_time) = self.clipboard_clear_time {
-                    let remaining = clear_time.saturating_duration_since(Instant::now());
-                    let new_seconds = remaining.as_secs() as u8;
-                    if new_seconds != *seconds_left {
-                        self.view = AppView::CopyCountdown {
-                            entry_name: entry_name.clone(),
-                            seconds_left: new_seconds,
-                        };
+                    Event::Paste(text) => Some(self.handle_paste(Zeroizing::new(text))),
+                    Event::FocusLost => {
+                        if self.config.blank_on_focus_loss && self.can_blank() {
+                            self.blank();
+                            self.blanked_by_focus = true;
+                        }
+                        None
+                    }
+                    Event::FocusGained => {
+                        if self.blanked_by_focus {
+                            self.unblank();
+                        }
+                        None
                     }
+                    _ => None,
+                };
+                if let Some(result) = result {
+                    self.last_input = Instant::now();
+                    match result {
+                        Err(CryptoKeeperError::VaultModified) => self.confirm_overwrite(),
+                        result => result?,
+                    }
+                    self.dirty = self.has_pending_input();
+                    self.report_upgrade();
                 }
             }
         }
 
-        Ok(())
+        self.save_last_selected()
     }
 
     fn render(&mut self, frame: &mut Frame) {
+        let clipboard_status = self.clipboard_status();
+        let seconds_left = self.clipboard_seconds_left().unwrap_or(0);
+        let unlocked_at = self.session.as_ref().map(|s| s.unlocked_at);
         match &mut self.view {
             AppView::Wizard(wizard) => wizard.render(frame),
             AppView::Login(login) => login.render(frame),
-            AppView::Dashboard(dashboard) => dashboard.render(frame),
+            AppView::Dashboard(dashboard) => {
+                dashboard.set_clipboard_status(clipboard_status);
+                dashboard.set_unlocked_at(unlocked_at);
+                dashboard.render(frame);
+            }
             AppView::AddEntry(add_entry) => add_entry.render(frame),
             AppView::ViewEntry(view_entry) => view_entry.render(frame),
             AppView::EditEntry(edit_entry) => edit_entry.render(frame),
@@ -165,9 +357,8 @@ impl App {
             AppView::Help => {
                 Self::render_help_static(frame);
             }
-            AppView::CopyCountdown { entry_name, seconds_left } => {
+            AppView::CopyCountdown { entry_name } => {
                 let entry_name = entry_name.clone();
-                let seconds_left = *seconds_left;
                 Self::render_copy_countdown_static(frame, &entry_name, seconds_left);
             }
             AppView::Search(query) => {
@@ -177,12 +368,71 @@ impl App {
             AppView::Input(input, _) => {
                 input.render(frame);
             }
+            AppView::ImportPreview(preview) => preview.render(frame),
+            AppView::Blanked(_) => Self::render_blanked_static(frame),
+        }
+
+        if let Some(overlay) = &self.help_overlay {
+            if !matches!(self.view, AppView::Blanked(_)) {
+                overlay.render(frame);
+            }
+        }
+    }
+
+    /// Help overlay for the current screen if `key` opens it: F1 on any screen
+    /// with a key list, or `?` on screens where it is not typed as text.
+    fn context_help(&self, key: KeyCode, modifiers: KeyModifiers) -> Option<HelpOverlay> {
+        let f1 = key == KeyCode::F(1);
+        let question = key == KeyCode::Char('?') && !modifiers.contains(KeyModifiers::CONTROL);
+        match &self.view {
+            AppView::AddEntry(screen) if f1 => Some(HelpOverlay::new("Add Entry Keys", screen.help_keys())),
+            AppView::EditEntry(screen) if f1 => Some(HelpOverlay::new("Edit Entry Keys", screen.help_keys())),
+            AppView::RecoverySetup(screen) if f1 => {
+                Some(HelpOverlay::new("Recovery Setup Keys", screen.help_keys()))
+            }
+            AppView::ViewEntry(screen) if f1 || question => {
+                Some(HelpOverlay::new("Entry Keys", screen.help_keys()))
+            }
+            AppView::Settings(screen) if f1 || question => {
+                Some(HelpOverlay::new("Settings Keys", screen.help_keys()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Forms take a bracketed paste in one piece so a pasted seed phrase is
+    /// never echoed; elsewhere it is typed in as ordinary keys.
+    fn handle_paste(&mut self, text: Zeroizing<String>) -> Result<()> {
+        match &mut self.view {
+            AppView::AddEntry(add_entry) => add_entry.handle_paste(&text),
+            AppView::EditEntry(edit_entry) => edit_entry.handle_paste(&text),
+            _ => {
+                for c in text.chars().filter(|c| !c.is_control()) {
+                    self.handle_key(KeyCode::Char(c), KeyModifiers::NONE)?;
+                }
+            }
         }
+        Ok(())
     }
 
     fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> Result<()> {
         if matches!(key, KeyCode::Char('c' | 'q')) && modifiers.contains(KeyModifiers::CONTROL) {
-            self.should_quit = true;
+            if self.dirty {
+                self.confirm_discard();
+            } else {
+                self.should_quit = true;
+            }
+            return Ok(());
+        }
+
+        if self.help_overlay.is_some() {
+            if matches!(key, KeyCode::Esc | KeyCode::F(1) | KeyCode::Char('?' | 'q')) {
+                self.help_overlay = None;
+            }
+            return Ok(());
+        }
+        if let Some(overlay) = self.context_help(key, modifiers) {
+            self.help_overlay = Some(overlay);
             return Ok(());
         }
 
@@ -191,8 +441,17 @@ impl App {
                 self.handle_wizard_input(key, modifiers)?;
             }
             AppView::Login(login) => {
+                if login.is_unlocking() {
+                    if key == KeyCode::Esc {
+                        self.cancel_unlock();
+                    }
+                    return Ok(());
+                }
                 // F1 for recovery
                 if key == KeyCode::F(1) {
+                    if self.refuse_if_read_only() {
+                        return Ok(());
+                    }
                     self.start_recovery()?;
                     return Ok(());
                 }
@@ -228,10 +487,13 @@ impl App {
             AppView::RecoverySetup(_) => {
                 self.handle_recovery_setup_input(key, modifiers)?;
             }
+            AppView::ImportPreview(_) => {
+                self.handle_import_preview_input(key, modifiers)?;
+            }
             AppView::Message { .. } => {
                 if matches!(key, KeyCode::Enter | KeyCode::Esc) {
                     if self.session.is_none() {
-                        self.view = AppView::Login(LoginScreen::new());
+                        self.view = AppView::Login(self.login_screen());
                     } else {
                         self.return_to_dashboard();
                     }
@@ -246,6 +508,10 @@ impl App {
                 if key == KeyCode::Esc {
                     self.clear_clipboard()?;
                     self.clipboard_clear_time = None;
+                    self.last_copied = None;
+                    self.return_to_dashboard();
+                } else if key == KeyCode::Enter {
+                    // Keep the secret on the clipboard; the dashboard shows the countdown
                     self.return_to_dashboard();
                 }
             }
@@ -259,7 +525,11 @@ impl App {
                     }
                     KeyCode::Enter => {
                         if let Some(session) = &self.session {
-                            let mut dashboard = Dashboard::new(session.vault.metadata());
+                            let mut dashboard = Dashboard::new(session.listing());
+                            dashboard.set_read_only(self.read_only);
+                            dashboard.set_network_glyphs(self.config.network_glyphs);
+                            dashboard.set_columns(configured_columns(&self.config, &DASHBOARD_COLUMNS));
+                            dashboard.set_show_archived(self.show_archived);
                             if let AppView::Search(q) = &self.view {
                                 dashboard.set_filter(q.clone());
                             }
@@ -283,6 +553,7 @@ impl App {
                     self.handle_input_result(result, purpose)?;
                 }
             }
+            AppView::Blanked(_) => self.unblank(),
         }
 
         Ok(())
@@ -308,39 +579,34 @@ impl App {
 
                 // Set up recovery if chosen
                 if let Some((question_index, answer)) = &result.recovery {
-                    let (vault_data, key, salt) =
+                    let (vault_data, key, salt, kdf_algorithm) =
                         storage::unlock_vault_returning_key(password.as_bytes())?;
 
-                    let answer_salt = crate::crypto::kdf::generate_salt();
-                    let answer_hash =
-                        crate::crypto::recovery::hash_answer(answer, &answer_salt)?;
-                    let (blob, nonce, blob_salt) =
-                        crate::crypto::recovery::create_recovery_blob(&*key, answer)?;
-
-                    self.config.recovery = Some(crate::config::RecoveryConfig {
-                        question_index: *question_index,
-                        answer_hash,
-                        answer_salt: answer_salt.to_vec(),
-                        master_key_blob: blob,
-                        master_key_blob_nonce: nonce,
-                        master_key_blob_salt: blob_salt,
-                    });
-
-                    self.session = Some(Session {
-                        vault: vault_data,
-                        password: password.clone(),
+                    self.config.recovery = Some(crate::config::RecoveryConfig::new(
+                        *question_index,
+                        answer,
+                        &key,
+                    )?);
+
+                    self.session = Some(Session::new(
+                        vault_data,
+                        password.clone(),
                         key,
                         salt,
-                    });
+                        kdf_algorithm,
+                        self.config.lock_memory,
+                    ));
                 } else {
-                    let (vault_data, key, salt) =
+                    let (vault_data, key, salt, kdf_algorithm) =
                         storage::unlock_vault_returning_key(password.as_bytes())?;
-                    self.session = Some(Session {
-                        vault: vault_data,
-                        password: password.clone(),
+                    self.session = Some(Session::new(
+                        vault_data,
+                        password.clone(),
                         key,
                         salt,
-                    });
+                        kdf_algorithm,
+                        self.config.lock_memory,
+                    ));
                 }
 
                 // Save config
@@ -364,7 +630,11 @@ impl App {
         let config = crate::config::load_config()?;
         match config.recovery {
             Some(recovery_config) => {
-                self.view = AppView::Recovery(RecoveryScreen::new(recovery_config));
+                self.view = AppView::Recovery(RecoveryScreen::new(
+                    recovery_config,
+                    config.recovery_locked_until,
+                    config.password_policy.clone(),
+                ));
             }
             None => {
                 self.show_message(
@@ -391,34 +661,34 @@ impl App {
                 match storage::read_vault_with_key(&*master_key, &data) {
                     Ok(vault) => {
                         // Re-encrypt vault with the new password
-                        storage::save_vault(&vault, new_password.as_bytes())?;
+                        let upgraded_from = storage::save_vault(&vault, new_password.as_bytes())?;
 
                         // Re-derive key and salt for the new session
-                        let (vault_data, new_key, new_salt) =
+                        let (vault_data, new_key, new_salt, kdf_algorithm) =
                             storage::unlock_vault_returning_key(new_password.as_bytes())?;
 
                         // Update recovery config with the new master key
                         let mut config = crate::config::load_config()?;
-                        if config.recovery.is_some() {
-                            // Password changed = recovery must be reset.
-                            // The recovery blob is encrypted under the old master key.
-                            config.recovery = None;
-                            crate::config::save_config(&config)?;
-                            self.config = config;
-                        }
-
-                        self.session = Some(Session {
-                            vault: vault_data,
-                            password: new_password,
-                            key: new_key,
-                            salt: new_salt,
-                        });
-
-                        self.show_message(
-                            "Recovery Successful".into(),
-                            "Master password changed successfully!\n\nNote: Your recovery question has been cleared.\nPlease set up a new one in Settings (Shift+S).".into(),
-                            false,
+                        config.clear_recovery_failures();
+                        // Password changed = recovery must be reset.
+                        // The recovery blob is encrypted under the old master key.
+                        config.recovery = None;
+                        crate::config::save_config(&config)?;
+                        self.config = config;
+
+                        let mut session = Session::new(
+                            vault_data,
+                            new_password,
+                            new_key,
+                            new_salt,
+                            kdf_algorithm,
+                            self.config.lock_memory,
                         );
+                        // Reported once the dashboard opens, after recovery setup
+                        session.upgraded_from = upgraded_from;
+                        self.session = Some(session);
+
+                        self.view = AppView::RecoverySetup(RecoverySetupScreen::resetup());
                     }
                     Err(e) => {
                         self.show_message(
@@ -429,8 +699,17 @@ impl App {
                     }
                 }
             }
+            super::screens::recovery::RecoveryAction::WrongAnswer => {
+                let mut config = crate::config::load_config()?;
+                config.record_recovery_failure(chrono::Utc::now());
+                crate::config::save_config(&config)?;
+                if let AppView::Recovery(recovery) = &mut self.view {
+                    recovery.set_locked_until(config.recovery_locked_until);
+                }
+                self.config = config;
+            }
             super::screens::recovery::RecoveryAction::Cancel => {
-                self.view = AppView::Login(LoginScreen::new());
+                self.view = AppView::Login(self.login_screen());
             }
             super::screens::recovery::RecoveryAction::Continue => {}
         }
@@ -440,22 +719,74 @@ impl App {
     // ─── Login ───────────────────────────────────────────────────────
 
     fn unlock_vault(&mut self, password: Zeroizing<String>) -> Result<()> {
-        match storage::unlock_vault_returning_key(password.as_bytes()) {
-            Ok((vault, key, salt)) => {
-                self.session = Some(Session {
+        let rx = storage::spawn_unlock(password.clone());
+        self.pending_unlock = Some((rx, password, Instant::now()));
+        if let AppView::Login(login) = &mut self.view {
+            login.start_unlocking();
+        }
+        Ok(())
+    }
+
+    /// Check whether the background unlock has finished and, if so, apply its result.
+    fn poll_unlock(&mut self) -> Result<()> {
+        let result = match &self.pending_unlock {
+            Some((rx, _, _)) => match rx.try_recv() {
+                Ok(result) => result,
+                Err(mpsc::TryRecvError::Empty) => return Ok(()),
+                Err(mpsc::TryRecvError::Disconnected) => Err(CryptoKeeperError::Encryption(
+                    "Unlock worker exited unexpectedly".into(),
+                )),
+            },
+            None => return Ok(()),
+        };
+        let Some((_, password, started)) = self.pending_unlock.take() else {
+            return Ok(());
+        };
+
+        match result {
+            Ok((vault, key, salt, kdf_algorithm)) => {
+                let session = Session::new(
                     vault,
                     password,
                     key,
                     salt,
-                });
+                    kdf_algorithm,
+                    self.config.lock_memory,
+                );
+                let lock_failed = session.memory_lock_failed();
+                self.session = Some(session);
                 self.return_to_dashboard();
+                // The whole unlock bounds the key derivation from above
+                let weak_kdf =
+                    storage::fast_unlock_warning(started.elapsed(), self.config.fast_unlock_warn_ms);
+                if lock_failed {
+                    self.show_message(
+                        "Memory Locking Unavailable".to_string(),
+                        "The OS refused to lock memory, so secrets may be swapped to disk.\nRaise the memlock limit or turn off memory locking in Settings.".to_string(),
+                        true,
+                    );
+                } else if let Some(warning) = weak_kdf {
+                    self.show_message(
+                        "Weak Key Derivation".to_string(),
+                        format!("{}\nUse Shift+P to change it.", warning),
+                        true,
+                    );
+                }
                 Ok(())
             }
             Err(e) => {
-                self.view = AppView::Login(LoginScreen::new());
+                self.view = AppView::Login(self.login_screen());
                 self.show_message(
                     "Login Failed".to_string(),
-                    format!("Failed to unlock vault: {}\n\nPress Enter to try again.\nPress F1 for password recovery.", e),
+                    format!(
+                        "Failed to unlock vault: {}\n\nPress Enter to try again.{}",
+                        e,
+                        if self.config.recovery.is_some() {
+                            "\nPress F1 for password recovery."
+                        } else {
+                            ""
+                        }
+                    ),
                     true,
                 );
                 Ok(())
@@ -463,6 +794,77 @@ impl App {
         }
     }
 
+    /// Tell the user once when a save rewrote an older vault format. Waits for
+    /// the dashboard so it never cuts into a screen's own flow.
+    fn report_upgrade(&mut self) {
+        if !matches!(self.view, AppView::Dashboard(_)) {
+            return;
+        }
+        let upgraded_from = self.session.as_mut().and_then(|s| s.upgraded_from.take());
+        if let Some(from) = upgraded_from {
+            self.show_message(
+                "Vault Upgraded".to_string(),
+                storage::upgrade_notice(from),
+                false,
+            );
+        }
+    }
+
+    /// Drop the decrypted vault and its key and return to the login screen,
+    /// clearing any secret still waiting on the clipboard.
+    fn lock(&mut self) -> Result<()> {
+        if self.clipboard_clear_time.take().is_some() {
+            self.clear_clipboard()?;
+        }
+        self.last_copied = None;
+        self.pending_view_entry_idx = None;
+        self.pending_copy_entry_idx = None;
+        self.show_archived = false;
+        self.session = None;
+        self.view = AppView::Login(self.login_screen());
+        // Only a convenience, so a config that can't be written doesn't stop the lock
+        if let Err(e) = self.save_last_selected() {
+            self.show_message(
+                "Could Not Save Settings".to_string(),
+                format!("The last viewed entry was not remembered: {}", e),
+                true,
+            );
+        }
+        Ok(())
+    }
+
+    /// Entry the dashboard selects when it opens.
+    fn last_selected(&self) -> Option<&str> {
+        self.pending_last_selected
+            .as_deref()
+            .or(self.config.last_selected_entry.as_deref())
+    }
+
+    /// Write the last viewed entry to the config. Done on lock and quit rather
+    /// than on every view.
+    fn save_last_selected(&mut self) -> Result<()> {
+        let Some(name) = self.pending_last_selected.take() else {
+            return Ok(());
+        };
+        // Reload so settings changed elsewhere since startup are kept
+        let mut config = crate::config::load_config()?;
+        config.last_selected_entry = Some(name);
+        crate::config::save_config(&config)?;
+        self.config.last_selected_entry = config.last_selected_entry;
+        Ok(())
+    }
+
+    /// Login screen, offering recovery only when it is set up.
+    fn login_screen(&self) -> LoginScreen {
+        LoginScreen::new(self.config.recovery.is_some())
+    }
+
+    /// Abandon an in-flight unlock; the worker's result is dropped when it arrives.
+    fn cancel_unlock(&mut self) {
+        self.pending_unlock = None;
+        self.view = AppView::Login(self.login_screen());
+    }
+
     // ─── Dashboard ───────────────────────────────────────────────────
 
     fn handle_dashboard_input(
@@ -475,6 +877,11 @@ impl App {
             _ => return Ok(()),
         };
 
+        // Ctrl+Up/Down moves the selected entry
+        if modifiers.contains(KeyModifiers::CONTROL) && matches!(key, KeyCode::Up | KeyCode::Down) {
+            return self.move_selected_entry(key == KeyCode::Up);
+        }
+
         // Enter works without modifier
         if modifiers.is_empty() && key == KeyCode::Enter {
             if let Some(idx) = selected_idx {
@@ -487,7 +894,7 @@ impl App {
                             ViewPasswordScreen::new("Enter Secondary Password"),
                         );
                     } else {
-                        self.view = AppView::ViewEntry(ViewEntryScreen::new(entry));
+                        self.open_entry_view(entry);
                     }
                 }
             }
@@ -500,6 +907,21 @@ impl App {
             return Ok(());
         }
 
+        // / opens the filter, even right after a typed name prefix
+        if modifiers.is_empty() && key == KeyCode::Char('/') {
+            self.view = AppView::Search(String::new());
+            return Ok(());
+        }
+
+        // Tab switches between the entries in use and the archived ones
+        if key == KeyCode::Tab {
+            self.show_archived = !self.show_archived;
+            if let AppView::Dashboard(d) = &mut self.view {
+                d.set_show_archived(self.show_archived);
+            }
+            return Ok(());
+        }
+
         // Shift+key commands
         if modifiers.contains(KeyModifiers::SHIFT) {
             match key {
@@ -507,8 +929,14 @@ impl App {
                     self.should_quit = true;
                     return Ok(());
                 }
+                KeyCode::Char('L') => {
+                    return self.lock();
+                }
                 KeyCode::Char('A') => {
-                    self.view = AppView::AddEntry(AddEntryScreen::new());
+                    if self.refuse_if_read_only() {
+                        return Ok(());
+                    }
+                    self.view = AppView::AddEntry(AddEntryScreen::new(&self.config));
                     return Ok(());
                 }
                 KeyCode::Char('V') => {
@@ -522,7 +950,7 @@ impl App {
                                     ViewPasswordScreen::new("Enter Secondary Password"),
                                 );
                             } else {
-                                self.view = AppView::ViewEntry(ViewEntryScreen::new(entry));
+                                self.open_entry_view(entry);
                             }
                         }
                     }
@@ -546,17 +974,29 @@ impl App {
                     return Ok(());
                 }
                 KeyCode::Char('E') => {
+                    if self.refuse_if_read_only() {
+                        return Ok(());
+                    }
                     if let Some(idx) = selected_idx {
+                        if self.refuse_if_hidden(idx) {
+                            return Ok(());
+                        }
                         if let Some(entry) = self.session.as_ref()
                             .and_then(|s| s.vault.entries.get(idx).cloned())
                         {
-                            self.view = AppView::EditEntry(EditEntryScreen::new(entry));
+                            self.view = AppView::EditEntry(EditEntryScreen::new(entry, Mask::from_config(&self.config)));
                         }
                     }
                     return Ok(());
                 }
                 KeyCode::Char('D') => {
+                    if self.refuse_if_read_only() {
+                        return Ok(());
+                    }
                     if let Some(idx) = selected_idx {
+                        if self.refuse_if_hidden(idx) {
+                            return Ok(());
+                        }
                         if let Some(entry) = self.session.as_ref()
                             .and_then(|s| s.vault.entries.get(idx))
                         {
@@ -569,6 +1009,9 @@ impl App {
                     }
                     return Ok(());
                 }
+                KeyCode::Char('R') => {
+                    return self.toggle_archived();
+                }
                 KeyCode::Char('F') => {
                     self.view = AppView::Search(String::new());
                     return Ok(());
@@ -579,18 +1022,37 @@ impl App {
                     return Ok(());
                 }
                 KeyCode::Char('X') => {
-                    let input = InputScreen::new("Export Vault", "Enter directory path:", false);
+                    let input = InputScreen::new("Export Vault", "Enter directory path:", false)
+                        .with_value(self.config.default_export_dir.as_deref().unwrap_or_default());
                     self.view = AppView::Input(input, InputPurpose::ExportPath);
                     return Ok(());
                 }
                 KeyCode::Char('I') => {
+                    if self.refuse_if_read_only() {
+                        return Ok(());
+                    }
                     let input = InputScreen::new("Import Vault", "Enter backup file path:", false);
                     self.view = AppView::Input(input, InputPurpose::ImportPath);
                     return Ok(());
                 }
                 KeyCode::Char('P') => {
-                    let input = InputScreen::new("Change Password", "Enter new master password:", true);
-                    self.view = AppView::Input(input, InputPurpose::ChangePassword);
+                    if self.refuse_if_read_only() {
+                        return Ok(());
+                    }
+                    let Some(session) = &self.session else {
+                        return Ok(());
+                    };
+                    let mut message = session.vault.password_change_summary();
+                    if self.config.recovery.is_some() {
+                        // The recovery blob holds the current key, so it cannot survive the change
+                        message.push_str(" Your recovery question will be cleared; you can set up a new one right after the change.");
+                    }
+                    message.push_str(" Continue?");
+                    self.view = AppView::Confirm(ConfirmScreen::new(
+                        "Change Password",
+                        &message,
+                        ConfirmAction::ChangePassword,
+                    ));
                     return Ok(());
                 }
                 _ => {}
@@ -599,12 +1061,98 @@ impl App {
 
         if should_handle_key {
             if let AppView::Dashboard(dashboard) = &mut self.view {
-                dashboard.handle_key(key, modifiers);
+                // Unmodified letters jump to the first entry with that name prefix
+                match key {
+                    KeyCode::Char(c) if modifiers.is_empty() && dashboard.is_typeahead_char(c) => {
+                        dashboard.type_ahead(c);
+                    }
+                    _ => dashboard.handle_key(key, modifiers),
+                }
             }
         }
         Ok(())
     }
 
+    /// Swap the selected entry with its visible neighbour and save the new order.
+    fn move_selected_entry(&mut self, up: bool) -> Result<()> {
+        if self.refuse_if_read_only() {
+            return Ok(());
+        }
+        let (from, to) = match &self.view {
+            AppView::Dashboard(d) => match (d.selected_index(), d.neighbor_index(up)) {
+                (Some(from), Some(to)) => (from, to),
+                _ => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        if let Some(session) = &mut self.session {
+            session.vault.move_entry(from, to);
+            session.save()?;
+            if let AppView::Dashboard(d) = &mut self.view {
+                d.set_entries(session.listing(), to);
+            }
+        }
+        Ok(())
+    }
+
+    /// Archive the selected entry, or restore it in the archived view. The
+    /// selection moves to a neighbour, since the entry leaves the list.
+    fn toggle_archived(&mut self) -> Result<()> {
+        if self.refuse_if_read_only() {
+            return Ok(());
+        }
+        let (idx, next) = match &self.view {
+            AppView::Dashboard(d) => match d.selected_index() {
+                Some(idx) => (idx, d.neighbor_index(false).or(d.neighbor_index(true))),
+                None => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        if let Some(session) = &mut self.session {
+            if let Some(entry) = session.vault.entries.get_mut(idx) {
+                entry.archived = !entry.archived;
+            }
+            session.save()?;
+            if let AppView::Dashboard(d) = &mut self.view {
+                d.set_entries(session.listing(), next.unwrap_or(0));
+            }
+        }
+        Ok(())
+    }
+
+    /// List a masked entry in full for the rest of the session.
+    fn reveal(&mut self, entry: &Entry) {
+        if let Some(session) = &mut self.session {
+            if entry.mask_metadata {
+                session.revealed.insert(entry.name.clone());
+            }
+        }
+    }
+
+    /// Refuse to show a masked entry's name before its secondary password is entered.
+    fn refuse_if_hidden(&mut self, idx: usize) -> bool {
+        let hidden = self.session.as_ref().is_some_and(|s| {
+            s.vault.entries.get(idx).is_some_and(|e| s.is_hidden(e))
+        });
+        if hidden {
+            self.show_message(
+                "Hidden Entry".to_string(),
+                "Open this entry with its secondary password first.".to_string(),
+                true,
+            );
+        }
+        hidden
+    }
+
+    /// Show `entry` and remember it as the one to select after the next unlock.
+    /// A masked entry's name is never written to the plaintext config.
+    fn open_entry_view(&mut self, entry: Entry) {
+        if !entry.mask_metadata && self.last_selected() != Some(entry.name.as_str()) {
+            self.pending_last_selected = Some(entry.name.clone());
+        }
+        self.view = AppView::ViewEntry(ViewEntryScreen::new(entry, Mask::from_config(&self.config)));
+    }
+
     fn get_selected_entry_copy(&self, dashboard: &Dashboard) -> Option<Entry> {
         let session = self.session.as_ref()?;
         let selected_idx = dashboard.selected_index()?;
@@ -621,7 +1169,7 @@ impl App {
 
         match action {
             super::screens::settings::SettingsAction::Save(updated_config) => {
-                self.config = updated_config;
+                self.config = *updated_config;
                 crate::config::save_config(&self.config)?;
                 self.return_to_dashboard();
             }
@@ -636,11 +1184,11 @@ impl App {
         Ok(())
     }
 
-    // ─── Recovery Setup (from Settings) ───────────────────────────────
+    // ─── Recovery Setup (from Settings or after a password change) ────
 
     fn handle_recovery_setup_input(&mut self, key: KeyCode, modifiers: KeyModifiers) -> Result<()> {
-        let action = match &mut self.view {
-            AppView::RecoverySetup(setup) => setup.handle_key(key, modifiers),
+        let (action, resetup) = match &mut self.view {
+            AppView::RecoverySetup(setup) => (setup.handle_key(key, modifiers), setup.is_resetup()),
             _ => return Ok(()),
         };
 
@@ -650,27 +1198,23 @@ impl App {
                 answer,
             } => {
                 if let Some(session) = &self.session {
-                    let master_key: &[u8; 32] = &*session.key;
-
-                    let answer_salt = crate::crypto::kdf::generate_salt();
-                    let answer_hash =
-                        crate::crypto::recovery::hash_answer(&answer, &answer_salt)?;
-                    let (blob, nonce, blob_salt) =
-                        crate::crypto::recovery::create_recovery_blob(master_key, &answer)?;
-
-                    self.config.recovery = Some(crate::config::RecoveryConfig {
+                    self.config.recovery = Some(crate::config::RecoveryConfig::new(
                         question_index,
-                        answer_hash,
-                        answer_salt: answer_salt.to_vec(),
-                        master_key_blob: blob,
-                        master_key_blob_nonce: nonce,
-                        master_key_blob_salt: blob_salt,
-                    });
+                        &answer,
+                        &session.key,
+                    )?);
                     crate::config::save_config(&self.config)?;
 
                     self.show_success("Recovery question configured successfully!".to_string());
                 }
             }
+            super::screens::recovery_setup::RecoverySetupAction::Cancel if resetup => {
+                self.show_message(
+                    "Recovery Not Set Up".into(),
+                    "Your vault has no recovery question.\nSet one up later in Settings (Shift+S).".into(),
+                    false,
+                );
+            }
             super::screens::recovery_setup::RecoverySetupAction::Cancel => {
                 // Return to settings
                 self.config = crate::config::load_config()?;
@@ -696,11 +1240,12 @@ impl App {
                     if let Some(entry) = self.session.as_ref()
                         .and_then(|s| s.vault.entries.get(idx).cloned())
                     {
-                        match self.decrypt_entry_secret(&entry, &view_pass) {
+                        match crate::crypto::entry_key::decrypt_entry(&entry, &view_pass) {
                             Ok(decrypted_secret) => {
+                                self.reveal(&entry);
                                 let mut revealed_entry = entry.clone();
                                 revealed_entry.secret = (*decrypted_secret).clone();
-                                self.view = AppView::ViewEntry(ViewEntryScreen::new(revealed_entry));
+                                self.open_entry_view(revealed_entry);
                             }
                             Err(_) => {
                                 let mut vp = ViewPasswordScreen::new("Enter Secondary Password");
@@ -716,8 +1261,9 @@ impl App {
                     if let Some(entry) = self.session.as_ref()
                         .and_then(|s| s.vault.entries.get(idx).cloned())
                     {
-                        match self.decrypt_entry_secret(&entry, &view_pass) {
+                        match crate::crypto::entry_key::decrypt_entry(&entry, &view_pass) {
                             Ok(decrypted_secret) => {
+                                self.reveal(&entry);
                                 let mut copy_entry = entry.clone();
                                 copy_entry.secret = (*decrypted_secret).clone();
                                 self.copy_to_clipboard(&copy_entry)?;
@@ -746,22 +1292,6 @@ impl App {
         Ok(())
     }
 
-    fn decrypt_entry_secret(&self, entry: &Entry, view_password: &str) -> Result<Zeroizing<String>> {
-        let wrapped = entry.entry_key_wrapped.as_ref()
-            .ok_or(CryptoKeeperError::SecondaryPasswordRequired)?;
-        let nonce = entry.entry_key_nonce.as_ref()
-            .ok_or(CryptoKeeperError::SecondaryPasswordRequired)?;
-        let salt = entry.entry_key_salt.as_ref()
-            .ok_or(CryptoKeeperError::SecondaryPasswordRequired)?;
-        let ct = entry.encrypted_secret.as_ref()
-            .ok_or(CryptoKeeperError::SecondaryPasswordRequired)?;
-        let ct_nonce = entry.encrypted_secret_nonce.as_ref()
-            .ok_or(CryptoKeeperError::SecondaryPasswordRequired)?;
-
-        let entry_key = crate::crypto::entry_key::unwrap_entry_key(wrapped, nonce, salt, view_password)?;
-        crate::crypto::entry_key::decrypt_secret(&entry_key, ct, ct_nonce)
-    }
-
     // ─── Add Entry ───────────────────────────────────────────────────
 
     fn handle_add_entry_input(
@@ -776,14 +1306,25 @@ impl App {
 
         match action {
             super::screens::add_entry::AddEntryAction::Save(entry) => {
-                if let Some(session) = &mut self.session {
-                    let msg = match &entry.public_address {
-                        Some(addr) => format!("Entry added! Address: {}", addr),
-                        None => "Entry added successfully!".to_string(),
-                    };
-                    session.vault.entries.push(entry);
-                    session.save()?;
-                    self.show_success(msg);
+                let existing = self.session.as_ref().and_then(|session| {
+                    let address = entry.public_address.as_deref()?;
+                    session.vault.find_by_address(address).map(|e| e.name.clone())
+                });
+                match existing {
+                    Some(existing) => {
+                        let message = format!(
+                            "This address is already stored as '{}'. Add this entry anyway?",
+                            existing
+                        );
+                        let confirm = AppView::Confirm(ConfirmScreen::new(
+                            "Duplicate Address",
+                            &message,
+                            ConfirmAction::AddEntry(entry),
+                        ));
+                        self.confirm_from_view =
+                            Some(Box::new(std::mem::replace(&mut self.view, confirm)));
+                    }
+                    None => self.add_entry(*entry)?,
                 }
             }
             super::screens::add_entry::AddEntryAction::Cancel => {
@@ -794,6 +1335,56 @@ impl App {
         Ok(())
     }
 
+    fn add_entry(&mut self, entry: Entry) -> Result<()> {
+        if let Some(session) = &mut self.session {
+            let msg = match &entry.public_address {
+                Some(addr) => format!("Entry added! Address: {}", addr),
+                None => "Entry added successfully!".to_string(),
+            };
+            session.vault.entries.push(entry);
+            session.save()?;
+            self.show_success(msg);
+        }
+        Ok(())
+    }
+
+    // ─── Import Preview ──────────────────────────────────────────────
+
+    fn handle_import_preview_input(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<()> {
+        let action = match &mut self.view {
+            AppView::ImportPreview(preview) => preview.handle_key(key, modifiers),
+            _ => return Ok(()),
+        };
+
+        match action {
+            ImportPreviewAction::Import => {
+                let view = std::mem::replace(&mut self.view, AppView::Help);
+                let (AppView::ImportPreview(preview), Some(session)) = (view, &mut self.session) else {
+                    self.return_to_dashboard();
+                    return Ok(());
+                };
+                let mut imported = 0;
+                for entry in preview.backup.entries {
+                    if !session.vault.has_entry(&entry.name) {
+                        session.vault.entries.push(entry);
+                        imported += 1;
+                    }
+                }
+                if imported > 0 {
+                    session.save()?;
+                }
+                self.show_success(format!("Imported {} entries from backup", imported));
+            }
+            ImportPreviewAction::Cancel => self.return_to_dashboard(),
+            ImportPreviewAction::Continue => {}
+        }
+        Ok(())
+    }
+
     // ─── View Entry ──────────────────────────────────────────────────
 
     fn handle_view_entry_input(
@@ -810,22 +1401,26 @@ impl App {
             super::screens::view_entry::ViewEntryAction::Close => {
                 self.return_to_dashboard();
             }
+            super::screens::view_entry::ViewEntryAction::Export(name) => {
+                let input = InputScreen::new(
+                    "Export Entry",
+                    &format!("Enter directory path for '{}':", name),
+                    false,
+                )
+                .with_value(self.config.default_export_dir.as_deref().unwrap_or_default());
+                self.view = AppView::Input(input, InputPurpose::ExportEntryPath(name));
+            }
             super::screens::view_entry::ViewEntryAction::Copy(secret) => {
-                use arboard::Clipboard;
-                let timeout = self.config.clipboard_timeout_secs;
-                if let Ok(mut clipboard) = Clipboard::new() {
-                    let _ = clipboard.set_text(&secret);
-                    self.clipboard_clear_time = Some(Instant::now() + Duration::from_secs(timeout));
-
-                    let entry_name = match &self.view {
-                        AppView::ViewEntry(v) => v.entry.name.clone(),
-                        _ => String::new(),
-                    };
-
-                    self.view = AppView::CopyCountdown {
-                        entry_name,
-                        seconds_left: timeout as u8,
-                    };
+                let entry = match &self.view {
+                    AppView::ViewEntry(v) => v.entry.clone(),
+                    _ => return Ok(()),
+                };
+                // Custom field values are copied without the key/seed confirmation
+                if *secret == entry.secret {
+                    self.copy_to_clipboard(&entry)?;
+                } else {
+                    let what = format!("this field of '{}'", entry.name);
+                    self.copy_value(&entry, &secret, &what, false)?;
                 }
             }
             super::screens::view_entry::ViewEntryAction::Continue => {}
@@ -857,7 +1452,10 @@ impl App {
                         .iter_mut()
                         .find(|e| e.name == original_name)
                     {
-                        *entry = updated_entry;
+                        if session.revealed.remove(&original_name) {
+                            session.revealed.insert(updated_entry.name.clone());
+                        }
+                        *entry = *updated_entry;
                     }
                     session.save()?;
                     self.show_success("Entry updated successfully!".to_string());
@@ -895,32 +1493,222 @@ impl App {
                             self.show_success("Entry deleted successfully!".to_string());
                         }
                     }
+                    ConfirmAction::OverwriteVault => {
+                        if let Some(session) = &mut self.session {
+                            session.force_save()?;
+                            self.show_success("Vault saved, replacing the copy on disk.".to_string());
+                        }
+                    }
+                    ConfirmAction::DiscardAndQuit => {
+                        self.confirm_from_view = None;
+                        self.should_quit = true;
+                    }
+                    ConfirmAction::ChangePassword => self.start_change_password(),
+                    ConfirmAction::CopySecret { entry_name, secret, timeout } => {
+                        self.copy_secret(&entry_name, &secret, timeout)?;
+                    }
+                    ConfirmAction::AddEntry(entry) => {
+                        self.confirm_from_view = None;
+                        self.add_entry(*entry)?;
+                    }
                 }
             }
-            Some(false) => {
-                self.return_to_dashboard();
-            }
+            Some(false) => match action {
+                ConfirmAction::OverwriteVault => self.reload_vault(),
+                ConfirmAction::DiscardAndQuit | ConfirmAction::AddEntry(_) => {
+                    match self.confirm_from_view.take() {
+                        Some(view) => self.view = *view,
+                        None => self.return_to_dashboard(),
+                    }
+                }
+                _ => self.return_to_dashboard(),
+            },
             None => {}
         }
         Ok(())
     }
 
+    fn start_change_password(&mut self) {
+        let input = InputScreen::new("Change Password", "Enter new master password:", true);
+        self.view = AppView::Input(input, InputPurpose::ChangePassword);
+    }
+
+    /// Whether the current screen holds typed input that quitting would lose.
+    fn has_pending_input(&self) -> bool {
+        match &self.view {
+            AppView::AddEntry(add_entry) => add_entry.has_pending_input(),
+            AppView::EditEntry(edit_entry) => edit_entry.has_pending_input(),
+            _ => false,
+        }
+    }
+
+    /// Ask before quitting away from unsaved input; No returns to the screen as it was.
+    fn confirm_discard(&mut self) {
+        let message = match &self.view {
+            AppView::EditEntry(_) => "Discard unsaved changes to this entry and quit?",
+            _ => "Discard unsaved entry and quit?",
+        };
+        let confirm = AppView::Confirm(ConfirmScreen::new(
+            "Unsaved Changes",
+            message,
+            ConfirmAction::DiscardAndQuit,
+        ));
+        self.confirm_from_view = Some(Box::new(std::mem::replace(&mut self.view, confirm)));
+        self.dirty = false;
+    }
+
+    /// Ask whether to overwrite a vault file that changed on disk or reload it.
+    fn confirm_overwrite(&mut self) {
+        self.view = AppView::Confirm(ConfirmScreen::new(
+            "Vault Changed on Disk",
+            "The vault file was modified outside this session. Overwrite it with your changes? (No reloads from disk and discards the last change.)",
+            ConfirmAction::OverwriteVault,
+        ));
+    }
+
+    fn reload_vault(&mut self) {
+        let Some(session) = &mut self.session else {
+            return;
+        };
+        match session.reload() {
+            Ok(()) => self.show_success("Vault reloaded from disk.".to_string()),
+            Err(e) => self.show_message(
+                "Reload Failed".to_string(),
+                format!("Could not reload the vault: {}\n\nIt may have been re-encrypted with a different password.", e),
+                true,
+            ),
+        }
+    }
+
     // ─── Clipboard ───────────────────────────────────────────────────
 
+    /// Copy an entry's secret, first asking for confirmation if `confirm_secret_copy`
+    /// is set and the secret is a private key or seed phrase.
     fn copy_to_clipboard(&mut self, entry: &Entry) -> Result<()> {
+        let what = format!(
+            "the {} for '{}'",
+            entry.secret_type.to_string().to_lowercase(),
+            entry.name
+        );
+        let ask = self.config.confirm_secret_copy && entry.secret_type != SecretType::Password;
+        self.copy_value(entry, &entry.secret, &what, ask)
+    }
+
+    /// Copy `secret`, one of `entry`'s values described by `what`. Asks first
+    /// when `ask` is set or the clipboard belongs to a remote SSH host.
+    fn copy_value(&mut self, entry: &Entry, secret: &str, what: &str, ask: bool) -> Result<()> {
+        if crate::clipboard::is_disabled(&self.config) {
+            self.show_clipboard_disabled();
+            return Ok(());
+        }
+        let timeout = self.config.clipboard_timeout_for(entry);
+        let remote = crate::clipboard::warn_remote(&self.config);
+        if ask || remote {
+            let mut message = format!("Copy {} to the clipboard?", what);
+            if remote {
+                message = format!("{} {}", crate::clipboard::REMOTE_WARNING, message);
+            }
+            self.view = AppView::Confirm(ConfirmScreen::new(
+                "Copy Secret",
+                &message,
+                ConfirmAction::CopySecret {
+                    entry_name: entry.name.clone(),
+                    secret: Zeroizing::new(secret.to_string()),
+                    timeout,
+                },
+            ));
+            return Ok(());
+        }
+        self.copy_secret(&entry.name, secret, timeout)
+    }
+
+    /// Copy `secret` and clear it after `timeout` seconds.
+    fn copy_secret(&mut self, entry_name: &str, secret: &str, timeout: u64) -> Result<()> {
         use arboard::Clipboard;
-        let timeout = self.config.clipboard_timeout_secs;
+        if crate::clipboard::is_disabled(&self.config) {
+            self.show_clipboard_disabled();
+            return Ok(());
+        }
         if let Ok(mut clipboard) = Clipboard::new() {
-            let _ = clipboard.set_text(&entry.secret);
+            let _ = clipboard.set_text(secret);
             self.clipboard_clear_time = Some(Instant::now() + Duration::from_secs(timeout));
+            self.last_copied = Some((entry_name.to_string(), Instant::now()));
             self.view = AppView::CopyCountdown {
-                entry_name: entry.name.clone(),
-                seconds_left: timeout as u8,
+                entry_name: entry_name.to_string(),
             };
         }
         Ok(())
     }
 
+    fn show_clipboard_disabled(&mut self) {
+        self.show_message(
+            "Clipboard Disabled".to_string(),
+            "Copying is turned off (--no-clipboard or Settings).\n\nOpen the entry and press r to reveal the secret instead.".to_string(),
+            false,
+        );
+    }
+
+    /// A revealed secret has been left on screen past the configured timeout.
+    fn reveal_expired(&self) -> bool {
+        let timeout = self.config.reveal_timeout_secs;
+        match &self.view {
+            AppView::ViewEntry(view_entry) if view_entry.is_revealed() && timeout > 0 => {
+                self.last_input.elapsed() >= Duration::from_secs(timeout)
+            }
+            _ => false,
+        }
+    }
+
+    /// The unlocked vault has gone without a keypress for the configured blank delay.
+    fn blank_due(&self) -> bool {
+        let delay = self.config.blank_after_secs;
+        delay > 0 && self.can_blank() && self.last_input.elapsed() >= Duration::from_secs(delay)
+    }
+
+    /// An unlocked vault is on screen and not already covered.
+    fn can_blank(&self) -> bool {
+        let blankable = !matches!(
+            self.view,
+            AppView::Wizard(_) | AppView::Login(_) | AppView::Recovery(_) | AppView::Blanked(_)
+        );
+        blankable && self.session.is_some()
+    }
+
+    /// Cover the current screen until a key is pressed.
+    fn blank(&mut self) {
+        let covered = std::mem::replace(&mut self.view, AppView::Help);
+        self.view = AppView::Blanked(Box::new(covered));
+    }
+
+    /// Bring back the screen under the idle cover. A copy countdown that ran
+    /// out while covered goes to the dashboard instead.
+    fn unblank(&mut self) {
+        self.blanked_by_focus = false;
+        let AppView::Blanked(covered) = std::mem::replace(&mut self.view, AppView::Help) else {
+            return;
+        };
+        self.view = *covered;
+        if matches!(self.view, AppView::CopyCountdown { .. }) && self.clipboard_clear_time.is_none() {
+            self.return_to_dashboard();
+        }
+    }
+
+    /// Dashboard status text for a secret still waiting to be cleared from the clipboard.
+    fn clipboard_status(&self) -> Option<String> {
+        let (name, _copied_at) = self.last_copied.as_ref()?;
+        let remaining = self.clipboard_seconds_left()?;
+        Some(format!("Clipboard: {} secret (clears in {}s)", name, remaining))
+    }
+
+    /// Whole seconds until the clipboard is cleared, rounded up so the display
+    /// starts at the full timeout and reaches 0 only when clearing is due.
+    fn clipboard_seconds_left(&self) -> Option<u64> {
+        let remaining = self
+            .clipboard_clear_time?
+            .saturating_duration_since(Instant::now());
+        Some(remaining.as_millis().div_ceil(1000) as u64)
+    }
+
     fn clear_clipboard(&self) -> Result<()> {
         use arboard::Clipboard;
         if let Ok(mut clipboard) = Clipboard::new() {
@@ -932,11 +1720,49 @@ impl App {
     // ─── Navigation ──────────────────────────────────────────────────
 
     fn return_to_dashboard(&mut self) {
+        self.help_overlay = None;
         if let Some(session) = &self.session {
-            self.view = AppView::Dashboard(Dashboard::new(session.vault.metadata()));
+            let mut dashboard = Dashboard::new(session.listing());
+            dashboard.set_read_only(self.read_only);
+            dashboard.set_network_glyphs(self.config.network_glyphs);
+            dashboard.set_columns(configured_columns(&self.config, &DASHBOARD_COLUMNS));
+            dashboard.set_show_archived(self.show_archived);
+            let last = self.last_selected();
+            if let Some(idx) = last.and_then(|name| session.vault.entries.iter().position(|e| e.name == name)) {
+                dashboard.select_index(idx);
+            }
+            self.view = AppView::Dashboard(dashboard);
         }
     }
 
+    /// Show the entry count in the terminal title while unlocked, and the bare
+    /// app name otherwise.
+    fn sync_window_title(&mut self) {
+        let title = match &self.session {
+            Some(session) => match session.vault.entries.len() {
+                1 => format!("{} \u{2014} 1 entry", theme::APP_TITLE),
+                n => format!("{} \u{2014} {} entries", theme::APP_TITLE, n),
+            },
+            None => theme::APP_TITLE.to_string(),
+        };
+        if title != self.window_title {
+            theme::set_title(&title);
+            self.window_title = title;
+        }
+    }
+
+    /// Show an explanation and return true if the vault was opened read-only.
+    fn refuse_if_read_only(&mut self) -> bool {
+        if self.read_only {
+            self.show_message(
+                "Read-Only".to_string(),
+                "The vault is open in another CryptoKeeper process.\nClose it to make changes.".to_string(),
+                true,
+            );
+        }
+        self.read_only
+    }
+
     fn show_success(&mut self, message: String) {
         self.view = AppView::Message {
             title: "Success".to_string(),
@@ -1000,10 +1826,13 @@ impl App {
                 Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
             )]),
             Line::from("  ↑/↓       Navigate entry list"),
+            Line::from("  Ctrl+↑/↓  Move selected entry up/down"),
             Line::from("  1-9       Quick jump to entry 1-9"),
             Line::from("  Type #    Type number + Enter (e.g. 15 + Enter)"),
+            Line::from("  a-z       Jump to the first name starting with the typed letters"),
             Line::from("  Enter     View selected entry"),
             Line::from("  /         Start filtering entries"),
+            Line::from("  Tab       Switch between entries in use and archived ones"),
             Line::from("  Esc       Clear filter or number entry"),
             Line::from(""),
             Line::from(vec![Span::styled(
@@ -1015,11 +1844,13 @@ impl App {
             Line::from("  Shift+C   Copy secret to clipboard"),
             Line::from("  Shift+E   Edit selected entry"),
             Line::from("  Shift+D   Delete selected entry"),
-            Line::from("  Shift+F   Find/filter entries"),
+            Line::from("  Shift+R   Archive selected entry (restore it when archived)"),
+            Line::from("  Shift+F   Find/filter entries (also /)"),
             Line::from("  Shift+X   Export vault"),
             Line::from("  Shift+I   Import vault"),
             Line::from("  Shift+P   Change password"),
             Line::from("  Shift+S   Settings"),
+            Line::from("  Shift+L   Lock the vault"),
             Line::from("  ?         Show this help"),
             Line::from("  Shift+Q   Quit application"),
             Line::from(""),
@@ -1030,6 +1861,7 @@ impl App {
             Line::from("  Ctrl+C    Quit from anywhere"),
             Line::from("  Ctrl+Q    Quit from anywhere"),
             Line::from("  F1        Password recovery (login screen)"),
+            Line::from("  F1        Keys for the current screen (elsewhere)"),
             Line::from("  Esc       Go back/cancel"),
             Line::from(""),
             Line::from(vec![Span::styled(
@@ -1052,7 +1884,7 @@ impl App {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Min(1),
-                Constraint::Length(28),
+                Constraint::Length(34),
                 Constraint::Min(1),
             ])
             .split(area);
@@ -1060,7 +1892,39 @@ impl App {
         frame.render_widget(paragraph, chunks[1]);
     }
 
-    fn render_copy_countdown_static(frame: &mut Frame, entry_name: &str, seconds_left: u8) {
+    fn render_blanked_static(frame: &mut Frame) {
+        use ratatui::{
+            layout::{Alignment, Constraint, Direction, Layout},
+            style::{Color, Modifier, Style},
+            text::{Line, Span},
+            widgets::Paragraph,
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(3),
+                Constraint::Min(1),
+            ])
+            .split(frame.area());
+
+        let lines = vec![
+            Line::from(Span::styled(
+                "CryptoKeeper",
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "(press any key)",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), chunks[1]);
+    }
+
+    fn render_copy_countdown_static(frame: &mut Frame, entry_name: &str, seconds_left: u64) {
         use ratatui::{
             layout::{Constraint, Direction, Layout},
             style::{Color, Modifier, Style},
@@ -1085,7 +1949,7 @@ impl App {
             .border_style(Style::default().fg(Color::Green));
 
         let message = format!(
-            "Secret for '{}' copied to clipboard!\n\nClearing in {} second{}...\n\nPress Esc to clear now",
+            "Secret for '{}' copied to clipboard!\n\nClearing in {} second{}...\n\nPress Esc to clear now, Enter to keep it and return",
             entry_name,
             seconds_left,
             if seconds_left == 1 { "" } else { "s" }
@@ -1163,10 +2027,14 @@ impl App {
                         if let Some(path) = self.pending_export_password.take() {
                             if let Some(session) = &self.session {
                                 let password = Zeroizing::new(value);
-                                let backup_path = std::path::Path::new(&path).join("backup.ck");
-                                match crate::vault::storage::write_backup(&session.vault, password.as_bytes(), &backup_path) {
-                                    Ok(_) => {
-                                        self.show_success(format!("Vault exported to {}/backup.ck", path));
+                                let result = crate::commands::export::prepare_directory(&path).and_then(|dir| {
+                                    let backup_path = dir.join("backup.ck");
+                                    crate::vault::storage::write_backup(&session.vault, password.as_bytes(), &backup_path)?;
+                                    Ok(backup_path)
+                                });
+                                match result {
+                                    Ok(backup_path) => {
+                                        self.show_success(format!("Vault exported to {}", backup_path.display()));
                                     }
                                     Err(e) => {
                                         self.show_message("Export Error".to_string(), format!("Failed to export: {}", e), true);
@@ -1175,6 +2043,35 @@ impl App {
                             }
                         }
                     }
+                    InputPurpose::ExportEntryPath(name) => {
+                        let input = InputScreen::new("Export Entry", "Enter backup password:", true);
+                        self.pending_export_password = Some(value);
+                        self.view = AppView::Input(input, InputPurpose::ExportEntryPassword(name));
+                    }
+                    InputPurpose::ExportEntryPassword(name) => {
+                        if let Some(path) = self.pending_export_password.take() {
+                            let single = self.session.as_ref().and_then(|s| s.vault.single_entry_vault(&name));
+                            if let Some(single) = single {
+                                let password = Zeroizing::new(value);
+                                let file_name = crate::commands::export::entry_file_name(&single.entries[0].name);
+                                let result = crate::commands::export::prepare_directory(&path).and_then(|dir| {
+                                    let backup_path = dir.join(&file_name);
+                                    crate::vault::storage::write_backup(&single, password.as_bytes(), &backup_path)?;
+                                    Ok(backup_path)
+                                });
+                                match result {
+                                    Ok(backup_path) => {
+                                        self.show_success(format!("'{}' exported to {}", name, backup_path.display()));
+                                    }
+                                    Err(e) => {
+                                        self.show_message("Export Error".to_string(), format!("Failed to export: {}", e), true);
+                                    }
+                                }
+                            } else {
+                                self.return_to_dashboard();
+                            }
+                        }
+                    }
                     InputPurpose::ImportPath => {
                         let input = InputScreen::new("Import Vault", "Enter backup password:", true);
                         self.pending_export_password = Some(value);
@@ -1182,21 +2079,17 @@ impl App {
                     }
                     InputPurpose::ImportPassword => {
                         if let Some(path) = self.pending_export_password.take() {
-                            if let Some(session) = &mut self.session {
+                            if let Some(session) = &self.session {
                                 let password = Zeroizing::new(value);
                                 match crate::vault::storage::read_backup(password.as_bytes(), std::path::Path::new(&path)) {
                                     Ok(backup) => {
-                                        let mut imported = 0;
-                                        for entry in backup.entries {
-                                            if !session.vault.has_entry(&entry.name) {
-                                                session.vault.entries.push(entry);
-                                                imported += 1;
-                                            }
-                                        }
-                                        if imported > 0 {
-                                            let _ = session.save();
-                                        }
-                                        self.show_success(format!("Imported {} entries from backup", imported));
+                                        let preview = ImportPreviewScreen::new(
+                                            &path,
+                                            backup,
+                                            &session.vault,
+                                            self.config.network_glyphs,
+                                        );
+                                        self.view = AppView::ImportPreview(preview);
                                     }
                                     Err(e) => {
                                         self.show_message("Import Error".to_string(), format!("Failed to import: {}", e), true);
@@ -1206,6 +2099,10 @@ impl App {
                         }
                     }
                     InputPurpose::ChangePassword => {
+                        if let Err(e) = self.config.password_policy.check(&value) {
+                            self.show_message("Password Change Error".to_string(), e.to_string(), true);
+                            return Ok(());
+                        }
                         let input = InputScreen::new("Change Password", "Confirm new password:", true);
                         self.pending_new_password = Some(value);
                         self.view = AppView::Input(input, InputPurpose::ConfirmPassword);
@@ -1215,19 +2112,14 @@ impl App {
                             if new_pass == value {
                                 if let Some(session) = &mut self.session {
                                     let password = Zeroizing::new(new_pass);
-                                    match crate::vault::storage::save_vault(&session.vault, password.as_bytes()) {
-                                        Ok(_) => {
-                                            // Warn about recovery invalidation
+                                    match session.change_password(password) {
+                                        Ok(()) => {
+                                            // The user agreed to this before starting the change
                                             let has_recovery = self.config.recovery.is_some();
-                                            session.password = password.clone();
                                             if has_recovery {
                                                 self.config.recovery = None;
                                                 let _ = crate::config::save_config(&self.config);
-                                                self.show_message(
-                                                    "Password Changed".into(),
-                                                    "Master password changed successfully!\n\nNote: Your recovery question has been cleared.\nPlease set up a new one in Settings (Shift+S).".into(),
-                                                    false,
-                                                );
+                                                self.view = AppView::RecoverySetup(RecoverySetupScreen::resetup());
                                             } else {
                                                 self.show_success("Master password changed successfully!".to_string());
                                             }
@@ -1252,4 +2144,57 @@ impl App {
 #[derive(Clone)]
 pub enum ConfirmAction {
     Delete(String),
+    /// The vault changed on disk: yes overwrites it, no reloads from disk
+    OverwriteVault,
+    /// Quit even though the previous screen has unsaved input
+    DiscardAndQuit,
+    /// Start changing the master password (clears the recovery question, if set)
+    ChangePassword,
+    /// Copy a private key or seed phrase after the user confirms
+    CopySecret {
+        entry_name: String,
+        secret: Zeroizing<String>,
+        timeout: u64,
+    },
+    /// Add an entry whose address another entry already has
+    AddEntry(Box<Entry>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dashboard_app() -> App {
+        App {
+            config: Config::default(),
+            session: None,
+            view: AppView::Dashboard(Dashboard::new(Vec::new())),
+            should_quit: false,
+            clipboard_clear_time: None,
+            pending_export_password: None,
+            pending_new_password: None,
+            pending_view_entry_idx: None,
+            pending_copy_entry_idx: None,
+            pending_unlock: None,
+            read_only: false,
+            last_copied: None,
+            last_input: Instant::now(),
+            dirty: false,
+            confirm_from_view: None,
+            help_overlay: None,
+            window_title: String::new(),
+            blanked_by_focus: false,
+            show_archived: false,
+            pending_last_selected: None,
+        }
+    }
+
+    #[test]
+    fn slash_opens_the_filter_after_typeahead() {
+        let mut app = dashboard_app();
+        app.handle_key(KeyCode::Char('a'), KeyModifiers::NONE).unwrap();
+        assert!(matches!(app.view, AppView::Dashboard(_)));
+        app.handle_key(KeyCode::Char('/'), KeyModifiers::NONE).unwrap();
+        assert!(matches!(app.view, AppView::Search(_)));
+    }
 }
@@ -138,11 +138,11 @@ pub fn print_table_box(
         }
         // Simple indented table
         let col_count = headers.len();
-        let mut col_widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+        let mut col_widths: Vec<usize> = headers.iter().map(|h| display_width(h)).collect();
         for row in rows {
             for (i, cell) in row.iter().enumerate() {
                 if i < col_count {
-                    col_widths[i] = col_widths[i].max(cell.len());
+                    col_widths[i] = col_widths[i].max(display_width(cell));
                 }
             }
         }
@@ -150,18 +150,18 @@ pub fn print_table_box(
         let header_line: String = headers
             .iter()
             .enumerate()
-            .map(|(i, h)| format!("{:<width$}", h, width = col_widths[i] + 2))
+            .map(|(i, h)| pad_to(h, col_widths[i] + 2))
             .collect();
         println!("  {}", header_line);
-        println!("  {}", "─".repeat(header_line.len()));
+        println!("  {}", "─".repeat(display_width(&header_line)));
         // Rows
         for row in rows {
             let row_line: String = row
                 .iter()
                 .enumerate()
                 .map(|(i, cell)| {
-                    let w = if i < col_count { col_widths[i] + 2 } else { cell.len() + 2 };
-                    format!("{:<width$}", cell, width = w)
+                    let w = if i < col_count { col_widths[i] + 2 } else { display_width(cell) + 2 };
+                    pad_to(cell, w)
                 })
                 .collect();
             println!("  {}", row_line);
@@ -236,7 +236,7 @@ pub fn print_table_box(
             .iter()
             .enumerate()
             .map(|(i, cell)| {
-                let w = if i < col_widths.len() { col_widths[i] } else { cell.len() };
+                let w = if i < col_widths.len() { col_widths[i] } else { display_width(cell) };
                 let truncated = truncate_display(cell, w.saturating_sub(1)); // leave 1 col gap
                 let style_fn = col_styles.get(i).copied().unwrap_or(default_style);
                 let styled = format!("{}", style_fn(&truncated));
@@ -274,11 +274,11 @@ fn compute_col_widths(
     }
 
     // Measure natural widths (max content + 2 padding)
-    let mut natural: Vec<usize> = headers.iter().map(|h| h.len() + 2).collect();
+    let mut natural: Vec<usize> = headers.iter().map(|h| display_width(h) + 2).collect();
     for row in rows {
         for (i, cell) in row.iter().enumerate() {
             if i < col_count {
-                natural[i] = natural[i].max(cell.len() + 2);
+                natural[i] = natural[i].max(display_width(cell) + 2);
             }
         }
     }
@@ -333,3 +333,12 @@ pub fn print_error(msg: &str) {
     eprintln!("  {} {}", "Error:".red().bold(), msg);
 }
 
+
+/// Print a warning message with a styled exclamation mark.
+pub fn print_warning(msg: &str) {
+    if !is_interactive() {
+        eprintln!("  Warning: {}", msg);
+        return;
+    }
+    eprintln!("  {} {}", "!".yellow().bold(), msg);
+}
@@ -0,0 +1,180 @@
+use colored::{ColoredString, Colorize};
+
+use crate::config::model::{Config, ListColumn};
+use crate::ui::theme::network_label;
+use crate::vault::model::{EntryMeta, SecretType};
+
+/// How one entry list column is headed, filled and drawn.
+pub struct ColumnSpec {
+    pub header: &'static str,
+    /// Cell text for an entry, given whether network glyphs are on; empty when
+    /// the entry has no value
+    pub value: fn(&EntryMeta, bool) -> String,
+    /// Color of the cell in the CLI table
+    pub style: fn(&str) -> ColoredString,
+    /// Longest value the CLI table prints before truncating (0: no limit)
+    pub max_width: usize,
+    /// Share of the dashboard table's width relative to the other columns
+    pub weight: u16,
+}
+
+/// Columns `list` shows unless configured otherwise.
+pub const CLI_COLUMNS: [ListColumn; 5] = [
+    ListColumn::Name,
+    ListColumn::Network,
+    ListColumn::Type,
+    ListColumn::Username,
+    ListColumn::AddressOrUrl,
+];
+
+/// Columns the dashboard shows unless configured otherwise.
+pub const DASHBOARD_COLUMNS: [ListColumn; 4] = [
+    ListColumn::Name,
+    ListColumn::Type,
+    ListColumn::Network,
+    ListColumn::Address,
+];
+
+/// The configured columns, or `default` when none are set.
+pub fn configured_columns(config: &Config, default: &[ListColumn]) -> Vec<ListColumn> {
+    config
+        .list_columns
+        .clone()
+        .unwrap_or_else(|| default.to_vec())
+}
+
+pub fn type_color(s: &str) -> ColoredString {
+    match s {
+        "Private Key" => s.yellow(),
+        "Seed Phrase" => s.magenta(),
+        "Password" => s.green(),
+        _ => s.normal(),
+    }
+}
+
+pub fn column_spec(column: ListColumn) -> ColumnSpec {
+    match column {
+        ListColumn::Name => ColumnSpec {
+            header: "Name",
+            value: |e, _| e.name.clone(),
+            style: |s| s.cyan(),
+            max_width: 0,
+            weight: 3,
+        },
+        ListColumn::Type => ColumnSpec {
+            header: "Type",
+            value: |e, _| e.secret_type.to_string(),
+            style: type_color,
+            max_width: 0,
+            weight: 2,
+        },
+        ListColumn::Network => ColumnSpec {
+            header: "Network",
+            value: |e, glyphs| network_label(&e.network, glyphs),
+            style: |s| s.normal(),
+            max_width: 0,
+            weight: 2,
+        },
+        ListColumn::Username => ColumnSpec {
+            header: "Username",
+            value: |e, _| e.username.clone().unwrap_or_default(),
+            style: |s| s.normal(),
+            max_width: 0,
+            weight: 2,
+        },
+        ListColumn::Address => ColumnSpec {
+            header: "Public Address",
+            value: |e, _| e.public_address.clone().or_else(|| e.username.clone()).unwrap_or_default(),
+            style: |s| s.dimmed(),
+            max_width: 20,
+            weight: 3,
+        },
+        ListColumn::Url => ColumnSpec {
+            header: "URL",
+            value: |e, _| e.url.clone().unwrap_or_default(),
+            style: |s| s.dimmed(),
+            max_width: 30,
+            weight: 3,
+        },
+        ListColumn::AddressOrUrl => ColumnSpec {
+            header: "Address / URL",
+            value: |e, _| {
+                let value = if e.secret_type == SecretType::Password {
+                    &e.url
+                } else {
+                    &e.public_address
+                };
+                value.clone().unwrap_or_default()
+            },
+            style: |s| s.dimmed(),
+            max_width: 20,
+            weight: 3,
+        },
+        ListColumn::Notes => ColumnSpec {
+            header: "Notes",
+            value: |e, _| e.notes.lines().next().unwrap_or_default().to_string(),
+            style: |s| s.dimmed(),
+            max_width: 30,
+            weight: 3,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::parse_list_columns;
+
+    fn headers(columns: &[ListColumn]) -> Vec<&'static str> {
+        columns.iter().map(|c| column_spec(*c).header).collect()
+    }
+
+    #[test]
+    fn defaults_keep_the_built_in_layouts() {
+        let config = Config::default();
+        assert_eq!(configured_columns(&config, &CLI_COLUMNS), CLI_COLUMNS);
+        assert_eq!(
+            headers(&configured_columns(&config, &CLI_COLUMNS)),
+            ["Name", "Network", "Type", "Username", "Address / URL"]
+        );
+        assert_eq!(
+            headers(&configured_columns(&config, &DASHBOARD_COLUMNS)),
+            ["Name", "Type", "Network", "Public Address"]
+        );
+    }
+
+    #[test]
+    fn configured_order_and_reset() {
+        let mut config = Config {
+            list_columns: parse_list_columns("url,name").unwrap(),
+            ..Config::default()
+        };
+        assert_eq!(headers(&configured_columns(&config, &CLI_COLUMNS)), ["URL", "Name"]);
+
+        assert!(parse_list_columns("name,balance").is_err());
+
+        config.list_columns = parse_list_columns("").unwrap();
+        assert_eq!(configured_columns(&config, &DASHBOARD_COLUMNS), DASHBOARD_COLUMNS);
+    }
+
+    #[test]
+    fn address_columns_fall_back_by_entry_type() {
+        let meta = EntryMeta {
+            name: "Mail".to_string(),
+            network: String::new(),
+            secret_type: SecretType::Password,
+            public_address: None,
+            username: Some("alice".to_string()),
+            url: Some("https://mail.example".to_string()),
+            notes: String::new(),
+            has_secondary_password: false,
+            mask_metadata: false,
+            archived: false,
+        };
+
+        let value = |column| (column_spec(column).value)(&meta, false);
+        assert_eq!(value(ListColumn::Address), "alice");
+        assert_eq!(value(ListColumn::AddressOrUrl), "https://mail.example");
+        assert_eq!(value(ListColumn::Url), "https://mail.example");
+    }
+}
@@ -8,11 +8,49 @@ use ratatui::{
     Frame,
 };
 
+use super::borders::truncate_display;
 use super::get_terminal_width;
 use super::theme::dim_border;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Narrowest terminal that gets the block-letter art.
+const WIDE_MIN_WIDTH: usize = 70;
+/// Narrowest terminal that gets the boxed title.
+const MEDIUM_MIN_WIDTH: usize = 50;
+/// Widest the header box grows; wider terminals center it.
+const MAX_HEADER_WIDTH: usize = 80;
+
+/// Which header layout fits the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderSize {
+    Wide,
+    Medium,
+    Narrow,
+}
+
+/// Pick the header layout for a terminal `width` columns wide.
+pub fn choose_header(width: usize) -> HeaderSize {
+    if width >= WIDE_MIN_WIDTH {
+        HeaderSize::Wide
+    } else if width >= MEDIUM_MIN_WIDTH {
+        HeaderSize::Medium
+    } else {
+        HeaderSize::Narrow
+    }
+}
+
+/// Width of the header box and the margin that centers it on the terminal.
+fn header_box(width: usize) -> (usize, usize) {
+    let box_width = width.min(MAX_HEADER_WIDTH);
+    (box_width, (width - box_width) / 2)
+}
+
+/// Title for the one-line header, shortened so it and its padding fit `width`.
+fn narrow_title(width: usize) -> String {
+    truncate_display(&format!("CRYPTOKEEPER v{}", VERSION), width.saturating_sub(2))
+}
+
 /// Measure display width of a string, ignoring ANSI escape codes.
 fn display_width(s: &str) -> usize {
     let stripped = console::strip_ansi_codes(s);
@@ -23,18 +61,18 @@ fn display_width(s: &str) -> usize {
 pub fn print_header() {
     let width = get_terminal_width() as usize;
 
-    if width >= 70 {
-        print_wide_header(width);
-    } else if width >= 50 {
-        print_medium_header(width);
-    } else {
-        print_narrow_header(width);
+    match choose_header(width) {
+        HeaderSize::Wide => print_wide_header(width),
+        HeaderSize::Medium => print_medium_header(width),
+        HeaderSize::Narrow => print_narrow_header(width),
     }
     println!();
 }
 
 fn print_wide_header(width: usize) {
-    let inner = width.saturating_sub(4); // "│ " + " │"
+    let (box_width, margin) = header_box(width);
+    let margin = " ".repeat(margin);
+    let inner = box_width.saturating_sub(4); // "│ " + " │"
 
     // Block letter art for CRYPTO
     let crypto_lines = [
@@ -64,7 +102,8 @@ fn print_wide_header(width: usize) {
     let title_dw = display_width(&title_embed);
     let remaining = (inner + 2).saturating_sub(title_dw + 1);
     println!(
-        "{}{}{}{}{}",
+        "{}{}{}{}{}{}",
+        margin,
         dim_border("┌"),
         dim_border("─"),
         title_embed.cyan().bold(),
@@ -73,31 +112,32 @@ fn print_wide_header(width: usize) {
     );
 
     // Empty line
-    print_padded_line("", inner);
+    print_padded_line("", inner, &margin);
 
     // CRYPTO art lines (centered, bold cyan)
     for line in &crypto_lines {
-        print_centered_art(line, inner);
+        print_centered_art(line, inner, &margin);
     }
 
     // KEEPER art lines (centered, bold cyan)
     for line in &keeper_lines {
-        print_centered_art(line, inner);
+        print_centered_art(line, inner, &margin);
     }
 
     // Empty line
-    print_padded_line("", inner);
+    print_padded_line("", inner, &margin);
 
     // Version + tagline (centered, dimmed)
     let info = format!("{} — {}", version_line, tagline);
-    print_centered_line(&format!("{}", info.dimmed()), &info, inner);
+    print_centered_line(&format!("{}", info.dimmed()), &info, inner, &margin);
 
     // Empty line
-    print_padded_line("", inner);
+    print_padded_line("", inner, &margin);
 
     // Bottom border
     println!(
-        "{}{}{}",
+        "{}{}{}{}",
+        margin,
         dim_border("└"),
         dim_border(&"─".repeat(inner + 2)),
         dim_border("┘")
@@ -121,20 +161,20 @@ fn print_medium_header(width: usize) {
     );
 
     // Empty line
-    print_padded_line("", inner);
+    print_padded_line("", inner, "");
 
     // Title (centered, bold cyan)
-    print_centered_line(&format!("{}", title.bold().cyan()), title, inner);
+    print_centered_line(&format!("{}", title.bold().cyan()), title, inner, "");
 
     // Empty line
-    print_padded_line("", inner);
+    print_padded_line("", inner, "");
 
     // Version + tagline
-    let info = format!("{} — {}", version_line, tagline);
-    print_centered_line(&format!("{}", info.dimmed()), &info, inner);
+    let info = truncate_display(&format!("{} — {}", version_line, tagline), inner);
+    print_centered_line(&format!("{}", info.dimmed()), &info, inner, "");
 
     // Empty line
-    print_padded_line("", inner);
+    print_padded_line("", inner, "");
 
     // Bottom border
     println!(
@@ -146,7 +186,7 @@ fn print_medium_header(width: usize) {
 }
 
 fn print_narrow_header(width: usize) {
-    let text = format!("CRYPTOKEEPER v{}", VERSION);
+    let text = narrow_title(width);
     let text_dw = display_width(&text);
     let side = width.saturating_sub(text_dw + 2) / 2;
     let right_side = width.saturating_sub(text_dw + 2 + side);
@@ -161,12 +201,13 @@ fn print_narrow_header(width: usize) {
 }
 
 /// Print a centered line (no ANSI codes in input) within bordered row.
-fn print_centered_art(line: &str, inner: usize) {
+fn print_centered_art(line: &str, inner: usize, margin: &str) {
     let art_width = display_width(line);
     let left_pad = inner.saturating_sub(art_width) / 2;
     let right_pad = inner.saturating_sub(art_width + left_pad);
     println!(
-        "{} {}{}{} {}",
+        "{}{} {}{}{} {}",
+        margin,
         dim_border("│"),
         " ".repeat(left_pad),
         line.bold().cyan(),
@@ -175,11 +216,12 @@ fn print_centered_art(line: &str, inner: usize) {
     );
 }
 
-fn print_padded_line(content: &str, inner: usize) {
+fn print_padded_line(content: &str, inner: usize, margin: &str) {
     let content_width = display_width(content);
     let padding = inner.saturating_sub(content_width);
     println!(
-        "{} {}{} {}",
+        "{}{} {}{} {}",
+        margin,
         dim_border("│"),
         content,
         " ".repeat(padding),
@@ -187,12 +229,13 @@ fn print_padded_line(content: &str, inner: usize) {
     );
 }
 
-fn print_centered_line(styled: &str, raw: &str, inner: usize) {
+fn print_centered_line(styled: &str, raw: &str, inner: usize, margin: &str) {
     let raw_width = display_width(raw);
     let left_pad = inner.saturating_sub(raw_width) / 2;
     let right_pad = inner.saturating_sub(raw_width + left_pad);
     println!(
-        "{} {}{}{} {}",
+        "{}{} {}{}{} {}",
+        margin,
         dim_border("│"),
         " ".repeat(left_pad),
         styled,
@@ -203,16 +246,20 @@ fn print_centered_line(styled: &str, raw: &str, inner: usize) {
 
 pub fn render_header(frame: &mut Frame, area: Rect) {
     let width = area.width as usize;
-    
-    let content = if width >= 70 {
-        build_wide_header()
-    } else if width >= 50 {
-        build_medium_header()
-    } else {
-        build_narrow_header()
-    };
-    
-    frame.render_widget(content, area);
+
+    match choose_header(width) {
+        HeaderSize::Wide => {
+            let (box_width, margin) = header_box(width);
+            let area = Rect {
+                x: area.x + margin as u16,
+                width: box_width as u16,
+                ..area
+            };
+            frame.render_widget(build_wide_header(), area);
+        }
+        HeaderSize::Medium => frame.render_widget(build_medium_header(), area),
+        HeaderSize::Narrow => frame.render_widget(build_narrow_header(width), area),
+    }
 }
 
 fn build_wide_header() -> Paragraph<'static> {
@@ -298,8 +345,8 @@ fn build_medium_header() -> Paragraph<'static> {
         .alignment(Alignment::Center)
 }
 
-fn build_narrow_header() -> Paragraph<'static> {
-    let text = format!("CRYPTOKEEPER v{}", VERSION);
+fn build_narrow_header(width: usize) -> Paragraph<'static> {
+    let text = narrow_title(width);
     
     let style = Style::default()
         .fg(Color::Cyan)
@@ -315,3 +362,34 @@ fn build_narrow_header() -> Paragraph<'static> {
         .block(block)
         .alignment(Alignment::Center)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choose_header_thresholds() {
+        assert_eq!(choose_header(200), HeaderSize::Wide);
+        assert_eq!(choose_header(70), HeaderSize::Wide);
+        assert_eq!(choose_header(69), HeaderSize::Medium);
+        assert_eq!(choose_header(50), HeaderSize::Medium);
+        assert_eq!(choose_header(49), HeaderSize::Narrow);
+        assert_eq!(choose_header(0), HeaderSize::Narrow);
+    }
+
+    #[test]
+    fn test_header_box_centers_on_wide_terminals() {
+        assert_eq!(header_box(70), (70, 0));
+        assert_eq!(header_box(80), (80, 0));
+        assert_eq!(header_box(200), (80, 60));
+        assert_eq!(header_box(81), (80, 0));
+    }
+
+    #[test]
+    fn test_narrow_title_fits() {
+        for width in 2..MEDIUM_MIN_WIDTH {
+            assert!(display_width(&narrow_title(width)) + 2 <= width, "width {}", width);
+        }
+        assert_eq!(narrow_title(40), format!("CRYPTOKEEPER v{}", VERSION));
+    }
+}
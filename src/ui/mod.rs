@@ -1,5 +1,6 @@
 pub mod app;
 pub mod borders;
+pub mod columns;
 pub mod header;
 pub mod prompt;
 pub mod screens;
@@ -26,7 +27,7 @@ pub fn setup_app_theme(clear: bool) {
     if !is_interactive() {
         return;
     }
-    theme::set_title("CryptoKeeper");
+    theme::set_title(theme::APP_TITLE);
     if clear {
         theme::clear_screen();
     }
@@ -4,14 +4,67 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
-use zeroize::Zeroize;
+use zeroize::{Zeroize, Zeroizing};
 
-use crate::crypto::derive::derive_address;
+use crate::config::model::{Config, CustomNetwork};
+use crate::crypto::kdf::KdfParams;
+use crate::crypto::derive::{
+    check_private_key, derive_address_on_path, guess_network, is_evm_network, resolve_network,
+    suggest_mnemonic_fixes, DerivationPath, EVM_NETWORKS,
+};
 use crate::crypto::entry_key;
-use crate::vault::model::{Entry, SecretType};
+use crate::crypto::keygen;
+use crate::ui::theme::Mask;
+use crate::ui::widgets::custom_fields::CustomFieldsEditor;
+use crate::vault::model::{normalize_name, Entry, SecretType};
+
+/// Secret types offered by the type selector, in display order.
+pub const SECRET_TYPES: [SecretType; 3] = [
+    SecretType::PrivateKey,
+    SecretType::SeedPhrase,
+    SecretType::Password,
+];
+
+/// Word counts a BIP39 seed phrase can have.
+const BIP39_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+/// Path levels must fit below the hardened flag.
+const MAX_PATH_LEVEL: u32 = 0x7fff_ffff;
+
+/// Built-in networks offered by the network selector, before the EVM chains.
+pub const BUILTIN_NETWORKS: [&str; 6] = ["Ethereum", "Bitcoin", "Solana", "Tron", "Cosmos", "Ripple"];
+
+/// Networks for the selector: built-ins, EVM chains, the user's custom networks, then "Other".
+pub fn network_options(config: &Config) -> Vec<String> {
+    BUILTIN_NETWORKS
+        .iter()
+        .chain(EVM_NETWORKS.iter())
+        .map(|n| n.to_string())
+        .chain(config.custom_networks.iter().map(|n| n.name.clone()))
+        .chain(std::iter::once("Other".to_string()))
+        .collect()
+}
+
+/// Flatten a pasted block onto one line: line breaks become single spaces
+/// (a seed phrase copied one word per line) and surrounding whitespace is dropped.
+pub fn paste_chars(text: &str) -> impl Iterator<Item = char> + '_ {
+    let mut prev_newline = false;
+    text.trim().chars().filter_map(move |c| match c {
+        '\r' | '\n' => {
+            let first = !prev_newline;
+            prev_newline = true;
+            first.then_some(' ')
+        }
+        c if c.is_control() => None,
+        c => {
+            prev_newline = false;
+            Some(c)
+        }
+    })
+}
 
 pub struct AddEntryScreen {
     current_field: usize,
@@ -20,17 +73,37 @@ pub struct AddEntryScreen {
     secret: String,
     secret_confirm: String,
     network: String,
+    /// BIP44 account of a seed phrase's address, typed as digits (empty: 0)
+    account: String,
+    /// Address index within the account, typed as digits (empty: 0)
+    address_index: String,
     username: String,
     url: String,
     notes: String,
     use_secondary_password: bool,
+    /// Also hide the name and address in listings until unlocked
+    mask_metadata: bool,
     secondary_password: String,
     secondary_password_confirm: String,
     show_type_select: bool,
     type_selected: usize,
     show_network_select: bool,
     network_selected: usize,
+    networks: Vec<String>,
+    custom_networks: Vec<CustomNetwork>,
+    /// Argon2 cost for wrapping the key of a secondary-password entry
+    entry_key_kdf: KdfParams,
+    custom_fields: CustomFieldsEditor,
+    mask: Mask,
     scroll_offset: usize,
+    /// Newly generated secret, shown in full until the user confirms a backup
+    generated: Option<Zeroizing<String>>,
+    /// Why the last Ctrl+G generated nothing
+    generate_error: Option<String>,
+    /// Why the last save attempt saved nothing
+    error_message: Option<String>,
+    /// Esc was pressed over typed input; a second Esc discards it
+    discard_armed: bool,
 }
 
 impl Drop for AddEntryScreen {
@@ -43,45 +116,161 @@ impl Drop for AddEntryScreen {
 }
 
 impl AddEntryScreen {
-    pub fn new() -> Self {
+    pub fn new(config: &Config) -> Self {
+        let type_selected = SECRET_TYPES
+            .iter()
+            .position(|t| *t == config.default_secret_type)
+            .unwrap_or(0);
+        let networks = network_options(config);
+        let network_selected = networks
+            .iter()
+            .position(|n| n.eq_ignore_ascii_case(&config.default_network))
+            .unwrap_or(networks.len() - 1);
+
         Self {
             current_field: 0,
             name: String::new(),
-            secret_type: SecretType::PrivateKey,
+            secret_type: config.default_secret_type.clone(),
             secret: String::new(),
             secret_confirm: String::new(),
-            network: "Ethereum".to_string(),
+            network: config.default_network.clone(),
+            account: String::new(),
+            address_index: String::new(),
             username: String::new(),
             url: String::new(),
             notes: String::new(),
             use_secondary_password: false,
+            mask_metadata: false,
             secondary_password: String::new(),
             secondary_password_confirm: String::new(),
             show_type_select: false,
-            type_selected: 0,
+            type_selected,
             show_network_select: false,
-            network_selected: 0,
+            network_selected,
+            networks,
+            custom_networks: config.custom_networks.clone(),
+            entry_key_kdf: config.entry_key_kdf,
+            custom_fields: CustomFieldsEditor::new(Vec::new()),
+            mask: Mask::from_config(config),
             scroll_offset: 0,
+            generated: None,
+            generate_error: None,
+            error_message: None,
+            discard_armed: false,
         }
     }
 
+    /// Whether leaving now would discard typed input.
+    pub fn has_pending_input(&self) -> bool {
+        let typed = [
+            &self.name,
+            &self.secret,
+            &self.secret_confirm,
+            &self.account,
+            &self.address_index,
+            &self.username,
+            &self.url,
+            &self.notes,
+            &self.secondary_password,
+            &self.secondary_password_confirm,
+        ]
+        .iter()
+        .any(|field| !field.is_empty());
+        typed || !self.custom_fields.is_empty() || self.generated.is_some()
+    }
+
+    /// Insert pasted text into the focused field. Secret fields render as dots,
+    /// so the pasted value is never drawn.
+    pub fn handle_paste(&mut self, text: &str) {
+        if self.show_type_select || self.show_network_select || self.generated.is_some() {
+            return;
+        }
+        self.discard_armed = false;
+        for c in paste_chars(text) {
+            self.insert_char(c);
+        }
+    }
+
+    /// Keys listed in the F1 help overlay.
+    pub fn help_keys(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("Tab / ↓", "Next field"),
+            ("Shift+Tab / ↑", "Previous field"),
+            ("Enter", "Open selector, toggle, or save on the last field"),
+            ("Ctrl+S", "Save entry"),
+            ("Ctrl+N", "Switch to the network the key looks like"),
+            ("Ctrl+G", "Generate a new key or seed phrase"),
+            ("Ctrl+F", "Add a custom field"),
+            ("Ctrl+T", "Mark custom field sensitive"),
+            ("Ctrl+D", "Remove custom field"),
+            ("Esc", "Close a selector; press twice to discard the entry"),
+        ]
+    }
+
     pub fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> AddEntryAction {
+        self.generate_error = None;
+        self.error_message = None;
+        if self.generated.is_some() {
+            self.handle_generated(key);
+            return AddEntryAction::Continue;
+        }
+
         if key == KeyCode::Esc {
-            return AddEntryAction::Cancel;
+            return self.step_back();
         }
+        self.discard_armed = false;
 
         if modifiers.contains(KeyModifiers::CONTROL) && key == KeyCode::Char('s') {
             return self.try_save();
         }
 
+        if modifiers.contains(KeyModifiers::CONTROL) && key == KeyCode::Char('n') {
+            if let Some(network) = self.network_suggestion() {
+                self.network = network.to_string();
+                self.network_selected = self
+                    .networks
+                    .iter()
+                    .position(|n| n == network)
+                    .unwrap_or(self.network_selected);
+            }
+            return AddEntryAction::Continue;
+        }
+
         if self.show_type_select {
             return self.handle_type_select(key);
         }
 
+        if modifiers.contains(KeyModifiers::CONTROL) && key == KeyCode::Char('g') {
+            if !self.show_network_select && self.is_crypto_type() {
+                self.generate_secret();
+            }
+            return AddEntryAction::Continue;
+        }
+
         if self.show_network_select {
             return self.handle_network_select(key);
         }
 
+        if modifiers.contains(KeyModifiers::CONTROL) && key == KeyCode::Char('f') {
+            self.current_field = self.custom_start() + self.custom_fields.add_row();
+            return AddEntryAction::Continue;
+        }
+
+        if modifiers.contains(KeyModifiers::CONTROL) && key == KeyCode::Char('t') {
+            if let Some(slot) = self.custom_slot() {
+                self.custom_fields.toggle_sensitive(slot);
+            }
+            return AddEntryAction::Continue;
+        }
+
+        if modifiers.contains(KeyModifiers::CONTROL) && key == KeyCode::Char('d') {
+            if let Some(slot) = self.custom_slot() {
+                self.custom_fields.remove_row(slot);
+                self.current_field = self.current_field.min(self.field_count() - 1);
+            }
+            return AddEntryAction::Continue;
+        }
+
         match key {
             KeyCode::Tab => {
                 self.current_field = (self.current_field + 1) % self.field_count();
@@ -114,9 +303,14 @@ impl AddEntryScreen {
                 else if self.is_crypto_type() && self.current_field == 4 {
                     self.show_network_select = true;
                 }
-                // Secondary password toggle
+                // Secondary password toggle: No -> Yes -> Yes, hiding the name -> No
                 else if self.current_field == self.secondary_toggle_field() {
-                    self.use_secondary_password = !self.use_secondary_password;
+                    if self.use_secondary_password && !self.mask_metadata {
+                        self.mask_metadata = true;
+                    } else {
+                        self.use_secondary_password = !self.use_secondary_password;
+                        self.mask_metadata = false;
+                    }
                     if !self.use_secondary_password {
                         self.secondary_password.zeroize();
                         self.secondary_password = String::new();
@@ -144,6 +338,58 @@ impl AddEntryScreen {
         }
     }
 
+    /// Esc closes an open selector first. Over typed input it only arms
+    /// discarding, which a second Esc confirms; an empty form cancels at once.
+    fn step_back(&mut self) -> AddEntryAction {
+        if self.show_type_select || self.show_network_select {
+            self.show_type_select = false;
+            self.show_network_select = false;
+        } else if self.discard_armed || !self.has_pending_input() {
+            return AddEntryAction::Cancel;
+        } else {
+            self.discard_armed = true;
+        }
+        AddEntryAction::Continue
+    }
+
+    /// Generate a seed phrase, or a private key for the selected network, and
+    /// show it for backup.
+    fn generate_secret(&mut self) {
+        let generated = if self.secret_type == SecretType::SeedPhrase {
+            keygen::generate_mnemonic().map(Some)
+        } else {
+            keygen::generate_private_key(resolve_network(&self.network, &self.custom_networks))
+        };
+        match generated {
+            Ok(Some(secret)) => self.generated = Some(secret),
+            Ok(None) => {
+                self.generate_error = Some(format!(
+                    "Cannot generate a {} key; choose a supported network first",
+                    self.network
+                ));
+            }
+            Err(e) => self.generate_error = Some(e.to_string()),
+        }
+    }
+
+    /// Keys on the backup screen of a generated secret: Y fills the secret
+    /// fields with it, Esc throws it away.
+    fn handle_generated(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                let Some(generated) = self.generated.take() else {
+                    return;
+                };
+                self.secret.zeroize();
+                self.secret = generated.to_string();
+                self.secret_confirm.zeroize();
+                self.secret_confirm = generated.to_string();
+            }
+            KeyCode::Esc => self.generated = None,
+            _ => {}
+        }
+    }
+
     fn handle_type_select(&mut self, key: KeyCode) -> AddEntryAction {
         match key {
             KeyCode::Up => {
@@ -152,22 +398,15 @@ impl AddEntryScreen {
                 }
             }
             KeyCode::Down => {
-                if self.type_selected < 2 {
+                if self.type_selected < SECRET_TYPES.len() - 1 {
                     self.type_selected += 1;
                 }
             }
             KeyCode::Enter => {
-                self.secret_type = match self.type_selected {
-                    0 => SecretType::PrivateKey,
-                    1 => SecretType::SeedPhrase,
-                    _ => SecretType::Password,
-                };
+                self.secret_type = SECRET_TYPES[self.type_selected].clone();
                 self.show_type_select = false;
                 self.current_field += 1;
             }
-            KeyCode::Esc => {
-                self.show_type_select = false;
-            }
             _ => {}
         }
         AddEntryAction::Continue
@@ -181,53 +420,135 @@ impl AddEntryScreen {
                 }
             }
             KeyCode::Down => {
-                if self.network_selected < 3 {
+                if self.network_selected < self.networks.len() - 1 {
                     self.network_selected += 1;
                 }
             }
             KeyCode::Enter => {
-                self.network = match self.network_selected {
-                    0 => "Ethereum",
-                    1 => "Bitcoin",
-                    2 => "Solana",
-                    _ => "Other",
-                }
-                .to_string();
+                self.network = self.networks[self.network_selected].clone();
                 self.show_network_select = false;
                 self.current_field += 1;
             }
-            KeyCode::Esc => {
-                self.show_network_select = false;
-            }
             _ => {}
         }
         AddEntryAction::Continue
     }
 
+    /// Network the pasted private key appears to belong to, if it differs from
+    /// the selected one.
+    fn network_suggestion(&self) -> Option<&'static str> {
+        if self.secret_type != SecretType::PrivateKey {
+            return None;
+        }
+        let mut current = resolve_network(&self.network, &self.custom_networks);
+        // Tron, Ripple, Cosmos and EVM keys are the same 32-byte hex as Ethereum's
+        if is_evm_network(current)
+            || matches!(current.to_lowercase().as_str(), "tron" | "ripple" | "xrp")
+        {
+            current = "Ethereum";
+        }
+        #[cfg(feature = "derive-cosmos")]
+        if crate::crypto::derive::cosmos_prefix(current).is_some() {
+            current = "Ethereum";
+        }
+        guess_network(&self.secret).filter(|n| !n.eq_ignore_ascii_case(current))
+    }
+
+    /// Why the entered private key looks unusable or unsafe, or the first
+    /// misspelled word of a seed phrase, if any.
+    fn key_warning(&self) -> Option<String> {
+        if self.secret.is_empty() {
+            return None;
+        }
+        if self.secret_type == SecretType::SeedPhrase {
+            if let Some((index, word)) = suggest_mnemonic_fixes(&self.secret).into_iter().next() {
+                return Some(format!("Word {} is not a BIP39 word. Did you mean '{}'?", index + 1, word));
+            }
+            let (count, valid) = self.seed_word_count()?;
+            return (!valid).then(|| {
+                format!("Seed phrases have 12, 15, 18, 21 or 24 words; this one has {}", count)
+            });
+        }
+        if self.secret_type != SecretType::PrivateKey {
+            return None;
+        }
+        let network = resolve_network(&self.network, &self.custom_networks);
+        check_private_key(&self.secret, network).err().map(|e| e.to_string())
+    }
+
+    /// Words typed into a seed phrase and whether that is a valid BIP39 length.
+    /// Other secret types have no word count.
+    fn seed_word_count(&self) -> Option<(usize, bool)> {
+        if self.secret_type != SecretType::SeedPhrase || self.secret.is_empty() {
+            return None;
+        }
+        let count = self.secret.split_whitespace().count();
+        Some((count, BIP39_WORD_COUNTS.contains(&count)))
+    }
+
+    /// Seed phrases get account(5) and address index(6) inputs after the
+    /// network, which move the fields below them down by two.
+    fn has_path_fields(&self) -> bool {
+        self.secret_type == SecretType::SeedPhrase
+    }
+
+    /// Account and address index from their inputs, or why they are unusable.
+    fn path_levels(&self) -> std::result::Result<(u32, u32), String> {
+        let level = |digits: &str, what: &str| {
+            if digits.is_empty() {
+                return Ok(0);
+            }
+            digits
+                .parse()
+                .ok()
+                .filter(|level| *level <= MAX_PATH_LEVEL)
+                .ok_or_else(|| format!("{} must be at most {}", what, MAX_PATH_LEVEL))
+        };
+        Ok((level(&self.account, "Account")?, level(&self.address_index, "Address index")?))
+    }
+
     /// Field index of the secondary password toggle.
     fn secondary_toggle_field(&self) -> usize {
         if self.is_crypto_type() {
-            // name(0), type(1), secret(2), confirm(3), network(4), notes(5), toggle(6)
-            6
+            // name(0), type(1), secret(2), confirm(3), network(4), notes(5), toggle(6),
+            // or with a seed phrase account(5), index(6), notes(7), toggle(8)
+            if self.has_path_fields() { 8 } else { 6 }
         } else {
             // name(0), type(1), secret(2), confirm(3), username(4), url(5), notes(6), toggle(7)
             7
         }
     }
 
+    /// Field index of the first custom field slot (after all fixed fields).
+    fn custom_start(&self) -> usize {
+        self.field_count() - self.custom_fields.slot_count()
+    }
+
+    /// Slot within the custom fields list, if one is focused.
+    fn custom_slot(&self) -> Option<usize> {
+        self.current_field.checked_sub(self.custom_start())
+    }
+
     fn insert_char(&mut self, c: char) {
+        if let Some(slot) = self.custom_slot() {
+            self.custom_fields.insert_char(slot, c);
+            return;
+        }
         if self.is_crypto_type() {
+            let toggle = self.secondary_toggle_field();
             match self.current_field {
                 0 => self.name.push(c),
                 2 => self.secret.push(c),
                 3 => self.secret_confirm.push(c),
                 // 4 = network selector, no typing
-                5 => self.notes.push(c),
-                // 6 = toggle, no typing
-                f if self.use_secondary_password && f == 7 => {
+                5 if self.has_path_fields() => push_digit(&mut self.account, c),
+                6 if self.has_path_fields() => push_digit(&mut self.address_index, c),
+                f if f == toggle - 1 => self.notes.push(c),
+                // toggle, no typing
+                f if self.use_secondary_password && f == toggle + 1 => {
                     self.secondary_password.push(c);
                 }
-                f if self.use_secondary_password && f == 8 => {
+                f if self.use_secondary_password && f == toggle + 2 => {
                     self.secondary_password_confirm.push(c);
                 }
                 _ => {}
@@ -253,16 +574,23 @@ impl AddEntryScreen {
     }
 
     fn delete_char(&mut self) {
+        if let Some(slot) = self.custom_slot() {
+            self.custom_fields.delete_char(slot);
+            return;
+        }
         if self.is_crypto_type() {
+            let toggle = self.secondary_toggle_field();
             match self.current_field {
                 0 => { self.name.pop(); }
                 2 => { self.secret.pop(); }
                 3 => { self.secret_confirm.pop(); }
-                5 => { self.notes.pop(); }
-                f if self.use_secondary_password && f == 7 => {
+                5 if self.has_path_fields() => { self.account.pop(); }
+                6 if self.has_path_fields() => { self.address_index.pop(); }
+                f if f == toggle - 1 => { self.notes.pop(); }
+                f if self.use_secondary_password && f == toggle + 1 => {
                     self.secondary_password.pop();
                 }
-                f if self.use_secondary_password && f == 8 => {
+                f if self.use_secondary_password && f == toggle + 2 => {
                     self.secondary_password_confirm.pop();
                 }
                 _ => {}
@@ -288,41 +616,65 @@ impl AddEntryScreen {
 
     fn field_count(&self) -> usize {
         let base = if self.is_crypto_type() {
-            7 // name, type, secret, confirm, network, notes, toggle
+            // name, type, secret, confirm, network, [account, index,] notes, toggle
+            self.secondary_toggle_field() + 1
         } else {
             8 // name, type, secret, confirm, username, url, notes, toggle
         };
-        if self.use_secondary_password {
-            base + 2 // secondary password + confirm
+        let secondary = if self.use_secondary_password {
+            2 // secondary password + confirm
         } else {
-            base
-        }
+            0
+        };
+        base + secondary + self.custom_fields.slot_count()
     }
 
     fn is_crypto_type(&self) -> bool {
         !matches!(self.secret_type, SecretType::Password)
     }
 
-    fn try_save(&self) -> AddEntryAction {
-        if self.name.is_empty() {
-            return AddEntryAction::Continue;
+    /// Build the entry, or explain in `error_message` why it cannot be saved.
+    fn try_save(&mut self) -> AddEntryAction {
+        match self.build_entry() {
+            Ok(entry) => AddEntryAction::Save(Box::new(entry)),
+            Err(message) => {
+                self.error_message = Some(message);
+                AddEntryAction::Continue
+            }
         }
+    }
 
-        if self.secret.is_empty() || self.secret != self.secret_confirm {
-            return AddEntryAction::Continue;
+    fn build_entry(&self) -> std::result::Result<Entry, String> {
+        let name = normalize_name(&self.name);
+        if name.is_empty() {
+            return Err("Name is required".into());
+        }
+
+        if self.secret.is_empty() {
+            return Err("Secret is required".into());
+        }
+        if self.secret != self.secret_confirm {
+            return Err("Secrets do not match".into());
         }
 
         if self.use_secondary_password {
-            if self.secondary_password.is_empty()
-                || self.secondary_password != self.secondary_password_confirm
-            {
-                return AddEntryAction::Continue;
+            if self.secondary_password.is_empty() {
+                return Err("Secondary password is required".into());
+            }
+            if self.secondary_password != self.secondary_password_confirm {
+                return Err("Secondary passwords do not match".into());
             }
         }
 
+        let (derivation_account, derivation_index) =
+            if self.has_path_fields() { self.path_levels()? } else { (0, 0) };
+
         // Auto-derive public address for crypto types
         let public_address = if self.is_crypto_type() {
-            match derive_address(&self.secret, &self.secret_type, &self.network) {
+            let network = resolve_network(&self.network, &self.custom_networks);
+            let path = DerivationPath::for_account(network, derivation_account, derivation_index)
+                .map_err(|e| e.to_string())?;
+            match derive_address_on_path(&self.secret, &self.secret_type, network, path) {
                 Ok(addr) => addr,
                 Err(_) => None, // Bad key format — save with no address
             }
@@ -334,17 +686,13 @@ impl AddEntryScreen {
 
         // Handle secondary password encryption
         let (has_secondary, secret_to_store, encrypted_secret, encrypted_secret_nonce,
-            entry_key_wrapped, entry_key_nonce, entry_key_salt) = if self.use_secondary_password {
+            entry_key_wrapped, entry_key_nonce, entry_key_salt, entry_key_kdf) = if self.use_secondary_password {
             let ek = entry_key::generate_entry_key();
-            let (ct, ct_nonce) = match entry_key::encrypt_secret(&ek, &self.secret) {
-                Ok(v) => v,
-                Err(_) => return AddEntryAction::Continue,
-            };
+            let (ct, ct_nonce) =
+                entry_key::encrypt_secret(&ek, &self.secret).map_err(|e| e.to_string())?;
             let (wrapped, wrap_nonce, salt) =
-                match entry_key::wrap_entry_key(&ek, &self.secondary_password) {
-                    Ok(v) => v,
-                    Err(_) => return AddEntryAction::Continue,
-                };
+                entry_key::wrap_entry_key(&ek, &self.secondary_password, self.entry_key_kdf)
+                    .map_err(|e| e.to_string())?;
             (
                 true,
                 "[encrypted]".to_string(),
@@ -353,17 +701,21 @@ impl AddEntryScreen {
                 Some(wrapped),
                 Some(wrap_nonce),
                 Some(salt),
+                Some(self.entry_key_kdf),
             )
         } else {
-            (false, self.secret.clone(), None, None, None, None, None)
+            (false, self.secret.clone(), None, None, None, None, None, None)
         };
 
         let entry = Entry {
-            name: self.name.clone(),
+            name,
             secret: secret_to_store,
             secret_type: self.secret_type.clone(),
             network: self.network.clone(),
             public_address,
+            address_is_manual: false,
+            derivation_account,
+            derivation_index,
             username: if self.username.is_empty() {
                 None
             } else {
@@ -375,17 +727,22 @@ impl AddEntryScreen {
                 Some(self.url.clone())
             },
             notes: self.notes.clone(),
+            custom_fields: self.custom_fields.to_fields(),
             created_at: now,
             updated_at: now,
             has_secondary_password: has_secondary,
             entry_key_wrapped,
             entry_key_nonce,
             entry_key_salt,
+            entry_key_kdf,
             encrypted_secret,
             encrypted_secret_nonce,
+            mask_metadata: has_secondary && self.mask_metadata,
+            clipboard_timeout_override: None,
+            archived: false,
         };
 
-        AddEntryAction::Save(entry)
+        Ok(entry)
     }
 
     pub fn render(&self, frame: &mut Frame) {
@@ -408,6 +765,11 @@ impl AddEntryScreen {
             return;
         }
 
+        if let Some(generated) = &self.generated {
+            self.render_generated(frame, form_area, generated);
+            return;
+        }
+
         let block = Block::default()
             .borders(Borders::ALL)
             .title(" Add New Entry ")
@@ -444,13 +806,21 @@ impl AddEntryScreen {
 
         // Field 2: Secret
         lines.push(Line::from(""));
-        let secret_masked = "\u{2022}".repeat(self.secret.len());
-        lines.push(self.render_field(field_idx, "Secret", &secret_masked, false));
+        let secret_masked = self.mask.hide(self.secret.chars().count());
+        let mut secret_line = self.render_field(field_idx, "Secret", &secret_masked, false);
+        if let Some((count, valid)) = self.seed_word_count() {
+            let color = if valid { Color::Green } else { Color::Yellow };
+            secret_line.spans.push(Span::styled(
+                format!("  {} word{}", count, if count == 1 { "" } else { "s" }),
+                Style::default().fg(color),
+            ));
+        }
+        lines.push(secret_line);
         field_idx += 1;
 
         // Field 3: Confirm secret
         lines.push(Line::from(""));
-        let secret_confirm_masked = "\u{2022}".repeat(self.secret_confirm.len());
+        let secret_confirm_masked = self.mask.hide(self.secret_confirm.chars().count());
         lines.push(self.render_field(field_idx, "Confirm secret", &secret_confirm_masked, false));
         field_idx += 1;
 
@@ -459,6 +829,25 @@ impl AddEntryScreen {
             lines.push(Line::from(""));
             lines.push(self.render_field(field_idx, "Network", &self.network, false));
             field_idx += 1;
+
+            if self.has_path_fields() {
+                // Fields 5 and 6: Account and address index
+                lines.push(Line::from(""));
+                lines.push(self.render_field(field_idx, "Account (optional)", &self.account, false));
+                field_idx += 1;
+
+                lines.push(Line::from(""));
+                let mut index_line =
+                    self.render_field(field_idx, "Address index (optional)", &self.address_index, false);
+                if let Some(path) = self.path_preview() {
+                    index_line.spans.push(Span::styled(
+                        format!("  {}", path),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                lines.push(index_line);
+                field_idx += 1;
+            }
         } else {
             // Field 4: Username
             lines.push(Line::from(""));
@@ -478,13 +867,17 @@ impl AddEntryScreen {
 
         // Secondary password toggle
         lines.push(Line::from(""));
-        let toggle_value = if self.use_secondary_password { "Yes" } else { "No" };
+        let toggle_value = match (self.use_secondary_password, self.mask_metadata) {
+            (false, _) => "No",
+            (true, false) => "Yes",
+            (true, true) => "Yes, and hide name in the list",
+        };
         lines.push(self.render_field(field_idx, "Secondary password", toggle_value, false));
         field_idx += 1;
 
         // Secondary password fields (only when toggled on)
-        let sp_masked = "\u{2022}".repeat(self.secondary_password.len());
-        let sp_confirm_masked = "\u{2022}".repeat(self.secondary_password_confirm.len());
+        let sp_masked = self.mask.hide(self.secondary_password.chars().count());
+        let sp_confirm_masked = self.mask.hide(self.secondary_password_confirm.chars().count());
         if self.use_secondary_password {
             lines.push(Line::from(""));
             lines.push(self.render_field(field_idx, "Secondary pwd", &sp_masked, false));
@@ -494,23 +887,49 @@ impl AddEntryScreen {
             lines.push(self.render_field(field_idx, "Confirm secondary", &sp_confirm_masked, false));
         }
 
+        // Custom fields, two slots per row
+        for line in self.custom_fields.render_lines(self.custom_slot(), &self.mask) {
+            lines.push(Line::from(""));
+            lines.push(line);
+        }
+
         lines.push(Line::from(""));
-        lines.push(Line::from(""));
+        if let Some(error) = self.error_message.as_ref().or(self.generate_error.as_ref()) {
+            lines.push(Line::from(Span::styled(error.clone(), Style::default().fg(Color::Red))));
+        } else if let Some(network) = self.network_suggestion() {
+            lines.push(Line::from(Span::styled(
+                format!("This looks like a {} key \u{2014} Ctrl+N to switch network", network),
+                Style::default().fg(Color::Yellow),
+            )));
+        } else if let Some(warning) = self.key_warning() {
+            lines.push(Line::from(Span::styled(warning, Style::default().fg(Color::Yellow))));
+        } else {
+            lines.push(Line::from(""));
+        }
 
         let help_text = if self.current_field == 1 {
             "\u{2191}\u{2193}: Scroll \u{2502} Enter: Select \u{2502} Tab: Next \u{2502} Esc: Cancel"
         } else if self.is_crypto_type() && self.current_field == 4 {
             "\u{2191}\u{2193}: Scroll \u{2502} Enter: Select \u{2502} Tab: Next \u{2502} Esc: Cancel"
         } else if self.current_field == self.secondary_toggle_field() {
-            "\u{2191}\u{2193}: Scroll \u{2502} Enter: Toggle \u{2502} Tab: Next \u{2502} Ctrl+S: Save \u{2502} Esc: Cancel"
+            "\u{2191}\u{2193}: Scroll \u{2502} Enter: Toggle \u{2502} Tab: Next \u{2502} Ctrl+S: Save \u{2502} F1: Keys \u{2502} Esc: Cancel"
+        } else if self.custom_slot().is_some() {
+            "\u{2191}\u{2193}: Scroll \u{2502} Tab: Next \u{2502} Ctrl+F: Add field \u{2502} Ctrl+T: Sensitive \u{2502} Ctrl+D: Remove \u{2502} Ctrl+S: Save \u{2502} F1: Keys \u{2502} Esc: Cancel"
         } else {
-            "\u{2191}\u{2193}: Scroll \u{2502} Tab: Next \u{2502} Shift+Tab: Previous \u{2502} Ctrl+S: Save \u{2502} Esc: Cancel"
+            "\u{2191}\u{2193}: Scroll \u{2502} Tab: Next \u{2502} Shift+Tab: Previous \u{2502} Ctrl+F: Add field \u{2502} Ctrl+S: Save \u{2502} F1: Keys \u{2502} Esc: Cancel"
         };
 
-        lines.push(Line::from(vec![Span::styled(
-            help_text,
-            Style::default().fg(Color::DarkGray),
-        )]));
+        if self.discard_armed {
+            lines.push(Line::from(Span::styled(
+                "Press Esc again to discard this entry, or any other key to keep editing",
+                Style::default().fg(Color::Yellow),
+            )));
+        } else {
+            lines.push(Line::from(vec![Span::styled(
+                help_text,
+                Style::default().fg(Color::DarkGray),
+            )]));
+        }
 
         // Skip lines based on scroll offset
         let visible_lines: Vec<Line> = lines
@@ -523,6 +942,14 @@ impl AddEntryScreen {
         frame.render_widget(paragraph, inner);
     }
 
+    /// The path the account and address index inputs select on the chosen
+    /// network, when they are valid for it.
+    fn path_preview(&self) -> Option<DerivationPath> {
+        let (account, index) = self.path_levels().ok()?;
+        let network = resolve_network(&self.network, &self.custom_networks);
+        DerivationPath::for_account(network, account, index).ok().flatten()
+    }
+
     fn render_field<'a>(
         &self,
         idx: usize,
@@ -554,9 +981,52 @@ impl AddEntryScreen {
         ])
     }
 
+    /// A generated secret in full, with the backup confirmation.
+    fn render_generated(&self, frame: &mut Frame, area: Rect, secret: &str) {
+        let what = if self.secret_type == SecretType::SeedPhrase {
+            format!("{}-word seed phrase", keygen::MNEMONIC_WORDS)
+        } else {
+            format!("{} private key", self.network)
+        };
+        let lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("Your new {}:", what),
+                Style::default().fg(Color::White),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                secret.to_string(),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Write it down and keep it offline. Anyone who has it controls the funds,",
+                Style::default().fg(Color::White),
+            )),
+            Line::from(Span::styled(
+                "and losing it loses them. It is not shown again on this screen.",
+                Style::default().fg(Color::White),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Y: I have written this down \u{2502} Esc: Discard",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Back Up New Secret ")
+            .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            .border_style(Style::default().fg(Color::Cyan));
+        frame.render_widget(
+            Paragraph::new(lines).block(block).wrap(Wrap { trim: false }),
+            area,
+        );
+    }
+
     fn render_type_select(&self, frame: &mut Frame, area: Rect) {
-        let types = ["Private Key", "Seed Phrase", "Password"];
-        let items: Vec<ListItem> = types
+        let items: Vec<ListItem> = SECRET_TYPES
             .iter()
             .enumerate()
             .map(|(i, t)| {
@@ -579,7 +1049,7 @@ impl AddEntryScreen {
         let list = List::new(items).block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Select Secret Type (\u{2191}/\u{2193} to navigate, Enter to select) ")
+                .title(" Select Secret Type (\u{2191}/\u{2193} to navigate, Enter to select, Esc to go back) ")
                 .border_style(Style::default().fg(Color::Cyan)),
         );
 
@@ -587,8 +1057,8 @@ impl AddEntryScreen {
     }
 
     fn render_network_select(&self, frame: &mut Frame, area: Rect) {
-        let networks = ["Ethereum", "Bitcoin", "Solana", "Other"];
-        let items: Vec<ListItem> = networks
+        let items: Vec<ListItem> = self
+            .networks
             .iter()
             .enumerate()
             .map(|(i, n)| {
@@ -611,7 +1081,7 @@ impl AddEntryScreen {
         let list = List::new(items).block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Select Network (\u{2191}/\u{2193} to navigate, Enter to select) ")
+                .title(" Select Network (\u{2191}/\u{2193} to navigate, Enter to select, Esc to go back) ")
                 .border_style(Style::default().fg(Color::Cyan)),
         );
 
@@ -619,6 +1089,13 @@ impl AddEntryScreen {
     }
 }
 
+/// Type into a numeric field: digits only, no more than a u32 holds.
+fn push_digit(field: &mut String, c: char) {
+    if c.is_ascii_digit() && field.len() < 10 {
+        field.push(c);
+    }
+}
+
 fn centered_rect(percent: u16, r: Rect) -> Rect {
     let width = r.width * percent / 100;
     let x = r.x + (r.width - width) / 2;
@@ -632,6 +1109,141 @@ fn centered_rect(percent: u16, r: Rect) -> Rect {
 
 pub enum AddEntryAction {
     Continue,
-    Save(Entry),
+    Save(Box<Entry>),
     Cancel,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(screen: &mut AddEntryScreen, key: KeyCode) -> AddEntryAction {
+        screen.handle_key(key, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn esc_closes_selector_before_cancelling() {
+        let mut screen = AddEntryScreen::new(&Config::default());
+        screen.current_field = 1;
+        press(&mut screen, KeyCode::Enter);
+        assert!(screen.show_type_select);
+
+        assert!(matches!(press(&mut screen, KeyCode::Esc), AddEntryAction::Continue));
+        assert!(!screen.show_type_select);
+        // Nothing typed, so the next Esc leaves at once
+        assert!(matches!(press(&mut screen, KeyCode::Esc), AddEntryAction::Cancel));
+    }
+
+    #[test]
+    fn esc_twice_discards_typed_input() {
+        let mut screen = AddEntryScreen::new(&Config::default());
+        press(&mut screen, KeyCode::Char('a'));
+
+        assert!(matches!(press(&mut screen, KeyCode::Esc), AddEntryAction::Continue));
+        // Any other key keeps editing and needs two presses again
+        press(&mut screen, KeyCode::Char('b'));
+        assert!(matches!(press(&mut screen, KeyCode::Esc), AddEntryAction::Continue));
+        assert_eq!(screen.name, "ab");
+        assert!(matches!(press(&mut screen, KeyCode::Esc), AddEntryAction::Cancel));
+    }
+
+    #[test]
+    fn save_explains_what_is_missing() {
+        let mut screen = AddEntryScreen::new(&Config::default());
+        let save = |screen: &mut AddEntryScreen| {
+            screen.handle_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        };
+
+        assert!(matches!(save(&mut screen), AddEntryAction::Continue));
+        assert_eq!(screen.error_message.as_deref(), Some("Name is required"));
+
+        screen.name = "cold".into();
+        screen.secret = "secret".into();
+        screen.secret_confirm = "secrte".into();
+        assert!(matches!(save(&mut screen), AddEntryAction::Continue));
+        assert_eq!(screen.error_message.as_deref(), Some("Secrets do not match"));
+
+        screen.secret_confirm = "secret".into();
+        screen.use_secondary_password = true;
+        screen.secondary_password = "one".into();
+        screen.secondary_password_confirm = "two".into();
+        assert!(matches!(save(&mut screen), AddEntryAction::Continue));
+        assert_eq!(screen.error_message.as_deref(), Some("Secondary passwords do not match"));
+
+        // The message stays until the next key
+        press(&mut screen, KeyCode::Backspace);
+        assert!(screen.error_message.is_none());
+
+        screen.use_secondary_password = false;
+        assert!(matches!(save(&mut screen), AddEntryAction::Save(_)));
+    }
+
+    #[cfg(all(feature = "derive-eth", feature = "derive-cosmos"))]
+    #[test]
+    fn hex_key_on_a_hex_key_network_is_not_called_ethereum() {
+        let mut screen = AddEntryScreen::new(&Config::default());
+        screen.secret_type = SecretType::PrivateKey;
+        screen.secret = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".into();
+        for network in ["Cosmos", "Osmosis", "Ripple", "xrp", "Ethereum"] {
+            screen.network = network.into();
+            assert_eq!(screen.network_suggestion(), None, "{}", network);
+        }
+        screen.network = "Solana".into();
+        assert_eq!(screen.network_suggestion(), Some("Ethereum"));
+    }
+
+    fn seed_screen(network: &str) -> AddEntryScreen {
+        let mut screen = AddEntryScreen::new(&Config::default());
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        screen.name = "hd".into();
+        screen.secret_type = SecretType::SeedPhrase;
+        screen.secret = phrase.into();
+        screen.secret_confirm = phrase.into();
+        screen.network = network.into();
+        screen
+    }
+
+    #[cfg(feature = "derive-eth")]
+    #[test]
+    fn seed_entry_keeps_its_account_and_index() {
+        let mut screen = seed_screen("Ethereum");
+        screen.current_field = 5;
+        for c in "1x".chars() {
+            press(&mut screen, KeyCode::Char(c));
+        }
+        press(&mut screen, KeyCode::Tab);
+        press(&mut screen, KeyCode::Char('2'));
+        assert_eq!((screen.account.as_str(), screen.address_index.as_str()), ("1", "2"));
+        press(&mut screen, KeyCode::Tab);
+        press(&mut screen, KeyCode::Char('n'));
+        assert_eq!(screen.notes, "n");
+
+        let AddEntryAction::Save(entry) = screen.handle_key(KeyCode::Char('s'), KeyModifiers::CONTROL) else {
+            panic!("entry not saved: {:?}", screen.error_message);
+        };
+        assert_eq!((entry.derivation_account, entry.derivation_index), (1, 2));
+        let path = DerivationPath::for_account("Ethereum", 1, 2).unwrap();
+        assert_eq!(
+            entry.public_address,
+            derive_address_on_path(&entry.secret, &entry.secret_type, "Ethereum", path).unwrap()
+        );
+        let default = derive_address_on_path(&entry.secret, &entry.secret_type, "Ethereum", None);
+        assert_ne!(entry.public_address, default.unwrap());
+    }
+
+    #[cfg(feature = "derive-sol")]
+    #[test]
+    fn index_on_a_path_without_one_is_refused() {
+        let mut screen = seed_screen("Solana");
+        screen.address_index = "3".into();
+        assert!(matches!(
+            screen.handle_key(KeyCode::Char('s'), KeyModifiers::CONTROL),
+            AddEntryAction::Continue
+        ));
+        assert!(screen.error_message.as_deref().unwrap().contains("no address index"));
+
+        screen.address_index = "9999999999".into();
+        screen.handle_key(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert_eq!(screen.error_message.as_deref(), Some("Address index must be at most 2147483647"));
+    }
+}
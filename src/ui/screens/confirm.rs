@@ -3,7 +3,7 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
 
@@ -43,14 +43,27 @@ impl ConfirmScreen {
     pub fn render(&self, frame: &mut Frame) {
         let area = frame.area();
 
+        // Grow the box for messages that wrap past three lines
+        let width = (area.width * 60 / 100).max(1) as usize;
+        let message_lines = self
+            .message
+            .lines()
+            .map(|line| line.chars().count().max(1).div_ceil(width))
+            .sum::<usize>()
+            .max(3) as u16;
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(1), Constraint::Length(8), Constraint::Min(1)])
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(message_lines + 5),
+                Constraint::Min(1),
+            ])
             .split(area);
 
         let inner_chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(3), Constraint::Length(3)])
+            .constraints([Constraint::Length(message_lines), Constraint::Length(3)])
             .split(centered_rect(60, chunks[1]));
 
         let block = Block::default()
@@ -60,7 +73,8 @@ impl ConfirmScreen {
             .border_style(Style::default().fg(Color::Yellow));
 
         let message_para = Paragraph::new(self.message.as_str())
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(Color::White))
+            .wrap(Wrap { trim: true });
 
         frame.render_widget(block.clone(), chunks[1]);
         frame.render_widget(message_para, inner_chunks[0]);
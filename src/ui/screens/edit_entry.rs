@@ -8,33 +8,135 @@ use ratatui::{
     Frame,
 };
 
-use crate::vault::model::Entry;
+use crate::ui::screens::add_entry::paste_chars;
+use crate::ui::theme::Mask;
+use crate::ui::widgets::custom_fields::CustomFieldsEditor;
+use crate::vault::model::{normalize_name, Entry, FieldChange};
 
 pub struct EditEntryScreen {
     pub original_name: String,
+    /// Entry as it was when the screen opened, for the change summary
+    original: Box<Entry>,
     entry: Entry,
     current_field: usize,
+    custom_fields: CustomFieldsEditor,
+    /// Digits of the clipboard timeout override; empty uses the config's
+    clipboard_timeout: String,
+    mask: Mask,
+    /// Fields were typed into since the screen opened
+    modified: bool,
+    /// Changes awaiting confirmation before saving
+    review: Option<Vec<FieldChange>>,
+    /// Esc was pressed over unsaved changes; a second Esc discards them
+    discard_armed: bool,
 }
 
 impl EditEntryScreen {
-    pub fn new(entry: Entry) -> Self {
+    pub fn new(entry: Entry, mask: Mask) -> Self {
         let original_name = entry.name.clone();
+        let custom_fields = CustomFieldsEditor::new(entry.custom_fields.clone());
+        let clipboard_timeout = entry
+            .clipboard_timeout_override
+            .map(|t| t.to_string())
+            .unwrap_or_default();
         Self {
             original_name,
+            original: Box::new(entry.clone()),
             entry,
             current_field: 0,
+            custom_fields,
+            clipboard_timeout,
+            mask,
+            modified: false,
+            review: None,
+            discard_armed: false,
         }
     }
 
+    /// Whether leaving now would discard typed changes.
+    pub fn has_pending_input(&self) -> bool {
+        self.modified
+    }
+
+    /// Insert pasted text into the focused field.
+    pub fn handle_paste(&mut self, text: &str) {
+        if self.review.is_some() {
+            return;
+        }
+        for c in paste_chars(text) {
+            self.insert_char(c);
+        }
+        self.modified = true;
+    }
+
+    /// Keys listed in the F1 help overlay.
+    pub fn help_keys(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("Tab / ↓", "Next field"),
+            ("Shift+Tab / ↑", "Previous field"),
+            ("Enter", "Next field, or review changes on the last field"),
+            ("Ctrl+S", "Review changes and save"),
+            ("Ctrl+F", "Add a custom field"),
+            ("Ctrl+T", "Mark custom field sensitive"),
+            ("Ctrl+D", "Remove custom field"),
+            ("Esc", "Cancel; press twice to discard changes"),
+        ]
+    }
+
     pub fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> EditEntryAction {
+        if self.review.is_some() {
+            return match key {
+                KeyCode::Enter | KeyCode::Char('y') => {
+                    if self.entry.public_address != self.original.public_address {
+                        self.entry.address_is_manual = self.entry.public_address.is_some();
+                    }
+                    self.entry.updated_at = Utc::now();
+                    EditEntryAction::Save(Box::new(self.entry.clone()))
+                }
+                KeyCode::Esc | KeyCode::Char('n') => {
+                    self.review = None;
+                    EditEntryAction::Continue
+                }
+                _ => EditEntryAction::Continue,
+            };
+        }
+
         if key == KeyCode::Esc {
-            return EditEntryAction::Cancel;
+            if self.discard_armed || !self.modified {
+                return EditEntryAction::Cancel;
+            }
+            self.discard_armed = true;
+            return EditEntryAction::Continue;
         }
+        self.discard_armed = false;
 
         if modifiers.contains(KeyModifiers::CONTROL) && key == KeyCode::Char('s') {
             return self.try_save();
         }
 
+        if modifiers.contains(KeyModifiers::CONTROL) && key == KeyCode::Char('f') {
+            self.current_field = self.custom_start() + self.custom_fields.add_row();
+            self.modified = true;
+            return EditEntryAction::Continue;
+        }
+
+        if modifiers.contains(KeyModifiers::CONTROL) && key == KeyCode::Char('t') {
+            if let Some(slot) = self.custom_slot() {
+                self.custom_fields.toggle_sensitive(slot);
+                self.modified = true;
+            }
+            return EditEntryAction::Continue;
+        }
+
+        if modifiers.contains(KeyModifiers::CONTROL) && key == KeyCode::Char('d') {
+            if let Some(slot) = self.custom_slot() {
+                self.custom_fields.remove_row(slot);
+                self.current_field = self.current_field.min(self.field_count() - 1);
+                self.modified = true;
+            }
+            return EditEntryAction::Continue;
+        }
+
         match key {
             KeyCode::Tab => {
                 self.current_field = (self.current_field + 1) % self.field_count();
@@ -68,17 +170,39 @@ impl EditEntryScreen {
             }
             KeyCode::Char(c) if !modifiers.contains(KeyModifiers::CONTROL) => {
                 self.insert_char(c);
+                self.modified = true;
                 EditEntryAction::Continue
             }
             KeyCode::Backspace => {
                 self.delete_char();
+                self.modified = true;
                 EditEntryAction::Continue
             }
             _ => EditEntryAction::Continue,
         }
     }
 
+    /// Field index of the first custom field slot (after all fixed fields).
+    fn custom_start(&self) -> usize {
+        self.field_count() - self.custom_fields.slot_count()
+    }
+
+    /// Slot within the custom fields list, if one is focused.
+    fn custom_slot(&self) -> Option<usize> {
+        self.current_field.checked_sub(self.custom_start())
+    }
+
     fn insert_char(&mut self, c: char) {
+        if let Some(slot) = self.custom_slot() {
+            self.custom_fields.insert_char(slot, c);
+            return;
+        }
+        if self.current_field == self.timeout_field() {
+            if c.is_ascii_digit() {
+                self.clipboard_timeout.push(c);
+            }
+            return;
+        }
         match self.current_field {
             0 => self.entry.name.push(c),
             1 => {
@@ -117,6 +241,14 @@ impl EditEntryScreen {
     }
 
     fn delete_char(&mut self) {
+        if let Some(slot) = self.custom_slot() {
+            self.custom_fields.delete_char(slot);
+            return;
+        }
+        if self.current_field == self.timeout_field() {
+            self.clipboard_timeout.pop();
+            return;
+        }
         match self.current_field {
             0 => {
                 self.entry.name.pop();
@@ -150,12 +282,13 @@ impl EditEntryScreen {
         }
     }
 
+    /// Field index of the clipboard timeout, the last fixed field.
+    fn timeout_field(&self) -> usize {
+        if self.is_password_type() { 4 } else { 3 }
+    }
+
     fn field_count(&self) -> usize {
-        if self.is_password_type() {
-            4
-        } else {
-            3
-        }
+        self.timeout_field() + 1 + self.custom_fields.slot_count()
     }
 
     fn is_password_type(&self) -> bool {
@@ -166,20 +299,77 @@ impl EditEntryScreen {
     }
 
     fn try_save(&mut self) -> EditEntryAction {
+        self.entry.name = normalize_name(&self.entry.name);
         if self.entry.name.is_empty() {
             return EditEntryAction::Continue;
         }
 
-        self.entry.updated_at = Utc::now();
-        EditEntryAction::Save(self.entry.clone())
+        // Zero would clear the clipboard before it could be pasted
+        self.entry.clipboard_timeout_override = match self.clipboard_timeout.as_str() {
+            "" => None,
+            digits => match digits.parse::<u64>() {
+                Ok(secs) if secs > 0 => Some(secs),
+                _ => return EditEntryAction::Continue,
+            },
+        };
+        self.entry.custom_fields = self.custom_fields.to_fields();
+        let changes = self.original.diff(&self.entry);
+        if changes.is_empty() {
+            return EditEntryAction::Cancel;
+        }
+        self.review = Some(changes);
+        EditEntryAction::Continue
+    }
+
+    /// Summary of pending changes shown before saving.
+    fn render_review(&self, frame: &mut Frame, changes: &[FieldChange]) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(changes.len() as u16 + 7),
+                Constraint::Min(1),
+            ])
+            .split(frame.area());
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Review Changes ")
+            .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            .border_style(Style::default().fg(Color::Cyan));
+
+        let mut lines = vec![Line::from("")];
+        for change in changes {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", change),
+                Style::default().fg(Color::White),
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Enter: Save │ Esc: Keep editing",
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        let paragraph = Paragraph::new(lines).block(block);
+        frame.render_widget(paragraph, centered_rect(70, chunks[1]));
     }
 
     pub fn render(&self, frame: &mut Frame) {
+        if let Some(changes) = &self.review {
+            self.render_review(frame, changes);
+            return;
+        }
+
         let area = frame.area();
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(1), Constraint::Length(18), Constraint::Min(1)])
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(20 + 2 * self.custom_fields.slot_count() as u16),
+                Constraint::Min(1),
+            ])
             .split(area);
 
         let form_area = centered_rect(70, chunks[1]);
@@ -247,6 +437,19 @@ impl EditEntryScreen {
 
         lines.push(Line::from(""));
         lines.push(self.render_field(field_idx, "Notes (optional)", &self.entry.notes));
+        field_idx += 1;
+
+        lines.push(Line::from(""));
+        lines.push(self.render_field(
+            field_idx,
+            "Clipboard timeout (seconds, empty = default)",
+            &self.clipboard_timeout,
+        ));
+
+        for line in self.custom_fields.render_lines(self.custom_slot(), &self.mask) {
+            lines.push(Line::from(""));
+            lines.push(line);
+        }
 
         lines.push(Line::from(""));
         lines.push(Line::from(""));
@@ -262,10 +465,21 @@ impl EditEntryScreen {
         ]));
 
         lines.push(Line::from(""));
-        lines.push(Line::from(vec![Span::styled(
-            "Tab: Next field │ Shift+Tab: Previous │ Enter: Save │ Esc: Cancel",
-            Style::default().fg(Color::DarkGray),
-        )]));
+        if self.discard_armed {
+            lines.push(Line::from(Span::styled(
+                "Press Esc again to discard your changes, or any other key to keep editing",
+                Style::default().fg(Color::Yellow),
+            )));
+        } else {
+            lines.push(Line::from(vec![Span::styled(
+                if self.custom_slot().is_some() {
+                    "Tab: Next field │ Ctrl+F: Add field │ Ctrl+T: Sensitive │ Ctrl+D: Remove │ Enter: Save │ F1: Keys │ Esc: Cancel"
+                } else {
+                    "Tab: Next field │ Shift+Tab: Previous │ Ctrl+F: Add field │ Enter: Save │ F1: Keys │ Esc: Cancel"
+                },
+                Style::default().fg(Color::DarkGray),
+            )]));
+        }
 
         let paragraph = Paragraph::new(lines);
         frame.render_widget(paragraph, inner);
@@ -308,6 +522,6 @@ fn centered_rect(percent: u16, r: Rect) -> Rect {
 
 pub enum EditEntryAction {
     Continue,
-    Save(Entry),
+    Save(Box<Entry>),
     Cancel,
 }
@@ -0,0 +1,165 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::ui::theme::network_label;
+use crate::vault::model::{names_match, EntryMeta, VaultData};
+
+pub enum ImportPreviewAction {
+    Continue,
+    Import,
+    Cancel,
+}
+
+/// Lists what a decrypted backup would add before anything is merged.
+pub struct ImportPreviewScreen {
+    /// File the backup was read from, shown in the title
+    source: String,
+    /// The decrypted backup, merged by the caller on `Import`
+    pub backup: VaultData,
+    entries: Vec<EntryMeta>,
+    /// Per entry: its name is already taken, so importing skips it
+    conflicts: Vec<bool>,
+    selected: usize,
+    network_glyphs: bool,
+}
+
+impl ImportPreviewScreen {
+    pub fn new(source: &str, backup: VaultData, vault: &VaultData, network_glyphs: bool) -> Self {
+        let entries = backup.metadata();
+        // A name repeated within the backup is skipped after its first copy.
+        // Compared on the stored names, since masked entries are listed hidden.
+        let conflicts = backup
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                vault.has_entry(&e.name)
+                    || backup.entries[..i].iter().any(|p| names_match(&p.name, &e.name))
+            })
+            .collect();
+        Self {
+            source: source.to_string(),
+            backup,
+            entries,
+            conflicts,
+            selected: 0,
+            network_glyphs,
+        }
+    }
+
+    /// Entries that importing would add.
+    pub fn new_count(&self) -> usize {
+        self.conflicts.iter().filter(|c| !**c).count()
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode, _modifiers: KeyModifiers) -> ImportPreviewAction {
+        let last = self.entries.len().saturating_sub(1);
+        match key {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(last),
+            KeyCode::PageUp => self.selected = self.selected.saturating_sub(10),
+            KeyCode::PageDown => self.selected = (self.selected + 10).min(last),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = last,
+            KeyCode::Enter | KeyCode::Char('y') if self.new_count() > 0 => {
+                return ImportPreviewAction::Import;
+            }
+            KeyCode::Enter | KeyCode::Esc | KeyCode::Char('n' | 'q') => {
+                return ImportPreviewAction::Cancel;
+            }
+            _ => {}
+        }
+        ImportPreviewAction::Continue
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(3),
+                Constraint::Length(1),
+            ])
+            .split(frame.area());
+
+        let skipped = self.entries.len() - self.new_count();
+        let mut summary = vec![Span::styled(
+            format!("  {} new entries", self.new_count()),
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+        )];
+        if skipped > 0 {
+            summary.push(Span::styled(
+                format!(", {} skipped because the name is already taken", skipped),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        let header = Paragraph::new(Line::from(summary)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Import from {} ", self.source))
+                .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+        frame.render_widget(header, chunks[0]);
+
+        let header_cells = ["#", "Name", "Type", "Network", "Import"]
+            .into_iter()
+            .map(|h| Cell::from(h).style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+        let rows = self.entries.iter().zip(&self.conflicts).enumerate().map(|(idx, (entry, conflict))| {
+            let (status, style) = if *conflict {
+                ("skip: name exists", Style::default().fg(Color::DarkGray))
+            } else {
+                ("new", Style::default())
+            };
+            Row::new(vec![
+                Cell::from((idx + 1).to_string()),
+                Cell::from(entry.name.clone()),
+                Cell::from(entry.secret_type.to_string()),
+                Cell::from(network_label(&entry.network, self.network_glyphs)),
+                Cell::from(status),
+            ])
+            .style(style)
+        });
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Fill(3),
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Length(18),
+        ];
+        let table = Table::new(rows, widths)
+            .header(Row::new(header_cells).height(1))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Entries in Backup ")
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .column_spacing(1);
+        // Selecting the row scrolls the table to keep it in view
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, chunks[1], &mut state);
+
+        let hint = if self.new_count() > 0 {
+            "  ↑/↓ Scroll   Enter Import   Esc Cancel"
+        } else {
+            "  Nothing to import.   Esc Back"
+        };
+        frame.render_widget(
+            Paragraph::new(hint).style(Style::default().fg(Color::DarkGray)),
+            chunks[2],
+        );
+    }
+}
@@ -24,6 +24,12 @@ impl InputScreen {
         }
     }
 
+    /// Start with `value` already typed, e.g. a configured default path.
+    pub fn with_value(mut self, value: &str) -> Self {
+        self.value = value.to_string();
+        self
+    }
+
     pub fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> Option<InputResult> {
         match key {
             KeyCode::Char(c) if !modifiers.contains(KeyModifiers::CONTROL) => {
@@ -1,26 +1,42 @@
 use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::{
     layout::{Constraint, Direction, Layout},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::Paragraph,
+    widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use std::time::Instant;
 use zeroize::Zeroizing;
 
+use crate::ui::theme::spinner_frame;
 use crate::ui::widgets::password_field::{PasswordAction, PasswordField};
 
 pub struct LoginScreen {
     password_field: PasswordField,
+    /// Set while the key is being derived on a background thread.
+    unlocking_since: Option<Instant>,
+    /// A recovery question is set up, so F1 is worth offering
+    recovery_available: bool,
 }
 
 impl LoginScreen {
-    pub fn new() -> Self {
+    pub fn new(recovery_available: bool) -> Self {
         Self {
             password_field: PasswordField::new("Enter your master password to unlock the vault:"),
+            unlocking_since: None,
+            recovery_available,
         }
     }
 
+    pub fn start_unlocking(&mut self) {
+        self.unlocking_since = Some(Instant::now());
+    }
+
+    pub fn is_unlocking(&self) -> bool {
+        self.unlocking_since.is_some()
+    }
+
     pub fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> Option<Zeroizing<String>> {
         match self.password_field.handle_key(key, modifiers) {
             PasswordAction::Submit(password) => Some(Zeroizing::new(password)),
@@ -36,13 +52,58 @@ impl LoginScreen {
             .constraints([Constraint::Min(1), Constraint::Length(1)])
             .split(area);
 
-        self.password_field.render(frame, chunks[0]);
+        if let Some(started) = self.unlocking_since {
+            self.render_unlocking(frame, chunks[0], started);
+        } else {
+            self.password_field.render(frame, chunks[0]);
+        }
 
-        let hint = Paragraph::new(Line::from(vec![
-            Span::styled("F1", Style::default().fg(Color::Cyan)),
-            Span::styled(" Forgot password?", Style::default().fg(Color::DarkGray)),
-        ]))
-        .style(Style::default().bg(Color::Black));
+        let hint = if self.is_unlocking() {
+            Line::from(vec![
+                Span::styled("Esc", Style::default().fg(Color::Cyan)),
+                Span::styled(" Cancel", Style::default().fg(Color::DarkGray)),
+            ])
+        } else if self.recovery_available {
+            Line::from(vec![
+                Span::styled("F1", Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    " Recover using your security question",
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])
+        } else {
+            Line::from("")
+        };
+        let hint = Paragraph::new(hint).style(Style::default().bg(Color::Black));
         frame.render_widget(hint, chunks[1]);
     }
+
+    fn render_unlocking(&self, frame: &mut Frame, area: ratatui::layout::Rect, started: Instant) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(7), Constraint::Min(1)])
+            .split(area);
+
+        let elapsed = started.elapsed();
+        let text = vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(spinner_frame(elapsed), Style::default().fg(Color::Cyan)),
+                Span::styled(" Unlocking vault...", Style::default().fg(Color::White)),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("{:.1}s elapsed", elapsed.as_secs_f32()),
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Enter Master Password ")
+            .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            .border_style(Style::default().fg(Color::Cyan));
+
+        frame.render_widget(Paragraph::new(text).block(block), chunks[1]);
+    }
 }
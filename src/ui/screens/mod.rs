@@ -1,6 +1,7 @@
 pub mod add_entry;
 pub mod confirm;
 pub mod edit_entry;
+pub mod import_preview;
 pub mod input;
 pub mod login;
 pub mod recovery;
@@ -6,10 +6,12 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
+use chrono::{DateTime, Utc};
+use std::time::{Duration, Instant};
 use zeroize::{Zeroize, Zeroizing};
 
 use crate::config::RecoveryConfig;
-use crate::config::model::RECOVERY_QUESTIONS;
+use crate::config::model::{PasswordPolicy, RECOVERY_QUESTIONS, RECOVERY_RETRY_DELAY_SECS};
 use crate::crypto::recovery;
 
 #[derive(Clone, Copy, PartialEq)]
@@ -28,6 +30,11 @@ pub struct RecoveryScreen {
     error_message: Option<String>,
     recovery_config: RecoveryConfig,
     master_key: Option<Zeroizing<[u8; 32]>>,
+    /// Recovery is refused until this time (from `Config`)
+    locked_until: Option<DateTime<Utc>>,
+    /// Next answer is ignored until this instant after a wrong one
+    retry_at: Option<Instant>,
+    policy: PasswordPolicy,
 }
 
 impl Drop for RecoveryScreen {
@@ -41,6 +48,8 @@ impl Drop for RecoveryScreen {
 pub enum RecoveryAction {
     Continue,
     Cancel,
+    /// A wrong answer was entered; the app records it in the config
+    WrongAnswer,
     /// Recovery complete: (master_key, new_password)
     Complete {
         master_key: Zeroizing<[u8; 32]>,
@@ -49,7 +58,11 @@ pub enum RecoveryAction {
 }
 
 impl RecoveryScreen {
-    pub fn new(recovery_config: RecoveryConfig) -> Self {
+    pub fn new(
+        recovery_config: RecoveryConfig,
+        locked_until: Option<DateTime<Utc>>,
+        policy: PasswordPolicy,
+    ) -> Self {
         let question = RECOVERY_QUESTIONS
             .get(recovery_config.question_index as usize)
             .unwrap_or(&"Unknown question")
@@ -64,12 +77,29 @@ impl RecoveryScreen {
             error_message: None,
             recovery_config,
             master_key: None,
+            locked_until,
+            retry_at: None,
+            policy,
         }
     }
 
+    /// Update the lockout after the app has recorded a wrong answer.
+    pub fn set_locked_until(&mut self, locked_until: Option<DateTime<Utc>>) {
+        self.locked_until = locked_until;
+    }
+
+    fn cooldown_secs(&self) -> Option<u64> {
+        let remaining = (self.locked_until? - Utc::now()).num_milliseconds();
+        (remaining > 0).then(|| (remaining as u64).div_ceil(1000))
+    }
+
+    fn retry_pending(&self) -> bool {
+        self.retry_at.is_some_and(|at| Instant::now() < at)
+    }
+
     pub fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> RecoveryAction {
         if key == KeyCode::Esc {
-            return RecoveryAction::Cancel;
+            return self.step_back();
         }
 
         self.error_message = None;
@@ -88,6 +118,30 @@ impl RecoveryScreen {
         }
     }
 
+    /// Esc returns to the previous step, clearing what was typed there, and
+    /// cancels only from the answer. Going back past the new password forgets
+    /// the recovered key, so the answer is asked again.
+    fn step_back(&mut self) -> RecoveryAction {
+        self.error_message = None;
+        match self.step {
+            Step::Answer => return RecoveryAction::Cancel,
+            Step::NewPassword => {
+                self.new_password.zeroize();
+                self.new_password = String::new();
+                self.answer.zeroize();
+                self.answer = String::new();
+                self.master_key = None;
+                self.step = Step::Answer;
+            }
+            Step::ConfirmPassword => {
+                self.confirm_password.zeroize();
+                self.confirm_password = String::new();
+                self.step = Step::NewPassword;
+            }
+        }
+        RecoveryAction::Continue
+    }
+
     fn current_buffer_mut(&mut self) -> &mut String {
         match self.step {
             Step::Answer => &mut self.answer,
@@ -111,6 +165,10 @@ impl RecoveryScreen {
 
         match self.step {
             Step::Answer => {
+                if self.cooldown_secs().is_some() || self.retry_pending() {
+                    return RecoveryAction::Continue;
+                }
+
                 let normalized = recovery::normalize_answer(&self.answer);
 
                 // Verify answer
@@ -118,13 +176,17 @@ impl RecoveryScreen {
                     &normalized,
                     &self.recovery_config.answer_salt,
                     &self.recovery_config.answer_hash,
+                    self.recovery_config.kdf,
                 ) {
                     Ok(true) => {}
                     Ok(false) => {
                         self.error_message = Some("Incorrect answer. Try again.".to_string());
                         self.answer.zeroize();
                         self.answer = String::new();
-                        return RecoveryAction::Continue;
+                        self.retry_at = Some(
+                            Instant::now() + Duration::from_secs(RECOVERY_RETRY_DELAY_SECS),
+                        );
+                        return RecoveryAction::WrongAnswer;
                     }
                     Err(_) => {
                         self.error_message =
@@ -141,6 +203,7 @@ impl RecoveryScreen {
                     &self.recovery_config.master_key_blob_nonce,
                     &self.recovery_config.master_key_blob_salt,
                     &normalized,
+                    self.recovery_config.kdf,
                 ) {
                     Ok(key) => {
                         self.master_key = Some(key);
@@ -157,9 +220,8 @@ impl RecoveryScreen {
                 }
             }
             Step::NewPassword => {
-                if self.new_password.len() < 8 {
-                    self.error_message =
-                        Some("Password must be at least 8 characters.".to_string());
+                if let Err(e) = self.policy.check(&self.new_password) {
+                    self.error_message = Some(e.to_string());
                     return RecoveryAction::Continue;
                 }
                 self.step = Step::ConfirmPassword;
@@ -249,7 +311,7 @@ impl RecoveryScreen {
                     Span::styled("\u{2588}", Style::default().fg(Color::Cyan)),
                 ]));
                 lines.push(Line::from(Span::styled(
-                    "  (minimum 8 characters)",
+                    format!("  (minimum {} characters)", self.policy.min_length),
                     Style::default().fg(Color::DarkGray),
                 )));
             }
@@ -270,7 +332,18 @@ impl RecoveryScreen {
             }
         }
 
-        if let Some(ref error) = self.error_message {
+        let status = match self.step {
+            Step::Answer => self
+                .cooldown_secs()
+                .map(recovery::cooldown_message)
+                .or_else(|| {
+                    self.retry_pending()
+                        .then(|| "Incorrect answer. Please wait...".to_string())
+                }),
+            _ => None,
+        };
+
+        if let Some(ref error) = status.or_else(|| self.error_message.clone()) {
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
                 format!("  {}", error),
@@ -279,10 +352,11 @@ impl RecoveryScreen {
         }
 
         lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            "  Enter: Submit | Esc: Cancel",
-            Style::default().fg(Color::DarkGray),
-        )));
+        let hint = match self.step {
+            Step::Answer => "  Enter: Submit | Esc: Cancel",
+            _ => "  Enter: Submit | Esc: Back",
+        };
+        lines.push(Line::from(Span::styled(hint, Style::default().fg(Color::DarkGray))));
 
         let paragraph = Paragraph::new(lines).block(block).wrap(Wrap { trim: false });
         frame.render_widget(paragraph, chunks[1]);
@@ -6,7 +6,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
-use zeroize::Zeroize;
+use zeroize::{Zeroize, Zeroizing};
 
 use crate::config::model::RECOVERY_QUESTIONS;
 use crate::crypto::recovery;
@@ -24,6 +24,8 @@ pub struct RecoverySetupScreen {
     answer: String,
     confirm_answer: String,
     error_message: Option<String>,
+    /// Opened because a password change cleared the previous recovery
+    resetup: bool,
 }
 
 impl Drop for RecoverySetupScreen {
@@ -48,9 +50,30 @@ impl RecoverySetupScreen {
             answer: String::new(),
             confirm_answer: String::new(),
             error_message: None,
+            resetup: false,
         }
     }
 
+    /// Setup offered right after a password change cleared recovery.
+    pub fn resetup() -> Self {
+        let mut screen = Self::new();
+        screen.resetup = true;
+        screen
+    }
+
+    pub fn is_resetup(&self) -> bool {
+        self.resetup
+    }
+
+    /// Keys listed in the F1 help overlay.
+    pub fn help_keys(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("↑/↓", "Choose a question"),
+            ("Enter", "Select the question or submit the answer"),
+            ("Esc", "Back one step, or cancel from the question list"),
+        ]
+    }
+
     pub fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> RecoverySetupAction {
         if key == KeyCode::Esc {
             match self.step {
@@ -104,10 +127,11 @@ impl RecoverySetupScreen {
                     RecoverySetupAction::Continue
                 }
                 KeyCode::Enter => {
-                    let trimmed = self.answer.trim();
-                    if trimmed.len() < 3 {
-                        self.error_message =
-                            Some("Answer must be at least 3 characters.".to_string());
+                    if self.answer_length() < recovery::MIN_ANSWER_LENGTH {
+                        self.error_message = Some(format!(
+                            "Answer must be at least {} characters.",
+                            recovery::MIN_ANSWER_LENGTH
+                        ));
                         RecoverySetupAction::Continue
                     } else {
                         self.step = Step::ConfirmAnswer;
@@ -145,6 +169,11 @@ impl RecoverySetupScreen {
         }
     }
 
+    /// Characters in the answer as it will be matched, after `normalize_answer`.
+    fn answer_length(&self) -> usize {
+        Zeroizing::new(recovery::normalize_answer(&self.answer)).chars().count()
+    }
+
     pub fn render(&self, frame: &mut Frame) {
         let area = frame.area();
 
@@ -152,7 +181,7 @@ impl RecoverySetupScreen {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Min(1),
-                Constraint::Length(14),
+                Constraint::Length(if self.resetup { 17 } else { 14 }),
                 Constraint::Min(1),
             ])
             .split(area);
@@ -172,14 +201,21 @@ impl RecoverySetupScreen {
 
         match self.step {
             Step::SelectQuestion => {
-                let mut lines = vec![
-                    Line::from(""),
+                let mut lines = vec![Line::from("")];
+                if self.resetup {
+                    lines.push(Line::from(Span::styled(
+                        "Your master password changed, so recovery was cleared. Set it up again now, or press Esc to skip.",
+                        Style::default().fg(Color::Green),
+                    )));
+                    lines.push(Line::from(""));
+                }
+                lines.extend([
                     Line::from(Span::styled(
                         "Select a recovery question:",
                         Style::default().fg(Color::White),
                     )),
                     Line::from(""),
-                ];
+                ]);
 
                 for (i, question) in RECOVERY_QUESTIONS.iter().enumerate() {
                     let style = if i == self.question_index {
@@ -230,7 +266,15 @@ impl RecoverySetupScreen {
                         Span::styled("\u{2588}", Style::default().fg(Color::Cyan)),
                     ]),
                     Line::from(Span::styled(
-                        "  (minimum 3 characters)",
+                        format!(
+                            "  {} characters as matched (minimum {})",
+                            self.answer_length(),
+                            recovery::MIN_ANSWER_LENGTH
+                        ),
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                    Line::from(Span::styled(
+                        "  Case and extra spaces are ignored, so \"Blue  Sky\" matches \"blue sky\".",
                         Style::default().fg(Color::DarkGray),
                     )),
                 ];
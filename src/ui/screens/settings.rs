@@ -8,21 +8,260 @@ use ratatui::{
 };
 
 use crate::config::model::Config;
+use crate::ui::screens::add_entry::{network_options, SECRET_TYPES};
+use crate::ui::theme::MASK_CHARS;
 
-#[derive(Clone, PartialEq)]
-enum SettingsField {
-    ClipboardTimeout,
-    RecoveryStatus,
+/// How a setting is edited when Enter is pressed on it.
+enum Editor {
+    /// Typed in as digits; values below `min` are ignored
+    Numeric {
+        get: fn(&Config) -> u64,
+        set: fn(&mut Config, u64),
+        min: u64,
+        unit: &'static str,
+    },
+    /// Flipped on/off
+    Toggle {
+        get: fn(&Config) -> bool,
+        set: fn(&mut Config, bool),
+    },
+    /// Advanced to the next of a fixed set of options
+    Cycle {
+        get: fn(&Config) -> String,
+        next: fn(&mut Config),
+    },
+    /// Opens another screen
+    Action {
+        status: fn(&Config) -> String,
+        action: fn() -> SettingsAction,
+    },
 }
 
-const FIELDS: [SettingsField; 2] = [
-    SettingsField::ClipboardTimeout,
-    SettingsField::RecoveryStatus,
+struct Setting {
+    label: &'static str,
+    /// Shown after the value, e.g. when the change takes effect
+    note: &'static str,
+    editor: Editor,
+    /// Blank line after this setting to group related options
+    group_end: bool,
+}
+
+const SETTINGS: [Setting; 19] = [
+    Setting {
+        label: "Clipboard timeout",
+        note: "",
+        editor: Editor::Numeric {
+            get: |c| c.clipboard_timeout_secs,
+            set: |c, v| c.clipboard_timeout_secs = v,
+            min: 1,
+            unit: "seconds",
+        },
+        group_end: false,
+    },
+    Setting {
+        label: "Confirm before copying keys and seeds",
+        note: "",
+        editor: Editor::Toggle {
+            get: |c| c.confirm_secret_copy,
+            set: |c, v| c.confirm_secret_copy = v,
+        },
+        group_end: false,
+    },
+    Setting {
+        label: "Never use the clipboard",
+        note: "",
+        editor: Editor::Toggle {
+            get: |c| c.disable_clipboard,
+            set: |c, v| c.disable_clipboard = v,
+        },
+        group_end: false,
+    },
+    Setting {
+        label: "Warn before copying over SSH",
+        note: "",
+        editor: Editor::Toggle {
+            get: |c| c.warn_remote_clipboard,
+            set: |c, v| c.warn_remote_clipboard = v,
+        },
+        group_end: false,
+    },
+    Setting {
+        label: "Hide revealed secret after",
+        note: "(0 = never)",
+        editor: Editor::Numeric {
+            get: |c| c.reveal_timeout_secs,
+            set: |c, v| c.reveal_timeout_secs = v,
+            min: 0,
+            unit: "seconds",
+        },
+        group_end: false,
+    },
+    Setting {
+        label: "Blank screen when idle after",
+        note: "(0 = never)",
+        editor: Editor::Numeric {
+            get: |c| c.blank_after_secs,
+            set: |c, v| c.blank_after_secs = v,
+            min: 0,
+            unit: "seconds",
+        },
+        group_end: false,
+    },
+    Setting {
+        label: "Blank screen when the window loses focus",
+        note: "(if the terminal reports it)",
+        editor: Editor::Toggle {
+            get: |c| c.blank_on_focus_loss,
+            set: |c, v| c.blank_on_focus_loss = v,
+        },
+        group_end: true,
+    },
+    Setting {
+        label: "Mask character",
+        note: "",
+        editor: Editor::Cycle {
+            get: |c| c.mask_char.to_string(),
+            next: |c| {
+                let next = MASK_CHARS
+                    .iter()
+                    .position(|m| *m == c.mask_char)
+                    .map_or(0, |i| (i + 1) % MASK_CHARS.len());
+                c.mask_char = MASK_CHARS[next];
+            },
+        },
+        group_end: false,
+    },
+    Setting {
+        label: "Network glyphs in lists",
+        note: "(\u{039e} \u{20bf} \u{25ce})",
+        editor: Editor::Toggle {
+            get: |c| c.network_glyphs,
+            set: |c, v| c.network_glyphs = v,
+        },
+        group_end: false,
+    },
+    Setting {
+        label: "Show last characters of hidden keys",
+        note: "(0 = off, never for seed phrases)",
+        editor: Editor::Numeric {
+            get: |c| c.reveal_last_chars as u64,
+            set: |c, v| c.reveal_last_chars = v as usize,
+            min: 0,
+            unit: "",
+        },
+        group_end: true,
+    },
+    Setting {
+        label: "Default secret type",
+        note: "",
+        editor: Editor::Cycle {
+            get: |c| c.default_secret_type.to_string(),
+            next: |c| {
+                let next = SECRET_TYPES
+                    .iter()
+                    .position(|t| *t == c.default_secret_type)
+                    .map_or(0, |i| (i + 1) % SECRET_TYPES.len());
+                c.default_secret_type = SECRET_TYPES[next].clone();
+            },
+        },
+        group_end: false,
+    },
+    Setting {
+        label: "Default network",
+        note: "",
+        editor: Editor::Cycle {
+            get: |c| c.default_network.clone(),
+            next: |c| {
+                let networks = network_options(c);
+                let next = networks
+                    .iter()
+                    .position(|n| n.eq_ignore_ascii_case(&c.default_network))
+                    .map_or(0, |i| (i + 1) % networks.len());
+                c.default_network = networks[next].clone();
+            },
+        },
+        group_end: true,
+    },
+    Setting {
+        label: "Minimum password length",
+        note: "(new passwords)",
+        editor: Editor::Numeric {
+            get: |c| c.password_policy.min_length as u64,
+            set: |c, v| c.password_policy.min_length = v as usize,
+            min: 1,
+            unit: "characters",
+        },
+        group_end: false,
+    },
+    Setting {
+        label: "Require mixed character types",
+        note: "(3 of: lower, upper, digit, symbol)",
+        editor: Editor::Toggle {
+            get: |c| c.password_policy.require_mix,
+            set: |c, v| c.password_policy.require_mix = v,
+        },
+        group_end: true,
+    },
+    Setting {
+        label: "Secondary password KDF memory",
+        note: "(new entries)",
+        editor: Editor::Numeric {
+            get: |c| (c.entry_key_kdf.m_cost / 1024) as u64,
+            set: |c, v| c.entry_key_kdf.m_cost = (v as u32).saturating_mul(1024),
+            min: 8,
+            unit: "MB",
+        },
+        group_end: false,
+    },
+    Setting {
+        label: "Secondary password KDF iterations",
+        note: "(new entries)",
+        editor: Editor::Numeric {
+            get: |c| c.entry_key_kdf.t_cost as u64,
+            set: |c, v| c.entry_key_kdf.t_cost = v as u32,
+            min: 1,
+            unit: "",
+        },
+        group_end: true,
+    },
+    Setting {
+        label: "Lock secrets in memory",
+        note: "(next unlock)",
+        editor: Editor::Toggle {
+            get: |c| c.lock_memory,
+            set: |c, v| c.lock_memory = v,
+        },
+        group_end: true,
+    },
+    Setting {
+        label: "Recovery question",
+        note: "",
+        editor: Editor::Action {
+            status: |c| {
+                if c.recovery.is_some() {
+                    "Configured".to_string()
+                } else {
+                    "Not set".to_string()
+                }
+            },
+            action: || SettingsAction::SetupRecovery,
+        },
+        group_end: false,
+    },
+    Setting {
+        label: "Keep recovery in its own file",
+        note: "(recovery.json, not config.json)",
+        editor: Editor::Toggle {
+            get: |c| c.recovery_in_separate_file,
+            set: |c, v| c.recovery_in_separate_file = v,
+        },
+        group_end: true,
+    },
 ];
 
 pub enum SettingsAction {
     Continue,
-    Save(Config),
+    Save(Box<Config>),
     Cancel,
     SetupRecovery,
 }
@@ -44,6 +283,17 @@ impl SettingsScreen {
         }
     }
 
+    /// Keys listed in the help overlay.
+    pub fn help_keys(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("↑/↓", "Choose a setting"),
+            ("Enter", "Edit a number, flip a toggle, or cycle an option"),
+            ("0-9", "Type the new number while editing"),
+            ("Esc", "Finish editing, or save and close"),
+            ("q", "Save and close"),
+        ]
+    }
+
     pub fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers) -> SettingsAction {
         if key == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL) {
             return SettingsAction::Cancel;
@@ -61,25 +311,28 @@ impl SettingsScreen {
                 SettingsAction::Continue
             }
             KeyCode::Down => {
-                if self.selected < FIELDS.len() - 1 {
+                if self.selected < SETTINGS.len() - 1 {
                     self.selected += 1;
                 }
                 SettingsAction::Continue
             }
             KeyCode::Enter => {
-                match FIELDS[self.selected] {
-                    SettingsField::ClipboardTimeout => {
+                match &SETTINGS[self.selected].editor {
+                    Editor::Numeric { get, .. } => {
                         self.editing = true;
-                        self.edit_buffer = self.config.clipboard_timeout_secs.to_string();
+                        self.edit_buffer = get(&self.config).to_string();
                     }
-                    SettingsField::RecoveryStatus => {
-                        return SettingsAction::SetupRecovery;
+                    Editor::Toggle { get, set } => {
+                        let current = get(&self.config);
+                        set(&mut self.config, !current);
                     }
+                    Editor::Cycle { next, .. } => next(&mut self.config),
+                    Editor::Action { action, .. } => return action(),
                 }
                 SettingsAction::Continue
             }
-            KeyCode::Esc => SettingsAction::Save(self.config.clone()),
-            KeyCode::Char('q') => SettingsAction::Save(self.config.clone()),
+            KeyCode::Esc => SettingsAction::Save(Box::new(self.config.clone())),
+            KeyCode::Char('q') => SettingsAction::Save(Box::new(self.config.clone())),
             _ => SettingsAction::Continue,
         }
     }
@@ -95,9 +348,11 @@ impl SettingsScreen {
                 SettingsAction::Continue
             }
             KeyCode::Enter => {
-                if let Ok(val) = self.edit_buffer.parse::<u64>() {
-                    if val > 0 {
-                        self.config.clipboard_timeout_secs = val;
+                if let Editor::Numeric { set, min, .. } = &SETTINGS[self.selected].editor {
+                    if let Ok(val) = self.edit_buffer.parse::<u64>() {
+                        if val >= *min {
+                            set(&mut self.config, val);
+                        }
                     }
                 }
                 self.editing = false;
@@ -113,6 +368,17 @@ impl SettingsScreen {
         }
     }
 
+    fn field_style(&self, index: usize) -> Style {
+        if self.selected == index {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        }
+    }
+
     pub fn render(&self, frame: &mut Frame) {
         let area = frame.area();
 
@@ -120,7 +386,7 @@ impl SettingsScreen {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Min(1),
-                Constraint::Length(14),
+                Constraint::Length(36),
                 Constraint::Min(1),
             ])
             .split(area);
@@ -136,57 +402,44 @@ impl SettingsScreen {
             Line::from(""),
         ];
 
-        // Clipboard timeout
-        let timeout_selected = self.selected == 0;
-        let timeout_style = if timeout_selected {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Cyan)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::White)
-        };
-
-        if self.editing && timeout_selected {
-            lines.push(Line::from(vec![
-                Span::styled("  Clipboard timeout: ", Style::default().fg(Color::White)),
-                Span::styled(&self.edit_buffer, Style::default().fg(Color::Yellow)),
-                Span::styled("█", Style::default().fg(Color::Cyan)),
-                Span::styled(" seconds", Style::default().fg(Color::DarkGray)),
-            ]));
-        } else {
-            lines.push(Line::from(Span::styled(
-                format!(
-                    "  Clipboard timeout: {} seconds",
-                    self.config.clipboard_timeout_secs
-                ),
-                timeout_style,
-            )));
-        }
-
-        lines.push(Line::from(""));
-
-        // Recovery status
-        let recovery_selected = self.selected == 1;
-        let recovery_style = if recovery_selected {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Cyan)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::White)
-        };
-        let recovery_status = if self.config.recovery.is_some() {
-            "Configured"
-        } else {
-            "Not set"
-        };
-        lines.push(Line::from(Span::styled(
-            format!("  Recovery question: {}", recovery_status),
-            recovery_style,
-        )));
+        for (i, setting) in SETTINGS.iter().enumerate() {
+            if self.editing && i == self.selected {
+                let unit = match setting.editor {
+                    Editor::Numeric { unit, .. } => unit,
+                    _ => "",
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {}: ", setting.label),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(&self.edit_buffer, Style::default().fg(Color::Yellow)),
+                    Span::styled("█", Style::default().fg(Color::Cyan)),
+                    Span::styled(format!(" {}", unit), Style::default().fg(Color::DarkGray)),
+                ]));
+            } else {
+                let value = match &setting.editor {
+                    Editor::Numeric { get, unit, .. } => {
+                        format!("{} {}", get(&self.config), unit).trim_end().to_string()
+                    }
+                    Editor::Toggle { get, .. } => {
+                        if get(&self.config) { "On" } else { "Off" }.to_string()
+                    }
+                    Editor::Cycle { get, .. } => get(&self.config),
+                    Editor::Action { status, .. } => status(&self.config),
+                };
+                let mut text = format!("  {}: {}", setting.label, value);
+                if !setting.note.is_empty() {
+                    text.push(' ');
+                    text.push_str(setting.note);
+                }
+                lines.push(Line::from(Span::styled(text, self.field_style(i))));
+            }
 
-        lines.push(Line::from(""));
+            if setting.group_end {
+                lines.push(Line::from(""));
+            }
+        }
 
         // Vault path (display only)
         lines.push(Line::from(vec![
@@ -196,7 +449,7 @@ impl SettingsScreen {
 
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
-            "  ↑/↓ Navigate  |  Enter Edit  |  Esc Save & Close",
+            "  ↑/↓ Navigate  |  Enter Edit  |  ? Keys  |  Esc Save & Close",
             Style::default().fg(Color::DarkGray),
         )));
 
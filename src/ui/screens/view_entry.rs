@@ -7,21 +7,54 @@ use ratatui::{
     Frame,
 };
 
-use crate::vault::model::Entry;
+use crate::crypto::derive::{self, ChainAddress};
+use crate::ui::theme::{chunk_for_display, Mask};
+use crate::vault::model::{CustomField, Entry, SecretType};
+
+/// Group size for a revealed private key (hex keys read as 8 groups of 8)
+const KEY_CHUNK: usize = 8;
+/// Group size for public addresses
+const ADDRESS_CHUNK: usize = 4;
 
 pub struct ViewEntryScreen {
     pub entry: Entry,
     secret_revealed: bool,
+    mask: Mask,
+    /// Show the private key and addresses in space-separated groups (`g`)
+    chunked: bool,
+    /// Addresses on every built-in network, once requested with `a`
+    all_addresses: Option<Vec<(&'static str, ChainAddress)>>,
 }
 
 impl ViewEntryScreen {
-    pub fn new(entry: Entry) -> Self {
+    pub fn new(entry: Entry, mask: Mask) -> Self {
         Self {
             entry,
             secret_revealed: false,
+            mask,
+            chunked: false,
+            all_addresses: None,
         }
     }
 
+    /// Whether the secret is currently shown in plain text.
+    pub fn is_revealed(&self) -> bool {
+        self.secret_revealed
+    }
+
+    /// Keys listed in the help overlay.
+    pub fn help_keys(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("r", "Reveal or hide the secret"),
+            ("c", "Copy the secret (while revealed)"),
+            ("1-9", "Copy a custom field (sensitive ones while revealed)"),
+            ("a", "Show the address on every network"),
+            ("g", "Group the key and addresses in chunks (copying is unaffected)"),
+            ("x", "Export this entry to its own backup"),
+            ("Esc/q", "Close"),
+        ]
+    }
+
     pub fn handle_key(&mut self, key: KeyCode, _modifiers: KeyModifiers) -> ViewEntryAction {
         match key {
             KeyCode::Esc | KeyCode::Char('q') => ViewEntryAction::Close,
@@ -29,6 +62,26 @@ impl ViewEntryScreen {
                 self.secret_revealed = !self.secret_revealed;
                 ViewEntryAction::Continue
             }
+            KeyCode::Char('a') if self.entry.secret_type != SecretType::Password => {
+                if self.all_addresses.take().is_none() {
+                    self.all_addresses = Some(derive::derive_all(&self.entry.secret, &self.entry.secret_type));
+                }
+                ViewEntryAction::Continue
+            }
+            KeyCode::Char('g') => {
+                self.chunked = !self.chunked;
+                ViewEntryAction::Continue
+            }
+            KeyCode::Char('x') => ViewEntryAction::Export(self.entry.name.clone()),
+            KeyCode::Char(c @ '1'..='9') => {
+                let idx = c as usize - '1' as usize;
+                match self.entry.custom_fields.get(idx) {
+                    Some(field) if !field.sensitive || self.secret_revealed => {
+                        ViewEntryAction::Copy(field.value.clone())
+                    }
+                    _ => ViewEntryAction::Continue,
+                }
+            }
             KeyCode::Char('c') => {
                 if self.secret_revealed {
                     ViewEntryAction::Copy(self.entry.secret.clone())
@@ -40,12 +93,47 @@ impl ViewEntryScreen {
         }
     }
 
+    /// Sensitive fields share the secret's reveal toggle.
+    fn field_value_span(&self, field: &CustomField) -> Span<'static> {
+        if !field.sensitive {
+            Span::styled(field.value.clone(), Style::default().fg(Color::White))
+        } else if self.secret_revealed {
+            Span::styled(field.value.clone(), Style::default().fg(Color::Yellow))
+        } else {
+            Span::styled(self.mask.hide(8), Style::default().fg(Color::DarkGray))
+        }
+    }
+
+    /// `value` as displayed: grouped when chunking is on. Copy actions use the
+    /// stored value, so the groups never reach the clipboard.
+    fn display(&self, value: &str, size: usize) -> String {
+        if self.chunked {
+            chunk_for_display(value, size)
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// Rows taken by the all-networks address list: a blank line, the title,
+    /// and one per network (or a single note when none apply).
+    fn all_addresses_height(&self) -> u16 {
+        self.all_addresses
+            .as_ref()
+            .map_or(0, |report| 2 + report.len().max(1) as u16)
+    }
+
     pub fn render(&self, frame: &mut Frame) {
         let area = frame.area();
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(1), Constraint::Length(20), Constraint::Min(1)])
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(
+                    20 + self.entry.custom_fields.len() as u16 + self.all_addresses_height(),
+                ),
+                Constraint::Min(1),
+            ])
             .split(area);
 
         let view_area = centered_rect(70, chunks[1]);
@@ -72,7 +160,7 @@ impl ViewEntryScreen {
 
         lines.push(Line::from(""));
 
-        if !matches!(self.entry.secret_type, crate::vault::model::SecretType::Password) {
+        if !matches!(self.entry.secret_type, SecretType::Password) {
             lines.push(Line::from(vec![
                 Span::styled("Network: ", Style::default().fg(Color::Cyan)),
                 Span::styled(self.entry.network.clone(), Style::default().fg(Color::White)),
@@ -81,9 +169,26 @@ impl ViewEntryScreen {
             if let Some(ref addr) = self.entry.public_address {
                 lines.push(Line::from(vec![
                     Span::styled("Public Address: ", Style::default().fg(Color::Cyan)),
-                    Span::styled(addr.clone(), Style::default().fg(Color::White)),
+                    Span::styled(self.display(addr, ADDRESS_CHUNK), Style::default().fg(Color::White)),
+                ]));
+            }
+
+            if self.entry.derivation_account != 0 || self.entry.derivation_index != 0 {
+                lines.push(Line::from(vec![
+                    Span::styled("Account: ", Style::default().fg(Color::Cyan)),
+                    Span::styled(
+                        format!(
+                            "{}, address {}",
+                            self.entry.derivation_account, self.entry.derivation_index
+                        ),
+                        Style::default().fg(Color::White),
+                    ),
                 ]));
             }
+
+            if let Some(ref report) = self.all_addresses {
+                lines.extend(all_address_lines(report, |a| self.display(a, ADDRESS_CHUNK)));
+            }
         } else {
             if let Some(ref username) = self.entry.username {
                 lines.push(Line::from(vec![
@@ -109,15 +214,30 @@ impl ViewEntryScreen {
             lines.push(Line::from(self.entry.notes.clone()));
         }
 
+        if !self.entry.custom_fields.is_empty() {
+            lines.push(Line::from(""));
+            for (i, field) in self.entry.custom_fields.iter().enumerate() {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("[{}] ", i + 1), Style::default().fg(Color::DarkGray)),
+                    Span::styled(format!("{}: ", field.name), Style::default().fg(Color::Cyan)),
+                    self.field_value_span(field),
+                ]));
+            }
+        }
+
         lines.push(Line::from(""));
         lines.push(Line::from(""));
 
         let secret_display = if self.entry.has_secondary_password && !self.secret_revealed {
             "[Protected - secondary password required]".to_string()
+        } else if self.secret_revealed && self.entry.secret_type == SecretType::PrivateKey {
+            self.display(&self.entry.secret, KEY_CHUNK)
         } else if self.secret_revealed {
+            // Seed phrases are already words, and spaces in a password would mislead
             self.entry.secret.clone()
         } else {
-            "••••••••••••••••".to_string()
+            // Masks are fixed-width, so they are never grouped
+            self.mask.hide_secret(&self.entry.secret, &self.entry.secret_type)
         };
 
         lines.push(Line::from(vec![
@@ -135,11 +255,14 @@ impl ViewEntryScreen {
         lines.push(Line::from(""));
         lines.push(Line::from(""));
 
-        let help_text = if self.secret_revealed {
-            "r: Hide secret │ c: Copy to clipboard │ Esc/q: Close"
+        let mut help_text = if self.secret_revealed {
+            "r: Hide secret │ c: Copy to clipboard │ x: Export entry │ ?: Keys │ Esc/q: Close".to_string()
         } else {
-            "r: Reveal secret │ Esc/q: Close"
+            "r: Reveal secret │ a: All addresses │ x: Export entry │ ?: Keys │ Esc/q: Close".to_string()
         };
+        if !self.entry.custom_fields.is_empty() {
+            help_text.push_str(" │ 1-9: Copy field");
+        }
 
         lines.push(Line::from(vec![Span::styled(
             help_text,
@@ -151,6 +274,35 @@ impl ViewEntryScreen {
     }
 }
 
+fn all_address_lines(
+    report: &[(&'static str, ChainAddress)],
+    display: impl Fn(&str) -> String,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled("All networks:", Style::default().fg(Color::Cyan))),
+    ];
+    if report.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No address derivation in this build",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    let width = report.iter().map(|(network, _)| network.len()).max().unwrap_or(0);
+    for (network, address) in report {
+        let (value, color) = match address {
+            ChainAddress::Derived(address) => (display(address), Color::White),
+            ChainAddress::Failed(reason) => (reason.clone(), Color::DarkGray),
+            ChainAddress::NotCompiled => ("not in this build".to_string(), Color::DarkGray),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<width$}  ", network, width = width), Style::default().fg(Color::Cyan)),
+            Span::styled(value, Style::default().fg(color)),
+        ]));
+    }
+    lines
+}
+
 fn centered_rect(percent: u16, r: Rect) -> Rect {
     let width = r.width * percent / 100;
     let x = r.x + (r.width - width) / 2;
@@ -165,5 +317,7 @@ fn centered_rect(percent: u16, r: Rect) -> Rect {
 pub enum ViewEntryAction {
     Continue,
     Copy(String),
+    /// Export this entry (by name) to its own encrypted backup
+    Export(String),
     Close,
 }
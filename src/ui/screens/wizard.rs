@@ -7,7 +7,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::config::model::RECOVERY_QUESTIONS;
+use crate::config::model::{PasswordPolicy, RECOVERY_QUESTIONS};
 
 #[derive(Clone)]
 enum WizardStep {
@@ -35,6 +35,7 @@ pub struct WizardScreen {
     recovery_answer: String,
     recovery_confirm_answer: String,
     error_message: Option<String>,
+    policy: PasswordPolicy,
 }
 
 pub enum WizardAction {
@@ -44,7 +45,7 @@ pub enum WizardAction {
 }
 
 impl WizardScreen {
-    pub fn new() -> Self {
+    pub fn new(policy: PasswordPolicy) -> Self {
         Self {
             step: WizardStep::Welcome,
             password: String::new(),
@@ -54,6 +55,7 @@ impl WizardScreen {
             recovery_answer: String::new(),
             recovery_confirm_answer: String::new(),
             error_message: None,
+            policy,
         }
     }
 
@@ -84,8 +86,8 @@ impl WizardScreen {
                     WizardAction::Continue
                 }
                 KeyCode::Enter => {
-                    if self.password.is_empty() {
-                        self.error_message = Some("Password cannot be empty.".into());
+                    if let Err(e) = self.policy.check(&self.password) {
+                        self.error_message = Some(e.to_string());
                         WizardAction::Continue
                     } else {
                         self.step = WizardStep::ConfirmPassword;
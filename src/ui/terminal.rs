@@ -1,4 +1,5 @@
 use crossterm::{
+    event::{DisableBracketedPaste, DisableFocusChange, EnableBracketedPaste, EnableFocusChange},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, Clear, ClearType},
 };
@@ -8,30 +9,44 @@ use ratatui::{
 };
 use std::io::{self, Stdout};
 
+use crate::logging;
+
 pub type Tui = Terminal<CrosstermBackend<Stdout>>;
 
 pub fn init() -> io::Result<Tui> {
-    execute!(io::stdout(), EnterAlternateScreen)?;
+    // Bracketed paste delivers a paste as one event instead of a burst of keys.
+    // Focus changes are reported only by terminals that support them.
+    execute!(io::stdout(), EnterAlternateScreen, EnableBracketedPaste, EnableFocusChange)?;
     enable_raw_mode()?;
+    logging::set_suspended(true);
     let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
     terminal.clear()?;
     Ok(terminal)
 }
 
 pub fn restore() -> io::Result<()> {
-    execute!(io::stdout(), LeaveAlternateScreen)?;
+    execute!(io::stdout(), DisableFocusChange, DisableBracketedPaste, LeaveAlternateScreen)?;
     disable_raw_mode()?;
+    logging::set_suspended(false);
     Ok(())
 }
 
 pub fn exit_raw_mode_temporarily() -> io::Result<()> {
     disable_raw_mode()?;
-    execute!(io::stdout(), LeaveAlternateScreen)?;
+    execute!(io::stdout(), DisableFocusChange, DisableBracketedPaste, LeaveAlternateScreen)?;
+    logging::set_suspended(false);
     Ok(())
 }
 
 pub fn reenter_raw_mode() -> io::Result<()> {
-    execute!(io::stdout(), EnterAlternateScreen, Clear(ClearType::All))?;
+    execute!(
+        io::stdout(),
+        EnterAlternateScreen,
+        EnableBracketedPaste,
+        EnableFocusChange,
+        Clear(ClearType::All)
+    )?;
     enable_raw_mode()?;
+    logging::set_suspended(true);
     Ok(())
 }
@@ -1,6 +1,16 @@
 use colored::{ColoredString, Colorize};
 
+use crate::config::model::Config;
+use crate::vault::model::SecretType;
+
 use std::io::{self, Write};
+use std::iter;
+use std::time::Duration;
+
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Terminal title that reveals nothing about an open vault.
+pub const APP_TITLE: &str = "CryptoKeeper";
 
 pub fn set_title(title: &str) {
     let mut out = io::stdout();
@@ -25,3 +35,105 @@ pub fn heading(text: &str) -> ColoredString {
 pub fn dim_border(ch: &str) -> ColoredString {
     ch.cyan().dimmed()
 }
+
+/// Spinner glyph for the given elapsed time (advances every 80ms).
+pub fn spinner_frame(elapsed: Duration) -> &'static str {
+    SPINNER_FRAMES[(elapsed.as_millis() / 80) as usize % SPINNER_FRAMES.len()]
+}
+
+/// Single-column symbol for a well-known network, or "" for others.
+pub fn network_glyph(network: &str) -> &'static str {
+    match network.to_lowercase().as_str() {
+        "ethereum" => "\u{039e}",
+        "bitcoin" => "\u{20bf}",
+        "solana" => "\u{25ce}",
+        _ => "",
+    }
+}
+
+/// Network name for entry lists, prefixed with its glyph when enabled.
+pub fn network_label(network: &str, glyphs: bool) -> String {
+    match network_glyph(network) {
+        glyph if glyphs && !glyph.is_empty() => format!("{} {}", glyph, network),
+        _ => network.to_string(),
+    }
+}
+
+/// `s` in space-separated groups of `n` characters, for reading a long key or
+/// address aloud or comparing it against another copy. A `0x` prefix stays
+/// attached to the first group. Only for display: copy the original, never
+/// this.
+pub fn chunk_for_display(s: &str, n: usize) -> String {
+    if n == 0 {
+        return s.to_string();
+    }
+    let (prefix, rest) = match s.strip_prefix("0x") {
+        Some(rest) => ("0x", rest),
+        None => ("", s),
+    };
+    let mut chunked = String::with_capacity(s.len() + s.len() / n);
+    chunked.push_str(prefix);
+    for (i, c) in rest.chars().enumerate() {
+        if i > 0 && i % n == 0 {
+            chunked.push(' ');
+        }
+        chunked.push(c);
+    }
+    chunked
+}
+
+/// Mask characters offered in settings.
+pub const MASK_CHARS: [char; 4] = ['\u{2022}', '*', '#', '\u{00b7}'];
+
+/// How hidden secrets are drawn, from the user's config.
+#[derive(Clone, Copy)]
+pub struct Mask {
+    pub ch: char,
+    pub reveal_last: usize,
+}
+
+impl Mask {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            ch: config.mask_char,
+            reveal_last: config.reveal_last_chars,
+        }
+    }
+
+    /// `len` mask characters, e.g. for a field being typed.
+    pub fn hide(&self, len: usize) -> String {
+        iter::repeat_n(self.ch, len).collect()
+    }
+
+    /// Fixed-width mask of a stored secret, ending in its last few characters
+    /// when partial reveal is on. Seed phrases are always fully masked, and
+    /// nothing is shown unless the secret is at least twice as long as the tail.
+    pub fn hide_secret(&self, secret: &str, secret_type: &SecretType) -> String {
+        let mut masked = self.hide(16);
+        let len = secret.chars().count();
+        if self.reveal_last > 0 && *secret_type != SecretType::SeedPhrase && len >= self.reveal_last * 2
+        {
+            masked.extend(secret.chars().skip(len - self.reveal_last));
+        }
+        masked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_keep_every_character_in_order() {
+        assert_eq!(chunk_for_display("abcdefghij", 4), "abcd efgh ij");
+        assert_eq!(chunk_for_display("abcdefgh", 4), "abcd efgh");
+        assert_eq!(chunk_for_display("0x1234abcd5678", 4), "0x1234 abcd 5678");
+        assert_eq!(chunk_for_display("", 4), "");
+        assert_eq!(chunk_for_display("abc", 0), "abc");
+
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+        let chunked = chunk_for_display(key, 8);
+        assert_eq!(chunked.split(' ').count(), 8);
+        assert_eq!(chunked.replace(' ', ""), key);
+    }
+}
@@ -0,0 +1,131 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use zeroize::Zeroize;
+
+use crate::ui::theme::Mask;
+use crate::vault::model::CustomField;
+
+/// Editable list of custom fields shared by the add and edit forms.
+/// Each row occupies two focusable slots: its name, then its value.
+pub struct CustomFieldsEditor {
+    fields: Vec<CustomField>,
+}
+
+impl Drop for CustomFieldsEditor {
+    fn drop(&mut self) {
+        for field in &mut self.fields {
+            field.value.zeroize();
+        }
+    }
+}
+
+impl CustomFieldsEditor {
+    pub fn new(fields: Vec<CustomField>) -> Self {
+        Self { fields }
+    }
+
+    /// Number of focusable slots (two per row).
+    pub fn slot_count(&self) -> usize {
+        self.fields.len() * 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.iter().all(|f| f.name.is_empty() && f.value.is_empty())
+    }
+
+    /// Append an empty row; returns the slot of its name.
+    pub fn add_row(&mut self) -> usize {
+        self.fields.push(CustomField::default());
+        self.slot_count() - 2
+    }
+
+    /// Remove the row containing `slot`.
+    pub fn remove_row(&mut self, slot: usize) {
+        if slot / 2 < self.fields.len() {
+            let mut field = self.fields.remove(slot / 2);
+            field.value.zeroize();
+        }
+    }
+
+    /// Flip whether the row containing `slot` is masked like a secret.
+    pub fn toggle_sensitive(&mut self, slot: usize) {
+        if let Some(field) = self.fields.get_mut(slot / 2) {
+            field.sensitive = !field.sensitive;
+        }
+    }
+
+    pub fn insert_char(&mut self, slot: usize, c: char) {
+        if let Some(text) = self.slot_text(slot) {
+            text.push(c);
+        }
+    }
+
+    pub fn delete_char(&mut self, slot: usize) {
+        if let Some(text) = self.slot_text(slot) {
+            text.pop();
+        }
+    }
+
+    fn slot_text(&mut self, slot: usize) -> Option<&mut String> {
+        let field = self.fields.get_mut(slot / 2)?;
+        Some(if slot.is_multiple_of(2) {
+            &mut field.name
+        } else {
+            &mut field.value
+        })
+    }
+
+    /// Rows with a name, trimmed, ready to store on an entry.
+    pub fn to_fields(&self) -> Vec<CustomField> {
+        self.fields
+            .iter()
+            .filter(|f| !f.name.trim().is_empty())
+            .map(|f| CustomField {
+                name: f.name.trim().to_string(),
+                value: f.value.clone(),
+                sensitive: f.sensitive,
+            })
+            .collect()
+    }
+
+    /// One line per slot; `focused` is the active slot, if it is in this list.
+    /// Sensitive values are shown as dots, like the secret field.
+    pub fn render_lines(&self, focused: Option<usize>, mask: &Mask) -> Vec<Line<'static>> {
+        let mut lines = vec![];
+        for (row, field) in self.fields.iter().enumerate() {
+            let label = format!("Field {} name", row + 1);
+            lines.push(render_slot(&label, field.name.clone(), focused == Some(row * 2)));
+            let (label, value) = if field.sensitive {
+                ("  Value (sensitive)", mask.hide(field.value.chars().count()))
+            } else {
+                ("  Value", field.value.clone())
+            };
+            lines.push(render_slot(label, value, focused == Some(row * 2 + 1)));
+        }
+        lines
+    }
+}
+
+fn render_slot(label: &str, value: String, is_active: bool) -> Line<'static> {
+    let label_style = if is_active {
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+
+    let value_style = if is_active {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::Gray)
+    };
+
+    let cursor = if is_active { "\u{2588}" } else { "" };
+
+    Line::from(vec![
+        Span::styled(format!("{}: ", label), label_style),
+        Span::styled(value, value_style),
+        Span::styled(cursor, Style::default().fg(Color::Cyan)),
+    ])
+}
@@ -1,18 +1,34 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
 use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
+use crate::config::model::ListColumn;
 use crate::vault::model::EntryMeta;
 
 use super::entry_table::EntryTable;
 use super::menu_bar::MenuBar;
 use super::status_bar::StatusBar;
 
+/// Pause after which typed letters start a new name prefix.
+const TYPEAHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
 pub struct Dashboard {
     table: EntryTable,
     menu_bar: MenuBar,
+    read_only: bool,
+    clipboard_status: Option<String>,
+    unlocked_at: Option<DateTime<Local>>,
+    /// Name prefix typed so far and when its last character arrived
+    typeahead: String,
+    typeahead_at: Option<Instant>,
 }
 
 impl Dashboard {
@@ -20,18 +36,82 @@ impl Dashboard {
         Self {
             table: EntryTable::new(entries),
             menu_bar: MenuBar::new(),
+            read_only: false,
+            clipboard_status: None,
+            unlocked_at: None,
+            typeahead: String::new(),
+            typeahead_at: None,
         }
     }
 
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    pub fn set_network_glyphs(&mut self, network_glyphs: bool) {
+        self.table.set_network_glyphs(network_glyphs);
+    }
+
+    pub fn set_columns(&mut self, columns: Vec<ListColumn>) {
+        self.table.set_columns(columns);
+    }
+
+    pub fn set_clipboard_status(&mut self, status: Option<String>) {
+        self.clipboard_status = status;
+    }
+
+    pub fn set_unlocked_at(&mut self, unlocked_at: Option<DateTime<Local>>) {
+        self.unlocked_at = unlocked_at;
+    }
+
     pub fn selected_index(&self) -> Option<usize> {
         self.table.selected_index()
     }
 
+    pub fn neighbor_index(&self, up: bool) -> Option<usize> {
+        self.table.neighbor_index(up)
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<EntryMeta>, index: usize) {
+        self.table.set_entries(entries, index);
+    }
+
+    pub fn select_index(&mut self, index: usize) {
+        self.table.select_index(index);
+    }
+
     pub fn set_filter(&mut self, filter: String) {
         self.table.set_filter(filter);
     }
 
+    pub fn set_show_archived(&mut self, show_archived: bool) {
+        self.table.set_show_archived(show_archived);
+    }
+
+    /// Whether `c` belongs to a name prefix: a letter starts one, and while one
+    /// is in progress digits and spaces extend it. Punctuation such as `/`
+    /// keeps its own binding.
+    pub fn is_typeahead_char(&self, c: char) -> bool {
+        c.is_alphabetic() || (self.typeahead_active() && (c.is_alphanumeric() || c == ' '))
+    }
+
+    /// Add `c` to the name prefix and jump to the first entry it matches.
+    pub fn type_ahead(&mut self, c: char) {
+        if !self.typeahead_active() {
+            self.typeahead.clear();
+        }
+        self.typeahead.push(c);
+        self.typeahead_at = Some(Instant::now());
+        self.table.select_name_prefix(&self.typeahead);
+    }
+
+    fn typeahead_active(&self) -> bool {
+        self.typeahead_at
+            .is_some_and(|at| at.elapsed() < TYPEAHEAD_TIMEOUT)
+    }
+
     pub fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers) {
+        self.typeahead_at = None;
         self.table.handle_key(key, modifiers);
     }
 
@@ -49,16 +129,75 @@ impl Dashboard {
             .split(area);
 
         let entry_count = self.table.filtered_count();
+        let vault_name = if self.read_only {
+            "CryptoKeeper (read-only)"
+        } else {
+            "CryptoKeeper"
+        };
         let status_bar = StatusBar::new(
-            "CryptoKeeper",
+            vault_name,
             entry_count,
             self.table.filter_text(),
             self.table.number_buffer(),
+            self.clipboard_status.as_deref(),
+            self.unlocked_at,
         );
         status_bar.render(frame, chunks[0]);
 
-        self.table.render(frame, chunks[1]);
+        if self.table.is_empty() {
+            self.render_empty_vault(frame, chunks[1]);
+        } else {
+            self.table.render(frame, chunks[1]);
+        }
 
         self.menu_bar.render(frame, chunks[2]);
     }
+
+    /// First-run call to action for a vault with no entries yet.
+    fn render_empty_vault(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Entries ")
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let key = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let hint = Style::default().fg(Color::DarkGray);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                "Your vault is empty",
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+        if self.read_only {
+            lines.push(Line::from(Span::styled(
+                "The vault is open in another CryptoKeeper process, so entries can't be added.",
+                hint,
+            )));
+        } else {
+            lines.push(Line::from(vec![
+                Span::styled("Press ", Style::default().fg(Color::White)),
+                Span::styled("Shift+A", key),
+                Span::styled(" to add your first entry", Style::default().fg(Color::White)),
+            ]));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Store a private key, seed phrase or password; view it with Enter,",
+                hint,
+            )));
+            lines.push(Line::from(Span::styled("copy it with Shift+C, delete it with Shift+D.", hint)));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("?", key),
+            Span::styled(" lists every key", hint),
+        ]));
+
+        let height = (lines.len() as u16).min(inner.height);
+        let top = inner.y + inner.height.saturating_sub(height) / 2;
+        let centered = Rect { y: top, height, ..inner };
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), centered);
+    }
 }
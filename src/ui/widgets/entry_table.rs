@@ -1,12 +1,25 @@
 use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Rect},
     style::{Color, Modifier, Style},
     widgets::{Block, Borders, Cell, Row, Table},
     Frame,
 };
 
-use crate::vault::model::EntryMeta;
+use crate::config::model::ListColumn;
+use crate::ui::columns::{column_spec, ColumnSpec, DASHBOARD_COLUMNS};
+use crate::vault::model::{address_contains, EntryMeta};
+
+/// First and last four characters of a long address, e.g. `0xf3...2266`.
+fn shorten_middle(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let n = chars.len();
+    if n > 11 {
+        format!("{}...{}", chars[..4].iter().collect::<String>(), chars[n - 4..].iter().collect::<String>())
+    } else {
+        s.to_string()
+    }
+}
 
 pub struct EntryTable {
     entries: Vec<EntryMeta>,
@@ -14,6 +27,10 @@ pub struct EntryTable {
     filter: String,
     scroll_offset: usize,
     number_buffer: String,
+    network_glyphs: bool,
+    columns: Vec<ListColumn>,
+    /// List archived entries instead of the ones in use
+    show_archived: bool,
 }
 
 impl EntryTable {
@@ -24,9 +41,30 @@ impl EntryTable {
             filter: String::new(),
             scroll_offset: 0,
             number_buffer: String::new(),
+            network_glyphs: false,
+            columns: DASHBOARD_COLUMNS.to_vec(),
+            show_archived: false,
         }
     }
 
+    pub fn set_network_glyphs(&mut self, network_glyphs: bool) {
+        self.network_glyphs = network_glyphs;
+    }
+
+    pub fn set_columns(&mut self, columns: Vec<ListColumn>) {
+        self.columns = columns;
+    }
+
+    pub fn set_show_archived(&mut self, show_archived: bool) {
+        self.show_archived = show_archived;
+        self.selected = 0;
+    }
+
+    /// True when the vault has no entries at all, regardless of the filter.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
     pub fn selected_index(&self) -> Option<usize> {
         let filtered = self.filtered_entries();
         if filtered.is_empty() {
@@ -36,6 +74,32 @@ impl EntryTable {
         }
     }
 
+    /// Index of the entry shown just above (`up`) or below the selection, if any.
+    pub fn neighbor_index(&self, up: bool) -> Option<usize> {
+        let filtered = self.filtered_entries();
+        let pos = if up {
+            self.selected.checked_sub(1)?
+        } else {
+            self.selected + 1
+        };
+        filtered.get(pos).map(|(i, _)| *i)
+    }
+
+    /// Replace the listed entries, keeping the filter, and select the entry at `index`.
+    pub fn set_entries(&mut self, entries: Vec<EntryMeta>, index: usize) {
+        self.entries = entries;
+        self.select_index(index);
+    }
+
+    /// Select the entry at vault `index`, or the first one if it is not listed.
+    pub fn select_index(&mut self, index: usize) {
+        self.selected = self
+            .filtered_entries()
+            .iter()
+            .position(|(i, _)| *i == index)
+            .unwrap_or(0);
+    }
+
     pub fn filter_text(&self) -> &str {
         &self.filter
     }
@@ -48,6 +112,21 @@ impl EntryTable {
         &self.number_buffer
     }
 
+    /// Select the first listed entry whose name starts with `prefix`, ignoring
+    /// case. Returns false and leaves the selection alone when none does.
+    pub fn select_name_prefix(&mut self, prefix: &str) -> bool {
+        let prefix = prefix.to_lowercase();
+        let found = self
+            .filtered_entries()
+            .iter()
+            .position(|(_, e)| e.name.to_lowercase().starts_with(&prefix));
+        if let Some(pos) = found {
+            self.number_buffer.clear();
+            self.selected = pos;
+        }
+        found.is_some()
+    }
+
     pub fn set_filter(&mut self, filter: String) {
         self.filter = filter;
         self.selected = 0;
@@ -108,16 +187,22 @@ impl EntryTable {
     }
 
     fn filtered_entries(&self) -> Vec<(usize, &EntryMeta)> {
+        let listed = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.archived == self.show_archived);
         if self.filter.is_empty() {
-            self.entries.iter().enumerate().collect()
+            listed.collect()
         } else {
             let filter_lower = self.filter.to_lowercase();
-            self.entries
-                .iter()
-                .enumerate()
+            listed
                 .filter(|(_, e)| {
                     e.name.to_lowercase().contains(&filter_lower)
                         || e.network.to_lowercase().contains(&filter_lower)
+                        || e.public_address
+                            .as_deref()
+                            .is_some_and(|a| address_contains(a, &self.filter))
                 })
                 .collect()
         }
@@ -129,13 +214,13 @@ impl EntryTable {
         if filtered.is_empty() {
             let block = Block::default()
                 .borders(Borders::ALL)
-                .title(" Entries ")
+                .title(self.title())
                 .border_style(Style::default().fg(Color::Cyan));
 
-            let empty_msg = if self.filter.is_empty() {
-                "No entries yet. Press 'a' to add one."
-            } else {
-                "No entries match filter."
+            let empty_msg = match (self.filter.is_empty(), self.show_archived) {
+                (true, true) => "No archived entries. Tab returns to the entries in use.",
+                (true, false) => "Every entry is archived. Tab lists them, Shift+A adds a new one.",
+                (false, _) => "No entries match filter.",
             };
 
             let empty = ratatui::widgets::Paragraph::new(empty_msg)
@@ -146,36 +231,28 @@ impl EntryTable {
             return;
         }
 
-        let header_cells = ["#", "Name", "Type", "Network", "Public Address"]
-            .iter()
-            .map(|h| Cell::from(*h).style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+        let specs: Vec<(ListColumn, ColumnSpec)> =
+            self.columns.iter().map(|c| (*c, column_spec(*c))).collect();
+
+        let header_cells = std::iter::once("#")
+            .chain(specs.iter().map(|(_, spec)| spec.header))
+            .map(|h| Cell::from(h).style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
         let header = Row::new(header_cells).height(1);
 
         let rows = filtered.iter().enumerate().map(|(idx, (_original_idx, entry))| {
             let display_num = idx + 1;
-            let address_display = entry.public_address.as_ref()
-                .or(entry.username.as_ref())
-                .map(|s| {
-                    if s.chars().count() > 11 {
-                        let chars: Vec<char> = s.chars().collect();
-                        let n = chars.len();
-                        format!("{}...{}", chars[..4].iter().collect::<String>(), chars[n - 4..].iter().collect::<String>())
-                    } else {
-                        s.clone()
-                    }
-                })
-                .unwrap_or_else(|| String::from(""));
-
-            let lock_indicator = if entry.has_secondary_password { " [locked]" } else { "" };
-            let name_display = format!("{}{}", entry.name, lock_indicator);
 
-            let cells = vec![
-                Cell::from(display_num.to_string()),
-                Cell::from(name_display),
-                Cell::from(entry.secret_type.to_string()),
-                Cell::from(entry.network.clone()),
-                Cell::from(address_display),
-            ];
+            let cells = std::iter::once(Cell::from(display_num.to_string())).chain(specs.iter().map(
+                |(column, spec)| {
+                    let value = (spec.value)(entry, self.network_glyphs);
+                    let value = match column {
+                        ListColumn::Name if entry.has_secondary_password => format!("{} [locked]", value),
+                        ListColumn::Address | ListColumn::AddressOrUrl => shorten_middle(&value),
+                        _ => value,
+                    };
+                    Cell::from(value)
+                },
+            ));
 
             let style = if idx == self.selected {
                 Style::default()
@@ -189,24 +266,28 @@ impl EntryTable {
             Row::new(cells).style(style)
         });
 
-        let widths = [
-            ratatui::layout::Constraint::Length(4),
-            ratatui::layout::Constraint::Percentage(30),
-            ratatui::layout::Constraint::Percentage(20),
-            ratatui::layout::Constraint::Percentage(20),
-            ratatui::layout::Constraint::Percentage(30),
-        ];
+        let widths: Vec<Constraint> = std::iter::once(Constraint::Length(4))
+            .chain(specs.iter().map(|(_, spec)| Constraint::Fill(spec.weight)))
+            .collect();
 
         let table = Table::new(rows, widths)
             .header(header)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Entries ")
+                    .title(self.title())
                     .border_style(Style::default().fg(Color::Cyan)),
             )
             .column_spacing(1);
 
         frame.render_widget(table, area);
     }
+
+    fn title(&self) -> &'static str {
+        if self.show_archived {
+            " Archived Entries "
+        } else {
+            " Entries "
+        }
+    }
 }
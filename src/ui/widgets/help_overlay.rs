@@ -0,0 +1,70 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Popup listing the keys of the screen underneath it.
+pub struct HelpOverlay {
+    title: &'static str,
+    keys: &'static [(&'static str, &'static str)],
+}
+
+impl HelpOverlay {
+    pub fn new(title: &'static str, keys: &'static [(&'static str, &'static str)]) -> Self {
+        Self { title, keys }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let key_width = self.keys.iter().map(|(k, _)| k.width()).max().unwrap_or(0);
+        let text_width = self
+            .keys
+            .iter()
+            .map(|(_, d)| key_width + 2 + d.width())
+            .max()
+            .unwrap_or(0)
+            .max(self.title.width() + 2);
+
+        // Key rows, a blank line and the close hint, plus borders and padding
+        let width = (text_width as u16 + 6).min(area.width);
+        let height = (self.keys.len() as u16 + 4).min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let mut lines: Vec<Line> = self
+            .keys
+            .iter()
+            .map(|(key, description)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:<width$}  ", key, width = key_width),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::styled(*description, Style::default().fg(Color::White)),
+                ])
+            })
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            " Esc/F1 to close",
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", self.title))
+            .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            .border_style(Style::default().fg(Color::Cyan));
+
+        frame.render_widget(Clear, popup);
+        frame.render_widget(Paragraph::new(lines).block(block), popup);
+    }
+}
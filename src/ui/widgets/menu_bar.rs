@@ -24,6 +24,7 @@ impl MenuBar {
                 ("⇧C", "Copy"),
                 ("⇧E", "Edit"),
                 ("⇧D", "Delete"),
+                ("⇧R", "Archive"),
                 ("⇧F", "Find"),
                 ("⇧X", "Export"),
                 ("⇧I", "Import"),
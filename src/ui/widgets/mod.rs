@@ -1,5 +1,7 @@
+pub mod custom_fields;
 pub mod dashboard;
 pub mod entry_table;
+pub mod help_overlay;
 pub mod menu_bar;
 pub mod password_field;
 pub mod status_bar;
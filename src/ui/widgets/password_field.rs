@@ -6,6 +6,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use zeroize::Zeroize;
 
 pub struct PasswordField {
     buffer: String,
@@ -44,7 +45,8 @@ impl PasswordField {
                 if self.buffer.is_empty() {
                     PasswordAction::Continue
                 } else {
-                    PasswordAction::Submit(self.buffer.clone())
+                    // Hand the password over rather than keep a copy on screen
+                    PasswordAction::Submit(std::mem::take(&mut self.buffer))
                 }
             }
             KeyCode::Esc => PasswordAction::Cancel,
@@ -85,6 +87,12 @@ impl PasswordField {
     }
 }
 
+impl Drop for PasswordField {
+    fn drop(&mut self) {
+        self.buffer.zeroize();
+    }
+}
+
 pub enum PasswordAction {
     Continue,
     Submit(String),
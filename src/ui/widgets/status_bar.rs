@@ -1,3 +1,4 @@
+use chrono::{DateTime, Local};
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
@@ -11,15 +12,26 @@ pub struct StatusBar {
     entry_count: usize,
     filter_text: String,
     number_buffer: String,
+    clipboard_status: Option<String>,
+    unlocked_at: Option<DateTime<Local>>,
 }
 
 impl StatusBar {
-    pub fn new(vault_name: &str, entry_count: usize, filter_text: &str, number_buffer: &str) -> Self {
+    pub fn new(
+        vault_name: &str,
+        entry_count: usize,
+        filter_text: &str,
+        number_buffer: &str,
+        clipboard_status: Option<&str>,
+        unlocked_at: Option<DateTime<Local>>,
+    ) -> Self {
         Self {
             vault_name: vault_name.to_string(),
             entry_count,
             filter_text: filter_text.to_string(),
             number_buffer: number_buffer.to_string(),
+            clipboard_status: clipboard_status.map(str::to_string),
+            unlocked_at,
         }
     }
 
@@ -41,7 +53,7 @@ impl StatusBar {
             self.vault_name, self.entry_count, filter_display, number_display
         );
 
-        let spans = vec![Span::styled(
+        let mut spans = vec![Span::styled(
             content,
             Style::default()
                 .fg(Color::Black)
@@ -49,6 +61,24 @@ impl StatusBar {
                 .add_modifier(Modifier::BOLD),
         )];
 
+        // Reminder that secrets are decrypted in this session
+        if let Some(unlocked_at) = self.unlocked_at {
+            spans.push(Span::styled(
+                format!(" Unlocked since {} ", unlocked_at.format("%H:%M")),
+                Style::default().fg(Color::Black).bg(Color::Green),
+            ));
+        }
+
+        if let Some(ref status) = self.clipboard_status {
+            spans.push(Span::styled(
+                format!(" {} ", status),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
         let paragraph = Paragraph::new(Line::from(spans));
         frame.render_widget(paragraph, area);
     }
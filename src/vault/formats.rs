@@ -0,0 +1,80 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::error::{CryptoKeeperError, Result};
+
+pub const ARMOR_BEGIN: &str = "-----BEGIN CRYPTOKEEPER VAULT-----";
+pub const ARMOR_END: &str = "-----END CRYPTOKEEPER VAULT-----";
+
+/// Base64 characters per armored line.
+const LINE_WIDTH: usize = 64;
+
+/// Wrap encrypted vault or backup bytes in a pasteable text block.
+pub fn armor(data: &[u8]) -> String {
+    let encoded = STANDARD.encode(data);
+    let mut text = String::with_capacity(encoded.len() + encoded.len() / LINE_WIDTH + 80);
+    text.push_str(ARMOR_BEGIN);
+    text.push('\n');
+    for line in encoded.as_bytes().chunks(LINE_WIDTH) {
+        // Base64 output is ASCII, so every chunk is valid UTF-8
+        text.push_str(std::str::from_utf8(line).unwrap_or_default());
+        text.push('\n');
+    }
+    text.push_str(ARMOR_END);
+    text.push('\n');
+    text
+}
+
+/// Whether `data` holds an armored block rather than being a binary vault
+/// file. The block may sit anywhere in the text, as `dearmor` allows.
+pub fn is_armored(data: &[u8]) -> bool {
+    data.windows(ARMOR_BEGIN.len()).any(|w| w == ARMOR_BEGIN.as_bytes())
+}
+
+/// Decode an armored block back to the encrypted bytes. Text around the
+/// block (e.g. the rest of a note it was pasted into) is ignored.
+pub fn dearmor(text: &str) -> Result<Vec<u8>> {
+    let start = text.find(ARMOR_BEGIN).ok_or(CryptoKeeperError::InvalidVaultFormat)?
+        + ARMOR_BEGIN.len();
+    let len = text[start..].find(ARMOR_END).ok_or(CryptoKeeperError::InvalidVaultFormat)?;
+    let body: String = text[start..start + len]
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    STANDARD
+        .decode(body)
+        .map_err(|_| CryptoKeeperError::InvalidVaultFormat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn armor_roundtrip() {
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let text = armor(&data);
+        assert!(text.starts_with(ARMOR_BEGIN));
+        assert!(text.trim_end().ends_with(ARMOR_END));
+        assert!(text.lines().all(|l| l.len() <= LINE_WIDTH || l.starts_with("-----")));
+        assert!(is_armored(text.as_bytes()));
+        assert_eq!(dearmor(&text).unwrap(), data);
+    }
+
+    #[test]
+    fn dearmor_ignores_surrounding_text_and_line_endings() {
+        let text = armor(b"CKPTencrypted").replace('\n', "\r\n");
+        let pasted = format!("My backup from today:\r\n\r\n  {}\r\nend of note", text);
+        assert!(is_armored(pasted.as_bytes()));
+        assert_eq!(dearmor(&pasted).unwrap(), b"CKPTencrypted");
+    }
+
+    #[test]
+    fn dearmor_rejects_damaged_blocks() {
+        assert!(dearmor("no armor here").is_err());
+        assert!(dearmor(ARMOR_BEGIN).is_err());
+        let text = format!("{}\n!!not base64!!\n{}\n", ARMOR_BEGIN, ARMOR_END);
+        assert!(dearmor(&text).is_err());
+        assert!(!is_armored(b"CKPT\x02\x00\x00\x00"));
+    }
+}
@@ -0,0 +1,121 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::{CryptoKeeperError, Result};
+
+use super::storage::{ensure_vault_dir, vault_dir};
+
+/// Advisory lock held while an interactive session has the vault open.
+/// The lock file contains the owner's PID and is removed on drop.
+pub struct VaultLock {
+    path: PathBuf,
+}
+
+impl Drop for VaultLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+pub fn lock_path() -> PathBuf {
+    vault_dir().join("vault.lock")
+}
+
+/// Take the vault lock. Fails with `VaultLocked` if another live process holds it;
+/// locks left behind by crashed processes are replaced.
+pub fn acquire() -> Result<VaultLock> {
+    ensure_vault_dir()?;
+    acquire_at(&lock_path())
+}
+
+/// PID of another live process holding the vault lock, if any.
+pub fn holder() -> Option<u32> {
+    holder_at(&lock_path())
+}
+
+fn acquire_at(path: &Path) -> Result<VaultLock> {
+    // Second attempt only happens after clearing a stale lock.
+    for _ in 0..2 {
+        match OpenOptions::new().write(true).create_new(true).open(path) {
+            Ok(mut file) => {
+                write!(file, "{}", std::process::id())?;
+                return Ok(VaultLock {
+                    path: path.to_path_buf(),
+                });
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                if let Some(pid) = holder_at(path) {
+                    return Err(CryptoKeeperError::VaultLocked(pid));
+                }
+                let _ = fs::remove_file(path);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Err(CryptoKeeperError::Io(std::io::Error::other(
+        "Could not acquire vault lock",
+    )))
+}
+
+fn holder_at(path: &Path) -> Option<u32> {
+    let pid: u32 = fs::read_to_string(path).ok()?.trim().parse().ok()?;
+    (pid != std::process::id() && pid_alive(pid)).then_some(pid)
+}
+
+#[cfg(unix)]
+fn pid_alive(pid: u32) -> bool {
+    // Signal 0 performs the permission/existence check without delivering anything.
+    let ret = unsafe { libc::kill(pid as libc::pid_t, 0) };
+    ret == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn pid_alive(_pid: u32) -> bool {
+    // No cheap liveness check; treat the lock as held.
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn acquire_and_release() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("vault.lock");
+
+        let lock = acquire_at(&path).unwrap();
+        assert!(path.exists());
+        drop(lock);
+        assert!(!path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stale_lock_is_replaced() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("vault.lock");
+        // Above the kernel's pid_max, so never a live process.
+        fs::write(&path, "999999999").unwrap();
+
+        let _lock = acquire_at(&path).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, std::process::id().to_string());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn live_lock_is_refused() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("vault.lock");
+        // PID 1 always exists on unix.
+        fs::write(&path, "1").unwrap();
+
+        match acquire_at(&path) {
+            Err(CryptoKeeperError::VaultLocked(1)) => {}
+            _ => panic!("expected VaultLocked(1)"),
+        }
+    }
+}
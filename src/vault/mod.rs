@@ -1,2 +1,5 @@
+pub mod formats;
+pub mod lock;
 pub mod model;
 pub mod storage;
+pub mod store;
@@ -1,7 +1,11 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
-use zeroize::Zeroize;
+use unicode_normalization::UnicodeNormalization;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::crypto::kdf::KdfParams;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum SecretType {
@@ -20,6 +24,27 @@ impl fmt::Display for SecretType {
     }
 }
 
+/// A user-defined label/value pair stored alongside an entry (e.g. "PIN").
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CustomField {
+    pub name: String,
+    pub value: String,
+    /// Masked like the secret until revealed (e.g. a PIN)
+    #[serde(default)]
+    pub sensitive: bool,
+}
+
+impl fmt::Debug for CustomField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value: &dyn fmt::Debug = if self.sensitive { &"[REDACTED]" } else { &self.value };
+        f.debug_struct("CustomField")
+            .field("name", &self.name)
+            .field("value", value)
+            .field("sensitive", &self.sensitive)
+            .finish()
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Entry {
     pub name: String,
@@ -28,11 +53,22 @@ pub struct Entry {
     pub network: String,
     #[serde(default)]
     pub public_address: Option<String>,
+    /// The address was typed in by the user rather than derived from the secret
+    #[serde(default)]
+    pub address_is_manual: bool,
+    /// BIP44 account a seed phrase's address is derived at (0: the default)
+    #[serde(default)]
+    pub derivation_account: u32,
+    /// Address index within `derivation_account` (0: the first address)
+    #[serde(default)]
+    pub derivation_index: u32,
     #[serde(default)]
     pub username: Option<String>,
     #[serde(default)]
     pub url: Option<String>,
     pub notes: String,
+    #[serde(default)]
+    pub custom_fields: Vec<CustomField>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 
@@ -45,15 +81,34 @@ pub struct Entry {
     pub entry_key_nonce: Option<Vec<u8>>,
     #[serde(default)]
     pub entry_key_salt: Option<Vec<u8>>,
+    /// Argon2 cost the entry key was wrapped with (None: `KdfParams::ENTRY_KEY_DEFAULT`)
+    #[serde(default)]
+    pub entry_key_kdf: Option<KdfParams>,
     #[serde(default)]
     pub encrypted_secret: Option<Vec<u8>>,
     #[serde(default)]
     pub encrypted_secret_nonce: Option<Vec<u8>>,
+    /// Hide the name and address in listings until the secondary password is
+    /// entered. The plaintext metadata block never holds them.
+    #[serde(default)]
+    pub mask_metadata: bool,
+
+    /// Seconds this entry's secret stays on the clipboard, overriding
+    /// `clipboard_timeout_secs` (None: use the config)
+    #[serde(default)]
+    pub clipboard_timeout_override: Option<u64>,
+
+    /// Kept but no longer in use: left out of the default list and search
+    #[serde(default)]
+    pub archived: bool,
 }
 
 impl Drop for Entry {
     fn drop(&mut self) {
         self.secret.zeroize();
+        for field in &mut self.custom_fields {
+            field.value.zeroize();
+        }
         if let Some(ref mut wrapped) = self.entry_key_wrapped {
             wrapped.zeroize();
         }
@@ -74,13 +129,152 @@ impl fmt::Debug for Entry {
             .field("username", &self.username)
             .field("url", &self.url)
             .field("notes", &self.notes)
+            .field(
+                "custom_fields",
+                &self.custom_fields.iter().map(|f| &f.name).collect::<Vec<_>>(),
+            )
             .field("created_at", &self.created_at)
             .field("updated_at", &self.updated_at)
             .field("has_secondary_password", &self.has_secondary_password)
+            .field("mask_metadata", &self.mask_metadata)
+            .field("archived", &self.archived)
             .finish()
     }
 }
 
+impl Entry {
+    /// Name to show where no secondary password has been entered: masked
+    /// entries are listed as `MASKED_NAME`.
+    pub fn listed_name(&self) -> &str {
+        if self.mask_metadata {
+            MASKED_NAME
+        } else {
+            &self.name
+        }
+    }
+
+    /// Field-by-field changes from `self` to `other`, for confirming an edit.
+    /// The secret and sensitive custom fields are reported without values.
+    pub fn diff(&self, other: &Entry) -> Vec<FieldChange> {
+        let mut changes = vec![];
+        let mut compare = |field: &str, old: &str, new: &str| {
+            if old != new {
+                changes.push(FieldChange::value(field, old, new));
+            }
+        };
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+
+        compare("name", &self.name, &other.name);
+        compare("type", &self.secret_type.to_string(), &other.secret_type.to_string());
+        compare("network", &self.network, &other.network);
+        compare("address", &optional(&self.public_address), &optional(&other.public_address));
+        compare("username", &optional(&self.username), &optional(&other.username));
+        compare("url", &optional(&self.url), &optional(&other.url));
+        compare("notes", &self.notes, &other.notes);
+        let timeout = |t: Option<u64>| t.map_or("default".to_string(), |t| format!("{}s", t));
+        compare(
+            "clipboard timeout",
+            &timeout(self.clipboard_timeout_override),
+            &timeout(other.clipboard_timeout_override),
+        );
+        if self.secret != other.secret {
+            changes.push(FieldChange::hidden("secret"));
+        }
+
+        for new in &other.custom_fields {
+            let old = self.custom_fields.iter().find(|f| f.name == new.name);
+            if old.is_some_and(|old| old == new) {
+                continue;
+            }
+            let field = format!("field '{}'", new.name);
+            changes.push(if new.sensitive || old.is_some_and(|old| old.sensitive) {
+                FieldChange::hidden(&field)
+            } else {
+                FieldChange::value(&field, old.map_or("", |old| old.value.as_str()), &new.value)
+            });
+        }
+        for old in &self.custom_fields {
+            if !other.custom_fields.iter().any(|f| f.name == old.name) {
+                changes.push(FieldChange::removed(&format!("field '{}'", old.name)));
+            }
+        }
+        changes
+    }
+}
+
+/// One field that differs between two versions of an entry, from `Entry::diff`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub change: Change,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Old and new values, safe to display
+    Value { old: String, new: String },
+    /// A secret value changed; the values are never carried
+    Hidden,
+    Removed,
+}
+
+impl FieldChange {
+    fn value(field: &str, old: &str, new: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            change: Change::Value {
+                old: old.to_string(),
+                new: new.to_string(),
+            },
+        }
+    }
+
+    fn hidden(field: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            change: Change::Hidden,
+        }
+    }
+
+    fn removed(field: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            change: Change::Removed,
+        }
+    }
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.change {
+            Change::Value { old, new } => write!(
+                f,
+                "{}: {} \u{2192} {}",
+                self.field,
+                summarize(old),
+                summarize(new)
+            ),
+            Change::Hidden => write!(f, "{}: changed", self.field),
+            Change::Removed => write!(f, "{}: removed", self.field),
+        }
+    }
+}
+
+/// Value shortened to one line for a change summary.
+fn summarize(value: &str) -> String {
+    const MAX: usize = 40;
+    let value = value.trim();
+    if value.is_empty() {
+        return "(empty)".to_string();
+    }
+    let mut line: String = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() > MAX {
+        line = line.chars().take(MAX - 1).collect();
+        line.push('\u{2026}');
+    }
+    line
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EntryMeta {
     pub name: String,
@@ -95,6 +289,27 @@ pub struct EntryMeta {
     pub notes: String,
     #[serde(default)]
     pub has_secondary_password: bool,
+    #[serde(default)]
+    pub mask_metadata: bool,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// Shown in place of the name of a masked entry.
+pub const MASKED_NAME: &str = "\u{2022}\u{2022}\u{2022}\u{2022}\u{2022}\u{2022}";
+
+impl EntryMeta {
+    /// The listing of a masked entry: only its type and network remain.
+    fn masked(self) -> Self {
+        Self {
+            name: MASKED_NAME.to_string(),
+            public_address: None,
+            username: None,
+            url: None,
+            notes: String::new(),
+            ..self
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,13 +327,13 @@ impl VaultData {
     }
 
     pub fn find_entry(&self, name: &str) -> Option<&Entry> {
-        let name_lower = name.to_lowercase();
-        self.entries.iter().find(|e| e.name.to_lowercase() == name_lower)
+        let key = name_key(name);
+        self.entries.iter().find(|e| name_key(&e.name) == key)
     }
 
     pub fn remove_entry(&mut self, name: &str) -> Option<Entry> {
-        let name_lower = name.to_lowercase();
-        if let Some(pos) = self.entries.iter().position(|e| e.name.to_lowercase() == name_lower) {
+        let key = name_key(name);
+        if let Some(pos) = self.entries.iter().position(|e| name_key(&e.name) == key) {
             Some(self.entries.remove(pos))
         } else {
             None
@@ -129,15 +344,51 @@ impl VaultData {
         self.find_entry(name).is_some()
     }
 
-    /// Resolve an identifier to a 0-based index: try 1-based numeric index first, then name match.
+    /// Resolve an identifier to a 0-based index: try 1-based numeric index first, then name
+    /// match, then public address.
     fn resolve_index(&self, id: &str) -> Option<usize> {
         if let Ok(n) = id.parse::<usize>() {
             if n >= 1 && n <= self.entries.len() {
                 return Some(n - 1);
             }
         }
-        let id_lower = id.to_lowercase();
-        self.entries.iter().position(|e| e.name.to_lowercase() == id_lower)
+        let key = name_key(id);
+        self.entries
+            .iter()
+            .position(|e| name_key(&e.name) == key)
+            .or_else(|| self.address_index(id))
+    }
+
+    /// When `id` is a valid 1-based index and also the name of a different
+    /// entry, the 0-based positions of the entry at that index and of the one
+    /// with that name. `resolve_index` takes the index; callers that can ask
+    /// should let the user choose (see `commands::resolve_id`).
+    pub fn index_name_collision(&self, id: &str) -> Option<(usize, usize)> {
+        let n = id.parse::<usize>().ok()?;
+        if n < 1 || n > self.entries.len() {
+            return None;
+        }
+        let key = name_key(id);
+        let named = self.entries.iter().position(|e| name_key(&e.name) == key)?;
+        (named != n - 1).then_some((n - 1, named))
+    }
+
+    /// Index of the entry whose public address matches, ignoring case and any 0x prefix.
+    fn address_index(&self, address: &str) -> Option<usize> {
+        let wanted = normalize_address(address);
+        if wanted.is_empty() {
+            return None;
+        }
+        self.entries.iter().position(|e| {
+            e.public_address
+                .as_deref()
+                .is_some_and(|a| normalize_address(a) == wanted)
+        })
+    }
+
+    /// Entry whose public address matches `address`, ignoring case and any 0x prefix.
+    pub fn find_by_address(&self, address: &str) -> Option<&Entry> {
+        self.address_index(address).map(|i| &self.entries[i])
     }
 
     pub fn find_entry_by_id(&self, id: &str) -> Option<&Entry> {
@@ -157,10 +408,125 @@ impl VaultData {
         self.resolve_index(id).map(|i| self.entries[i].name.clone())
     }
 
+    /// A vault holding only the identified entry, for exporting it on its own.
+    /// The entry is cloned as stored, so secondary-password entries keep their
+    /// wrapped key and stay protected in the export.
+    pub fn single_entry_vault(&self, id: &str) -> Option<VaultData> {
+        let entry = self.find_entry_by_id(id)?.clone();
+        Some(VaultData {
+            entries: vec![entry],
+            version: self.version,
+        })
+    }
+
+    /// Move the entry at `from` to position `to`, shifting the entries between them.
+    /// Returns false if either index is out of range.
+    ///
+    /// The stored order is the base order everywhere entries are listed and numbered;
+    /// any pinning (e.g. favorites) is applied on top of it when displaying.
+    pub fn move_entry(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.entries.len() || to >= self.entries.len() {
+            return false;
+        }
+        let entry = self.entries.remove(from);
+        self.entries.insert(to, entry);
+        true
+    }
+
+    /// What a master password change touches, e.g. "3 entries will be re-encrypted;
+    /// 1 secondary-password entry is unaffected." Secondary-password entries keep
+    /// their own key wrapping.
+    pub fn password_change_summary(&self) -> String {
+        let secondary = self.entries.iter().filter(|e| e.has_secondary_password).count();
+        let reencrypted = self.entries.len() - secondary;
+        let plural = |n: usize| if n == 1 { "entry" } else { "entries" };
+        let mut summary = format!(
+            "{} {} will be re-encrypted",
+            reencrypted,
+            plural(reencrypted)
+        );
+        if secondary > 0 {
+            summary.push_str(&format!(
+                "; {} secondary-password {} {} unaffected",
+                secondary,
+                plural(secondary),
+                if secondary == 1 { "is" } else { "are" }
+            ));
+        }
+        summary.push('.');
+        summary
+    }
+
+    /// Indices of entries whose secret or a custom field name or value contains
+    /// `query`, ignoring case. Secondary-password entries are skipped: their
+    /// secrets stay encrypted until their own password is given. Archived
+    /// entries are skipped too.
+    pub fn search_secrets(&self, query: &str) -> Vec<usize> {
+        let query = Zeroizing::new(query.to_lowercase());
+        let contains = |text: &str| Zeroizing::new(text.to_lowercase()).contains(query.as_str());
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| !e.has_secondary_password && !e.archived)
+            .filter(|(_, e)| {
+                contains(&e.secret)
+                    || e.custom_fields
+                        .iter()
+                        .any(|f| contains(&f.name) || contains(&f.value))
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Summary counts for the stats command.
+    pub fn stats(&self) -> VaultStats {
+        let mut stats = VaultStats {
+            total: self.entries.len(),
+            ..VaultStats::default()
+        };
+        let mut by_network: BTreeMap<String, usize> = BTreeMap::new();
+
+        for entry in &self.entries {
+            match entry.secret_type {
+                SecretType::PrivateKey => stats.private_keys += 1,
+                SecretType::SeedPhrase => stats.seed_phrases += 1,
+                SecretType::Password => stats.passwords += 1,
+            }
+            if entry.secret_type != SecretType::Password {
+                *by_network.entry(entry.network.clone()).or_default() += 1;
+            }
+            if entry.has_secondary_password {
+                stats.with_secondary_password += 1;
+            }
+            if entry.public_address.is_some() {
+                stats.with_address += 1;
+            }
+            stats.notes_bytes += entry.notes.len();
+
+            if stats.oldest.as_ref().is_none_or(|(_, t)| entry.created_at < *t) {
+                stats.oldest = Some((entry.listed_name().to_string(), entry.created_at));
+            }
+            if stats.newest.as_ref().is_none_or(|(_, t)| entry.created_at > *t) {
+                stats.newest = Some((entry.listed_name().to_string(), entry.created_at));
+            }
+        }
+
+        stats.by_network = by_network.into_iter().collect();
+        stats
+    }
+
+    /// Listing of every entry, with masked entries hidden. This is what the
+    /// plaintext metadata block stores.
     pub fn metadata(&self) -> Vec<EntryMeta> {
+        self.metadata_revealing(|_| false)
+    }
+
+    /// Like `metadata`, but masked entries for which `revealed` holds are listed
+    /// in full.
+    pub fn metadata_revealing(&self, revealed: impl Fn(&Entry) -> bool) -> Vec<EntryMeta> {
         self.entries
             .iter()
-            .map(|e| EntryMeta {
+            .map(|e| (e, EntryMeta {
                 name: e.name.clone(),
                 network: e.network.clone(),
                 secret_type: e.secret_type.clone(),
@@ -169,21 +535,88 @@ impl VaultData {
                 url: e.url.clone(),
                 notes: e.notes.clone(),
                 has_secondary_password: e.has_secondary_password,
-            })
+                mask_metadata: e.mask_metadata,
+                archived: e.archived,
+            }))
+            .map(|(e, meta)| if e.mask_metadata && !revealed(e) { meta.masked() } else { meta })
             .collect()
     }
 }
 
+/// Canonical form of an entry name: Unicode NFC, trimmed, with each run of
+/// whitespace collapsed to a single space. Applied whenever a name is set.
+pub fn normalize_name(name: &str) -> String {
+    let composed: String = name.nfc().collect();
+    composed.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether two names identify the same entry: equal once normalized, ignoring case.
+pub fn names_match(a: &str, b: &str) -> bool {
+    name_key(a) == name_key(b)
+}
+
+/// Names are compared by this key, so entries saved before normalization
+/// (e.g. with a double space) still match their canonical spelling.
+fn name_key(name: &str) -> String {
+    normalize_name(name).to_lowercase()
+}
+
+/// Address lowercased with any 0x prefix removed, for comparisons.
+fn normalize_address(address: &str) -> String {
+    let address = address.trim();
+    address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+        .unwrap_or(address)
+        .to_lowercase()
+}
+
+/// Whether `address` contains `query`, ignoring case and any 0x prefix on either.
+pub fn address_contains(address: &str, query: &str) -> bool {
+    let query = normalize_address(query);
+    !query.is_empty() && normalize_address(address).contains(&query)
+}
+
+/// Overview of a vault's contents, computed by `VaultData::stats`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VaultStats {
+    pub total: usize,
+    pub private_keys: usize,
+    pub seed_phrases: usize,
+    pub passwords: usize,
+    /// Crypto entries per network, sorted by network name
+    pub by_network: Vec<(String, usize)>,
+    pub with_secondary_password: usize,
+    pub with_address: usize,
+    /// Name and creation time of the oldest entry
+    pub oldest: Option<(String, DateTime<Utc>)>,
+    /// Name and creation time of the newest entry
+    pub newest: Option<(String, DateTime<Utc>)>,
+    /// Total size of all notes in bytes
+    pub notes_bytes: usize,
+}
+
 pub struct VaultHeader;
 
 impl VaultHeader {
     pub const MAGIC: &'static [u8; 4] = b"CKPR";
     pub const FORMAT_VERSION_V1: u32 = 1;
+    /// V1 plus a plaintext metadata block (4-byte length + JSON) after the version
     pub const FORMAT_VERSION_V2: u32 = 2;
+    /// V2 plus the Argon2 variant and version bytes after p_cost; earlier
+    /// versions imply `KdfAlgorithm::LEGACY` (Argon2id v0x13)
+    pub const FORMAT_VERSION_V3: u32 = 3;
     /// V1: 4 (magic) + 4 (version) + 32 (salt) + 4 (m_cost) + 4 (t_cost) + 4 (p_cost) + 24 (nonce) + 4 (ct_len) = 80
     pub const HEADER_SIZE_V1: usize = 80;
+
+    /// Whether files of this version start with the plaintext metadata block.
+    pub fn has_metadata(version: u32) -> bool {
+        version == Self::FORMAT_VERSION_V2 || version == Self::FORMAT_VERSION_V3
+    }
 }
 
+/// Backups share the vault layout and versions. Since V3 they carry an empty
+/// metadata block, so only the Argon2 variant is added to V1.
 pub struct BackupHeader;
 
 impl BackupHeader {
@@ -202,17 +635,25 @@ mod tests {
             secret_type: SecretType::PrivateKey,
             network: "Ethereum".to_string(),
             public_address: None,
+            address_is_manual: false,
+            derivation_account: 0,
+            derivation_index: 0,
             username: None,
             url: None,
             notes: String::new(),
+            custom_fields: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
             has_secondary_password: false,
             entry_key_wrapped: None,
             entry_key_nonce: None,
             entry_key_salt: None,
+            entry_key_kdf: None,
             encrypted_secret: None,
             encrypted_secret_nonce: None,
+            mask_metadata: false,
+            clipboard_timeout_override: None,
+            archived: false,
         }
     }
 
@@ -296,13 +737,366 @@ mod tests {
         assert_eq!(vault.entries[0].name, "New");
     }
 
+    #[test]
+    fn normalize_name_trims_collapses_and_composes() {
+        assert_eq!(normalize_name("  My \t Wallet  "), "My Wallet");
+        // "e" + combining acute accent (NFD) becomes the single NFC character
+        assert_eq!(normalize_name("Cafe\u{301}"), "Caf\u{e9}");
+        assert_eq!(normalize_name(" \n "), "");
+    }
+
+    #[test]
+    fn equivalent_names_resolve_to_one_entry() {
+        // Saved before names were normalized
+        let vault = make_vault(&["My  Wallet", "Caf\u{e9}"]);
+        assert!(vault.has_entry("my wallet"));
+        assert!(vault.has_entry(" MY WALLET "));
+        assert_eq!(vault.resolve_entry_name("my wallet").as_deref(), Some("My  Wallet"));
+        assert_eq!(vault.resolve_entry_name("CAFE\u{301}").as_deref(), Some("Caf\u{e9}"));
+        assert!(names_match("Cafe\u{301}", "caf\u{e9}"));
+        assert!(!vault.has_entry("MyWallet"));
+    }
+
+    #[test]
+    fn single_entry_vault_keeps_protection() {
+        let mut vault = make_vault(&["Alpha", "Beta"]);
+        let beta = &mut vault.entries[1];
+        beta.secret = String::new();
+        beta.has_secondary_password = true;
+        beta.entry_key_wrapped = Some(vec![1, 2, 3]);
+        beta.encrypted_secret = Some(vec![4, 5, 6]);
+
+        let single = vault.single_entry_vault("beta").unwrap();
+        assert_eq!(single.entries.len(), 1);
+        let entry = &single.entries[0];
+        assert_eq!(entry.name, "Beta");
+        assert!(entry.has_secondary_password);
+        assert_eq!(entry.entry_key_wrapped, Some(vec![1, 2, 3]));
+        assert_eq!(entry.encrypted_secret, Some(vec![4, 5, 6]));
+        assert!(entry.secret.is_empty());
+
+        assert!(vault.single_entry_vault("Gamma").is_none());
+    }
+
+    #[test]
+    fn stats_summarize_vault() {
+        use chrono::Duration;
+
+        let mut vault = make_vault(&["Eth Key", "Btc Seed", "Email", "Sol Key"]);
+        let now = Utc::now();
+        vault.entries[0].created_at = now - Duration::days(3);
+        vault.entries[0].public_address = Some("0xabc".to_string());
+        vault.entries[0].notes = "cold".to_string();
+        vault.entries[1].secret_type = SecretType::SeedPhrase;
+        vault.entries[1].network = "Bitcoin".to_string();
+        vault.entries[1].has_secondary_password = true;
+        vault.entries[1].created_at = now - Duration::days(1);
+        vault.entries[2].secret_type = SecretType::Password;
+        vault.entries[2].network = String::new();
+        vault.entries[2].notes = "work".to_string();
+        vault.entries[2].created_at = now;
+        vault.entries[3].network = "Solana".to_string();
+        vault.entries[3].created_at = now - Duration::days(2);
+
+        let stats = vault.stats();
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.private_keys, 2);
+        assert_eq!(stats.seed_phrases, 1);
+        assert_eq!(stats.passwords, 1);
+        assert_eq!(
+            stats.by_network,
+            vec![
+                ("Bitcoin".to_string(), 1),
+                ("Ethereum".to_string(), 1),
+                ("Solana".to_string(), 1),
+            ]
+        );
+        assert_eq!(stats.with_secondary_password, 1);
+        assert_eq!(stats.with_address, 1);
+        assert_eq!(stats.oldest.unwrap().0, "Eth Key");
+        assert_eq!(stats.newest.unwrap().0, "Email");
+        assert_eq!(stats.notes_bytes, 8);
+
+        // A masked entry is counted but not named
+        vault.entries[2].mask_metadata = true;
+        let stats = vault.stats();
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.newest.unwrap().0, MASKED_NAME);
+    }
+
+    #[test]
+    fn stats_empty_vault() {
+        let stats = VaultData::new().stats();
+        assert_eq!(stats, VaultStats::default());
+    }
+
     #[test]
     fn numeric_name_index_wins() {
-        // Entry named "2" at position 0 (index 1). Looking up "2" should get index 2 (position 1).
+        // Entry named "2" at position 0 (index 1). Looking up "2" is ambiguous:
+        // index 2 is "other" (position 1) and the entry named "2" is position 0.
         let vault = make_vault(&["2", "other"]);
-        // "2" as index resolves to position 1 (0-based), which is "other"
+        assert_eq!(vault.index_name_collision("2"), Some((1, 0)));
+        // Without a choice, resolution still takes the index
         assert_eq!(vault.find_entry_by_id("2").unwrap().name, "other");
-        // To access the entry named "2", the user could use index "1"
+        // The entry named "2" is reachable by its own index, which is unambiguous
+        assert_eq!(vault.index_name_collision("1"), None);
         assert_eq!(vault.find_entry_by_id("1").unwrap().name, "2");
+        // A numeric name at its own index, or out of range, is no collision
+        assert_eq!(make_vault(&["1", "other"]).index_name_collision("1"), None);
+        assert_eq!(vault.index_name_collision("3"), None);
+    }
+
+    #[test]
+    fn custom_fields_default_and_roundtrip() {
+        // Entries saved before custom fields existed have no such key
+        let mut json = serde_json::to_value(make_entry("Old")).unwrap();
+        json.as_object_mut().unwrap().remove("custom_fields");
+        let old: Entry = serde_json::from_value(json).unwrap();
+        assert!(old.custom_fields.is_empty());
+
+        let mut entry = make_entry("New");
+        entry.custom_fields.push(CustomField {
+            name: "PIN".to_string(),
+            value: "1234".to_string(),
+            sensitive: true,
+        });
+        let loaded: Entry = serde_json::from_str(&serde_json::to_string(&entry).unwrap()).unwrap();
+        assert_eq!(loaded.custom_fields, entry.custom_fields);
+    }
+
+    #[test]
+    fn sensitive_custom_fields_are_redacted_in_debug() {
+        let mut field = CustomField {
+            name: "PIN".to_string(),
+            value: "1234".to_string(),
+            sensitive: true,
+        };
+        let shown = format!("{:?}", field);
+        assert!(shown.contains("PIN") && !shown.contains("1234"));
+
+        field.sensitive = false;
+        assert!(format!("{:?}", field).contains("1234"));
+    }
+
+    #[test]
+    fn metadata_carries_list_fields() {
+        let mut vault = make_vault(&["Exchange"]);
+        let entry = &mut vault.entries[0];
+        entry.public_address = Some("0xabc".to_string());
+        entry.username = Some("satoshi".to_string());
+        entry.url = Some("https://example.com".to_string());
+        entry.has_secondary_password = true;
+
+        let json = serde_json::to_string(&vault.metadata()).unwrap();
+        let meta: Vec<EntryMeta> = serde_json::from_str(&json).unwrap();
+        assert_eq!(meta[0].public_address.as_deref(), Some("0xabc"));
+        assert_eq!(meta[0].username.as_deref(), Some("satoshi"));
+        assert_eq!(meta[0].url.as_deref(), Some("https://example.com"));
+        assert!(meta[0].has_secondary_password);
+        assert!(!json.contains("secret\""));
+
+        // Metadata written before these fields existed still loads
+        let old: Vec<EntryMeta> = serde_json::from_str(
+            r#"[{"name":"Old","network":"Ethereum","secret_type":"PrivateKey","notes":""}]"#,
+        )
+        .unwrap();
+        assert_eq!(old[0].username, None);
+        assert_eq!(old[0].url, None);
+        assert_eq!(old[0].public_address, None);
+        assert!(!old[0].has_secondary_password);
+    }
+
+    #[test]
+    fn masked_entries_are_hidden_until_revealed() {
+        let mut vault = make_vault(&["Public", "Private"]);
+        let entry = &mut vault.entries[1];
+        entry.public_address = Some("0xabc".to_string());
+        entry.notes = "cold storage".to_string();
+        entry.has_secondary_password = true;
+        entry.mask_metadata = true;
+
+        let json = serde_json::to_string(&vault.metadata()).unwrap();
+        assert!(!json.contains("\"Private\"") && !json.contains("0xabc") && !json.contains("cold"));
+        let meta: Vec<EntryMeta> = serde_json::from_str(&json).unwrap();
+        assert_eq!(meta[0].name, "Public");
+        assert_eq!(meta[1].name, MASKED_NAME);
+        assert!(meta[1].mask_metadata);
+        assert_eq!(meta[1].network, "Ethereum");
+
+        let revealed = vault.metadata_revealing(|e| e.name == "Private");
+        assert_eq!(revealed[1].name, "Private");
+        assert_eq!(revealed[1].public_address.as_deref(), Some("0xabc"));
+    }
+
+    #[test]
+    fn archived_entries_are_listed_but_not_searched() {
+        let mut vault = make_vault(&["Old Exchange", "Hot Wallet"]);
+        vault.entries[0].archived = true;
+
+        let meta = vault.metadata();
+        assert!(meta[0].archived && !meta[1].archived);
+        assert_eq!(vault.search_secrets("secret"), vec![1]);
+
+        let loaded: Entry = serde_json::from_str(&serde_json::to_string(&vault.entries[0]).unwrap()).unwrap();
+        assert!(loaded.archived);
+    }
+
+    fn make_vault_with_address(address: &str) -> VaultData {
+        let mut vault = make_vault(&["Hot", "Cold"]);
+        vault.entries[1].public_address = Some(address.to_string());
+        vault
+    }
+
+    #[test]
+    fn resolve_by_address_ignores_case_and_prefix() {
+        let vault = make_vault_with_address("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+        for query in [
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+            "0XF39FD6E51AAD88F6F4CE6AB8827279CFFFB92266",
+            "f39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+        ] {
+            assert_eq!(vault.find_entry_by_id(query).unwrap().name, "Cold", "{}", query);
+        }
+        assert!(vault.find_entry_by_id("0x").is_none());
+        assert!(vault.find_entry_by_id("0xf39fd6").is_none());
+    }
+
+    #[test]
+    fn resolve_by_address_without_prefix_in_vault() {
+        let vault = make_vault_with_address("f39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+        assert_eq!(
+            vault.find_entry_by_id("0xF39FD6E51AAD88F6F4CE6AB8827279CFFFB92266").unwrap().name,
+            "Cold"
+        );
+    }
+
+    #[test]
+    fn find_by_address_names_the_existing_entry() {
+        let vault = make_vault_with_address("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+        let found = vault.find_by_address("F39FD6E51AAD88F6F4CE6AB8827279CFFFB92266");
+        assert_eq!(found.map(|e| e.name.as_str()), Some("Cold"));
+        assert!(vault.find_by_address("0x1234").is_none());
+        assert!(vault.find_by_address("").is_none());
+    }
+
+    #[test]
+    fn resolve_by_address_after_name() {
+        let vault = make_vault_with_address("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq");
+        assert_eq!(
+            vault.find_entry_by_id("BC1QAR0SRRR7XFKVY5L643LYDNW9RE59GTZZWF5MDQ").unwrap().name,
+            "Cold"
+        );
+        assert_eq!(vault.find_entry_by_id("hot").unwrap().name, "Hot");
+        assert!(vault.find_entry_by_id("bc1qunknown").is_none());
+    }
+
+    #[test]
+    fn address_contains_partial() {
+        assert!(address_contains("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266", "0xF39F"));
+        assert!(address_contains("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266", "b92266"));
+        assert!(!address_contains("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266", "0x"));
+        assert!(!address_contains("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266", "dead"));
+    }
+
+    #[test]
+    fn diff_lists_changed_fields() {
+        let old = make_entry("Wallet");
+        let mut new = old.clone();
+        assert!(old.diff(&new).is_empty());
+
+        new.name = "Main Wallet".to_string();
+        new.network = "Polygon".to_string();
+        new.notes = "cold storage".to_string();
+        let changes: Vec<String> = old.diff(&new).iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            changes,
+            vec![
+                "name: Wallet \u{2192} Main Wallet",
+                "network: Ethereum \u{2192} Polygon",
+                "notes: (empty) \u{2192} cold storage",
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_never_shows_secret_values() {
+        let mut old = make_entry("Wallet");
+        old.custom_fields = vec![CustomField {
+            name: "PIN".to_string(),
+            value: "1234".to_string(),
+            sensitive: true,
+        }];
+        let mut new = old.clone();
+        new.secret = "new-secret-value".to_string();
+        new.custom_fields[0].value = "9876".to_string();
+
+        let changes = old.diff(&new);
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|c| c.change == Change::Hidden));
+        let text: String = changes.iter().map(|c| c.to_string()).collect();
+        assert!(!text.contains("secret-value"));
+        assert!(!text.contains("1234") && !text.contains("9876"));
+    }
+
+    #[test]
+    fn diff_custom_field_added_and_removed() {
+        let mut old = make_entry("Wallet");
+        old.custom_fields = vec![CustomField {
+            name: "Exchange".to_string(),
+            value: "Kraken".to_string(),
+            sensitive: false,
+        }];
+        let mut new = old.clone();
+        new.custom_fields = vec![CustomField {
+            name: "Account".to_string(),
+            value: "42".to_string(),
+            sensitive: false,
+        }];
+        let changes: Vec<String> = old.diff(&new).iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            changes,
+            vec!["field 'Account': (empty) \u{2192} 42", "field 'Exchange': removed"]
+        );
+    }
+
+    #[test]
+    fn move_entry_reorders() {
+        let mut vault = make_vault(&["A", "B", "C", "D"]);
+        assert!(vault.move_entry(0, 2));
+        let names: Vec<&str> = vault.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["B", "C", "A", "D"]);
+        assert!(vault.move_entry(3, 0));
+        let names: Vec<&str> = vault.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["D", "B", "C", "A"]);
+        assert!(!vault.move_entry(4, 0));
+        assert_eq!(vault.entries.len(), 4);
+    }
+
+    #[test]
+    fn search_secrets_matches_secrets_and_fields() {
+        let mut vault = make_vault(&["A", "B", "C", "D"]);
+        vault.entries[0].secret = "0xAbCdEf".to_string();
+        vault.entries[1].custom_fields.push(CustomField {
+            name: "PIN".to_string(),
+            value: "98abcd".to_string(),
+            sensitive: true,
+        });
+        vault.entries[2].secret = "abcdef".to_string();
+        vault.entries[2].has_secondary_password = true;
+        assert_eq!(vault.search_secrets("ABCD"), vec![0, 1]);
+        assert_eq!(vault.search_secrets("pin"), vec![1]);
+        assert!(vault.search_secrets("nothing").is_empty());
+    }
+
+    #[test]
+    fn password_change_summary_counts_secondary_entries() {
+        let mut vault = make_vault(&["A", "B", "C"]);
+        assert_eq!(vault.password_change_summary(), "3 entries will be re-encrypted.");
+        vault.entries[1].has_secondary_password = true;
+        assert_eq!(
+            vault.password_change_summary(),
+            "2 entries will be re-encrypted; 1 secondary-password entry is unaffected."
+        );
     }
 }
@@ -1,25 +1,74 @@
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
 use zeroize::Zeroizing;
 
+use crate::crypto::kdf::KdfAlgorithm;
 use crate::crypto::{cipher, kdf};
 use crate::error::{CryptoKeeperError, Result};
+use crate::logging;
+use crate::vault::formats;
 use crate::vault::model::{BackupHeader, EntryMeta, VaultData, VaultHeader};
+use crate::vault::store::{set_file_permissions, temp_path, FileStore, VaultStore};
 
-/// Get the vault directory path, respecting CRYPTOKEEPER_VAULT_DIR env var.
+/// Get the vault directory path. Resolution order:
+/// 1. `CRYPTOKEEPER_VAULT_DIR`, if set
+/// 2. `~/.cryptokeeper`, if it already exists (vaults created before XDG support)
+/// 3. On XDG platforms, `$XDG_DATA_HOME/cryptokeeper` (default `~/.local/share/cryptokeeper`)
+/// 4. `~/.cryptokeeper`
 pub fn vault_dir() -> PathBuf {
-    if let Ok(dir) = std::env::var("CRYPTOKEEPER_VAULT_DIR") {
-        PathBuf::from(dir)
-    } else {
-        dirs_fallback()
+    resolve_dir(
+        std::env::var_os("CRYPTOKEEPER_VAULT_DIR").map(PathBuf::from),
+        legacy_dir(),
+        xdg_dir("XDG_DATA_HOME", ".local/share"),
+    )
+}
+
+/// Pick the override if given, else the legacy directory when it exists or no XDG
+/// directory applies, else the XDG directory.
+pub(crate) fn resolve_dir(
+    override_dir: Option<PathBuf>,
+    legacy: PathBuf,
+    xdg: Option<PathBuf>,
+) -> PathBuf {
+    if let Some(dir) = override_dir {
+        return dir;
+    }
+    match xdg {
+        Some(xdg) if !legacy.exists() => xdg,
+        _ => legacy,
     }
 }
 
-fn dirs_fallback() -> PathBuf {
+pub(crate) fn home_dir() -> PathBuf {
     let home = std::env::var("HOME")
         .or_else(|_| std::env::var("USERPROFILE"))
         .unwrap_or_else(|_| ".".to_string());
-    PathBuf::from(home).join(".cryptokeeper")
+    PathBuf::from(home)
+}
+
+/// `~/.cryptokeeper`, where the vault and config lived before XDG support.
+pub(crate) fn legacy_dir() -> PathBuf {
+    home_dir().join(".cryptokeeper")
+}
+
+/// `$var/cryptokeeper`, or `~/default/cryptokeeper` if `var` is unset or not an
+/// absolute path (as the XDG spec requires).
+#[cfg(all(unix, not(target_os = "macos")))]
+pub(crate) fn xdg_dir(var: &str, default: &str) -> Option<PathBuf> {
+    let base = std::env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .unwrap_or_else(|| home_dir().join(default));
+    Some(base.join("cryptokeeper"))
+}
+
+/// No XDG directories outside Linux and the BSDs.
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+pub(crate) fn xdg_dir(_var: &str, _default: &str) -> Option<PathBuf> {
+    None
 }
 
 pub fn vault_path() -> PathBuf {
@@ -27,19 +76,45 @@ pub fn vault_path() -> PathBuf {
 }
 
 pub fn vault_exists() -> bool {
-    vault_path().exists()
+    FileStore.exists(&vault_path())
 }
 
 /// Ensure the vault directory exists with proper permissions.
 pub fn ensure_vault_dir() -> Result<()> {
-    let dir = vault_dir();
+    ensure_private_dir(&vault_dir())
+}
+
+/// Create `dir` readable only by the owner (0o700) if it does not exist yet.
+pub fn ensure_private_dir(dir: &Path) -> Result<()> {
     if !dir.exists() {
-        fs::create_dir_all(&dir)?;
-        set_dir_permissions(&dir)?;
+        fs::create_dir_all(dir)?;
+        set_dir_permissions(dir)?;
     }
     Ok(())
 }
 
+/// Fail early with `VaultDirNotWritable` if the vault directory cannot be
+/// created or written to (e.g. a read-only mount), instead of deep in a save.
+pub fn check_vault_dir_writable() -> Result<()> {
+    check_dir_writable(&vault_dir())
+}
+
+fn check_dir_writable(dir: &Path) -> Result<()> {
+    let not_writable = || CryptoKeeperError::VaultDirNotWritable(dir.display().to_string());
+    if !dir.exists() {
+        fs::create_dir_all(dir).map_err(|_| not_writable())?;
+        set_dir_permissions(dir)?;
+    }
+    let probe = dir.join(format!(".write-test-{}", std::process::id()));
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .map_err(|_| not_writable())?;
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
 #[cfg(unix)]
 fn set_dir_permissions(path: &Path) -> Result<()> {
     use std::os::unix::fs::PermissionsExt;
@@ -52,19 +127,152 @@ fn set_dir_permissions(_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// A vault path readable or writable by users other than its owner.
+#[derive(Debug, PartialEq)]
+pub struct LoosePermissions {
+    pub path: PathBuf,
+    pub mode: u32,
+    pub is_dir: bool,
+}
+
+impl LoosePermissions {
+    /// The owner-only mode the path should have.
+    pub fn wanted_mode(&self) -> u32 {
+        if self.is_dir {
+            0o700
+        } else {
+            0o600
+        }
+    }
+
+    /// Reset the path to its owner-only mode.
+    pub fn fix(&self) -> Result<()> {
+        if self.is_dir {
+            set_dir_permissions(&self.path)
+        } else {
+            set_file_permissions(&self.path)
+        }
+    }
+}
+
+/// `vault.ck`, and the vault directory when it is CryptoKeeper's own, where
+/// either grants access beyond the owner (e.g. after being copied in from
+/// elsewhere). The folder of a custom vault path (say `~/Documents`) is the
+/// user's to manage, so it is not checked.
+pub fn loose_vault_permissions() -> Vec<LoosePermissions> {
+    let dir = vault_dir();
+    let own_dir = dir == legacy_dir()
+        || xdg_dir("XDG_DATA_HOME", ".local/share").is_some_and(|xdg| xdg == dir);
+    loose_permissions(own_dir.then_some(dir.as_path()), &vault_path())
+}
+
 #[cfg(unix)]
-fn set_file_permissions(path: &Path) -> Result<()> {
+fn loose_permissions(dir: Option<&Path>, file: &Path) -> Vec<LoosePermissions> {
     use std::os::unix::fs::PermissionsExt;
-    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
-    Ok(())
+
+    dir.map(|dir| (dir, true))
+        .into_iter()
+        .chain([(file, false)])
+        .filter_map(|(path, is_dir)| {
+            let mode = fs::metadata(path).ok()?.permissions().mode() & 0o777;
+            (mode & 0o077 != 0).then(|| LoosePermissions {
+                path: path.to_path_buf(),
+                mode,
+                is_dir,
+            })
+        })
+        .collect()
 }
 
 #[cfg(not(unix))]
-fn set_file_permissions(_path: &Path) -> Result<()> {
-    Ok(())
+fn loose_permissions(_dir: Option<&Path>, _file: &Path) -> Vec<LoosePermissions> {
+    Vec::new()
+}
+
+/// A staging file left next to the vault by a save that never reached its
+/// rename, e.g. because the process was killed.
+#[derive(Debug)]
+pub struct OrphanedTemp {
+    pub path: PathBuf,
+    vault: PathBuf,
+    /// Entries listed in the leftover file, if it holds a well-formed vault
+    pub temp_entries: Option<usize>,
+    /// Entries listed in the vault, if it exists and is well-formed
+    pub vault_entries: Option<usize>,
+}
+
+impl OrphanedTemp {
+    /// Delete the leftover file.
+    pub fn discard(&self) -> Result<()> {
+        fs::remove_file(&self.path)?;
+        Ok(())
+    }
+
+    /// Make the leftover file the vault. A vault file already there is kept
+    /// as `<name>.replaced`, or `<name>.replaced.N` when earlier restores
+    /// took that name, and its new path is returned.
+    pub fn restore(&self) -> Result<Option<PathBuf>> {
+        let kept = if self.vault.exists() {
+            let mut replaced = self.vault.with_extension("replaced");
+            let mut n = 1;
+            while replaced.exists() {
+                replaced = self.vault.with_extension(format!("replaced.{}", n));
+                n += 1;
+            }
+            fs::rename(&self.vault, &replaced)?;
+            Some(replaced)
+        } else {
+            None
+        };
+        fs::rename(&self.path, &self.vault)?;
+        Ok(kept)
+    }
+}
+
+/// The leftover staging file of the vault, unless another process holds the
+/// vault and may be saving right now.
+pub fn orphaned_vault_temp() -> Option<OrphanedTemp> {
+    if super::lock::holder().is_some() {
+        return None;
+    }
+    find_orphaned_temp(&vault_path())
+}
+
+fn find_orphaned_temp(vault: &Path) -> Option<OrphanedTemp> {
+    let path = temp_path(vault);
+    if !path.is_file() {
+        return None;
+    }
+    // Header and metadata checks only: without the password the contents can't be verified
+    let entries = |path: &Path| -> Option<usize> {
+        let data = fs::read(path).ok()?;
+        if data.len() < VaultHeader::HEADER_SIZE_V1 || &data[0..4] != VaultHeader::MAGIC {
+            return None;
+        }
+        parse_encrypted(&data).ok()?;
+        read_metadata(path).ok().map(|meta| meta.len())
+    };
+    Some(OrphanedTemp {
+        temp_entries: entries(&path),
+        vault_entries: entries(vault),
+        path,
+        vault: vault.to_path_buf(),
+    })
+}
+
+/// Content fingerprint of a vault file, used to notice changes made by other processes.
+pub type Fingerprint = [u8; 32];
+
+/// SHA-256 of the file contents at `path`.
+pub fn fingerprint(path: &Path) -> Result<Fingerprint> {
+    use sha2::{Digest, Sha256};
+    let data = fs::read(path)?;
+    Ok(Sha256::digest(&data).into())
 }
 
 /// Read entry metadata (names, network, type, notes) without password. Returns empty for v1 vaults.
+/// The metadata block is stored unencrypted and holds no secrets (masked
+/// entries leave it out), so the buffer read here needs no wiping.
 pub fn read_metadata(path: &Path) -> Result<Vec<EntryMeta>> {
     let data = fs::read(path)?;
     if data.len() < 12 {
@@ -74,7 +282,7 @@ pub fn read_metadata(path: &Path) -> Result<Vec<EntryMeta>> {
         return Ok(Vec::new());
     }
     let version = u32::from_le_bytes(data[4..8].try_into().unwrap());
-    if version != VaultHeader::FORMAT_VERSION_V2 {
+    if !VaultHeader::has_metadata(version) {
         return Ok(Vec::new());
     }
     let meta_len = u32::from_le_bytes(data[8..12].try_into().unwrap()) as usize;
@@ -95,128 +303,387 @@ pub fn read_vault_metadata() -> Result<Vec<EntryMeta>> {
     read_metadata(&path)
 }
 
-/// Encrypt and write vault data to disk atomically.
-pub fn write_vault(vault: &VaultData, password: &[u8], path: &Path) -> Result<()> {
-    write_encrypted_file(vault, password, path, VaultHeader::MAGIC)
+/// Encrypt and write vault data to disk atomically. Returns the format version
+/// the file was upgraded from, as `write_vault_upgrading`.
+pub fn write_vault(vault: &VaultData, password: &[u8], path: &Path) -> Result<Option<u32>> {
+    write_vault_upgrading(&FileStore, vault, password, path)
 }
 
 /// Encrypt and write backup file.
 pub fn write_backup(vault: &VaultData, password: &[u8], path: &Path) -> Result<()> {
-    write_encrypted_file(vault, password, path, BackupHeader::MAGIC)
+    write_encrypted_file(&FileStore, vault, password, path, BackupHeader::MAGIC)
+}
+
+/// Encrypt a backup in memory, e.g. for an armored text export.
+pub fn backup_bytes(vault: &VaultData, password: &[u8]) -> Result<Vec<u8>> {
+    encrypt_vault(vault, password, BackupHeader::MAGIC)
+}
+
+/// Write the vault in the current format. Returns the version the file at
+/// `path` had when it was a vault in an older format, which this upgraded.
+fn write_vault_upgrading(
+    store: &dyn VaultStore,
+    vault: &VaultData,
+    password: &[u8],
+    path: &Path,
+) -> Result<Option<u32>> {
+    let previous = outdated_version(store, path);
+    logging::debug!("Writing {} entries to {}", vault.entries.len(), path.display());
+    write_encrypted_file(store, vault, password, path, VaultHeader::MAGIC)?;
+    Ok(previous)
+}
+
+/// Format version of the vault at `path` if it is older than the one saves
+/// write. None when it is current or not a readable vault.
+fn outdated_version(store: &dyn VaultStore, path: &Path) -> Option<u32> {
+    let data = store.read_bytes(path).ok()?;
+    if data.len() < 8 || &data[0..4] != VaultHeader::MAGIC {
+        return None;
+    }
+    let version = u32::from_le_bytes(data[4..8].try_into().unwrap());
+    (version < VaultHeader::FORMAT_VERSION_V3).then_some(version)
+}
+
+/// Tell the user an older vault file was rewritten in the current format.
+pub fn upgrade_notice(from: u32) -> String {
+    format!(
+        "Vault upgraded from format V{} to V{}.",
+        from,
+        VaultHeader::FORMAT_VERSION_V3
+    )
 }
 
 fn write_encrypted_file(
+    store: &dyn VaultStore,
     vault: &VaultData,
     password: &[u8],
     path: &Path,
     magic: &[u8; 4],
 ) -> Result<()> {
+    let data = encrypt_vault(vault, password, magic)?;
+    store.write_bytes_atomic(path, &data)
+}
+
+fn encrypt_vault(vault: &VaultData, password: &[u8], magic: &[u8; 4]) -> Result<Vec<u8>> {
+    encrypt_vault_with(vault, password, magic, KdfAlgorithm::DEFAULT)
+}
+
+fn encrypt_vault_with(
+    vault: &VaultData,
+    password: &[u8],
+    magic: &[u8; 4],
+    kdf_algorithm: KdfAlgorithm,
+) -> Result<Vec<u8>> {
     let plaintext = Zeroizing::new(serde_json::to_vec(vault)?);
 
     let salt = kdf::generate_salt();
     let nonce = cipher::generate_nonce();
-    let key = kdf::derive_key(
+    let key = kdf::derive_key_with(
         password,
         &salt,
         kdf::DEFAULT_M_COST,
         kdf::DEFAULT_T_COST,
         kdf::DEFAULT_P_COST,
+        kdf_algorithm,
     )?;
 
     let ciphertext = cipher::encrypt(&*key, &nonce, &plaintext)?;
-    let ct_len = ciphertext.len() as u32;
+    // Backups get an empty metadata block: their header stays free of names
+    let meta_json = if magic == VaultHeader::MAGIC {
+        serde_json::to_vec(&vault.metadata())?
+    } else {
+        b"[]".to_vec()
+    };
+    let v3 = Some((meta_json.as_slice(), kdf_algorithm));
+    Ok(assemble_file(magic, v3, &salt, &nonce, &ciphertext))
+}
 
+/// Lay out an encrypted file: V3 when given the metadata block and the Argon2
+/// variant, V1 otherwise (only written by tests, as older files were). Costs
+/// are always the defaults.
+fn assemble_file(
+    magic: &[u8; 4],
+    v3: Option<(&[u8], KdfAlgorithm)>,
+    salt: &[u8; 32],
+    nonce: &[u8; 24],
+    ciphertext: &[u8],
+) -> Vec<u8> {
     let mut data = Vec::new();
     data.extend_from_slice(magic);
-
-    if magic == VaultHeader::MAGIC {
-        let meta = vault.metadata();
-        let meta_json = serde_json::to_vec(&meta)?;
-        let meta_len = meta_json.len() as u32;
-        data.extend_from_slice(&VaultHeader::FORMAT_VERSION_V2.to_le_bytes());
-        data.extend_from_slice(&meta_len.to_le_bytes());
-        data.extend_from_slice(&meta_json);
-    } else {
-        data.extend_from_slice(&VaultHeader::FORMAT_VERSION_V1.to_le_bytes());
+    match v3 {
+        Some((meta_json, _)) => {
+            data.extend_from_slice(&VaultHeader::FORMAT_VERSION_V3.to_le_bytes());
+            data.extend_from_slice(&(meta_json.len() as u32).to_le_bytes());
+            data.extend_from_slice(meta_json);
+        }
+        None => data.extend_from_slice(&VaultHeader::FORMAT_VERSION_V1.to_le_bytes()),
     }
 
-    data.extend_from_slice(&salt);
+    data.extend_from_slice(salt);
     data.extend_from_slice(&kdf::DEFAULT_M_COST.to_le_bytes());
     data.extend_from_slice(&kdf::DEFAULT_T_COST.to_le_bytes());
     data.extend_from_slice(&kdf::DEFAULT_P_COST.to_le_bytes());
-    data.extend_from_slice(&nonce);
-    data.extend_from_slice(&ct_len.to_le_bytes());
-    data.extend_from_slice(&ciphertext);
-
-    let temp_path = path.with_extension("tmp");
-    fs::write(&temp_path, &data)?;
-    set_file_permissions(&temp_path)?;
-    fs::rename(&temp_path, path)?;
+    if let Some((_, kdf_algorithm)) = v3 {
+        data.extend_from_slice(&kdf_algorithm.to_bytes());
+    }
+    data.extend_from_slice(nonce);
+    data.extend_from_slice(&(ciphertext.len() as u32).to_le_bytes());
+    data.extend_from_slice(ciphertext);
+    data
+}
 
-    Ok(())
+/// Header fields needed to open an encrypted file, and its ciphertext.
+struct EncryptedParts<'a> {
+    salt: [u8; 32],
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    kdf_algorithm: KdfAlgorithm,
+    nonce: [u8; 24],
+    ciphertext: &'a [u8],
 }
 
-/// Read and decrypt vault from disk.
-pub fn read_vault(password: &[u8], path: &Path) -> Result<VaultData> {
-    read_encrypted_file(password, path, VaultHeader::MAGIC)
+/// Split a vault or backup file (magic already checked) into its header fields
+/// and ciphertext. V2 and V3 put the metadata block before the salt; V3 adds
+/// the Argon2 variant and version after the costs. Files without them were
+/// derived with `KdfAlgorithm::LEGACY`, whatever today's default is.
+fn parse_encrypted(data: &[u8]) -> Result<EncryptedParts<'_>> {
+    if data.len() < VaultHeader::HEADER_SIZE_V1 {
+        return Err(CryptoKeeperError::InvalidVaultFormat);
+    }
+    let u32_at = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+
+    let version = u32_at(4);
+    let salt_offset = if VaultHeader::has_metadata(version) {
+        12 + u32_at(8) as usize
+    } else {
+        8
+    };
+    let algorithm_len = if version == VaultHeader::FORMAT_VERSION_V3 { 2 } else { 0 };
+    let nonce_offset = salt_offset + 32 + 4 + 4 + 4 + algorithm_len;
+    let ct_offset = nonce_offset + 24 + 4;
+    if data.len() < ct_offset {
+        return Err(CryptoKeeperError::InvalidVaultFormat);
+    }
+
+    let kdf_algorithm = if algorithm_len > 0 {
+        KdfAlgorithm::from_bytes([data[nonce_offset - 2], data[nonce_offset - 1]])?
+    } else {
+        KdfAlgorithm::LEGACY
+    };
+    let ct_len = u32_at(nonce_offset + 24) as usize;
+    if data.len() < ct_offset + ct_len {
+        return Err(CryptoKeeperError::InvalidVaultFormat);
+    }
+
+    Ok(EncryptedParts {
+        salt: data[salt_offset..salt_offset + 32].try_into().unwrap(),
+        m_cost: u32_at(salt_offset + 32),
+        t_cost: u32_at(salt_offset + 36),
+        p_cost: u32_at(salt_offset + 40),
+        kdf_algorithm,
+        nonce: data[nonce_offset..nonce_offset + 24].try_into().unwrap(),
+        ciphertext: &data[ct_offset..ct_offset + ct_len],
+    })
 }
 
 /// Read and decrypt backup from disk.
 pub fn read_backup(password: &[u8], path: &Path) -> Result<VaultData> {
-    read_encrypted_file(password, path, BackupHeader::MAGIC)
+    read_encrypted_file(&FileStore, password, path, &[BackupHeader::MAGIC])
 }
 
-fn read_encrypted_file(password: &[u8], path: &Path, expected_magic: &[u8; 4]) -> Result<VaultData> {
-    let data = fs::read(path)?;
+/// Read and decrypt either a vault or a backup file.
+pub fn read_any_encrypted(password: &[u8], path: &Path) -> Result<VaultData> {
+    read_encrypted_file(
+        &FileStore,
+        password,
+        path,
+        &[VaultHeader::MAGIC, BackupHeader::MAGIC],
+    )
+}
+
+/// How long each step of an unlock took, for tuning the KDF cost.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnlockTimings {
+    pub read: Duration,
+    pub kdf: Duration,
+    pub decrypt: Duration,
+    pub deserialize: Duration,
+    /// Argon2 memory (KiB), iterations and parallelism stored in the file header
+    pub kdf_params: (u32, u32, u32),
+}
+
+impl UnlockTimings {
+    pub fn total(&self) -> Duration {
+        self.read + self.kdf + self.decrypt + self.deserialize
+    }
+}
+
+/// Warning for a key derivation that finished in under `threshold_ms` (0: never
+/// warn): cheap enough that the vault's password is easy to brute-force here.
+pub fn fast_unlock_warning(kdf: Duration, threshold_ms: u64) -> Option<String> {
+    if threshold_ms == 0 || kdf >= Duration::from_millis(threshold_ms) {
+        return None;
+    }
+    Some(format!(
+        "Unlocking took only {} ms, so this vault's password would be cheap to brute-force. \
+         Changing the master password (the same one is fine) re-encrypts it with the current \
+         key-derivation cost.",
+        kdf.as_millis()
+    ))
+}
+
+/// Read and decrypt vault from disk, recording how long each step took.
+pub fn read_vault_timed(password: &[u8], path: &Path) -> Result<(VaultData, UnlockTimings)> {
+    read_encrypted_file_timed(&FileStore, password, path, &[VaultHeader::MAGIC])
+}
+
+fn read_encrypted_file(
+    store: &dyn VaultStore,
+    password: &[u8],
+    path: &Path,
+    accepted_magic: &[&[u8; 4]],
+) -> Result<VaultData> {
+    read_encrypted_file_timed(store, password, path, accepted_magic).map(|(vault, _)| vault)
+}
+
+fn read_encrypted_file_timed(
+    store: &dyn VaultStore,
+    password: &[u8],
+    path: &Path,
+    accepted_magic: &[&[u8; 4]],
+) -> Result<(VaultData, UnlockTimings)> {
+    let mut timings = UnlockTimings::default();
+    let started = Instant::now();
+    let mut data = store.read_bytes(path)?;
+    logging::debug!("Read {} ({} bytes)", path.display(), data.len());
+    // An armored backup pasted into a text file decodes to the same bytes
+    if formats::is_armored(&data) {
+        data = formats::dearmor(&String::from_utf8_lossy(&data))?;
+    }
 
     if data.len() < VaultHeader::HEADER_SIZE_V1 {
         return Err(CryptoKeeperError::InvalidVaultFormat);
     }
 
     let magic = &data[0..4];
-    if magic != expected_magic {
+    if !accepted_magic.iter().any(|m| magic == *m) {
         return Err(CryptoKeeperError::InvalidVaultFormat);
     }
 
-    let version = u32::from_le_bytes(data[4..8].try_into().unwrap());
-    let (salt_offset, ct_offset) = if version == VaultHeader::FORMAT_VERSION_V2 {
-        let meta_len = u32::from_le_bytes(data[8..12].try_into().unwrap()) as usize;
-        (12 + meta_len, 12 + meta_len + 32 + 4 + 4 + 4 + 24 + 4)
-    } else {
-        (8, VaultHeader::HEADER_SIZE_V1)
-    };
+    let parts = parse_encrypted(&data)?;
+    check_vault_size(parts.ciphertext.len(), max_vault_size_mb())?;
+    timings.read = started.elapsed();
+    timings.kdf_params = (parts.m_cost, parts.t_cost, parts.p_cost);
 
-    if data.len() < ct_offset {
-        return Err(CryptoKeeperError::InvalidVaultFormat);
-    }
+    let started = Instant::now();
+    let key = kdf::derive_key_with(
+        password,
+        &parts.salt,
+        parts.m_cost,
+        parts.t_cost,
+        parts.p_cost,
+        parts.kdf_algorithm,
+    )?;
+    timings.kdf = started.elapsed();
 
-    let mut salt = [0u8; 32];
-    salt.copy_from_slice(&data[salt_offset..salt_offset + 32]);
+    // Same steps as `decrypt_vault`, timed separately
+    let started = Instant::now();
+    let plaintext: Zeroizing<Vec<u8>> = cipher::decrypt(&*key, &parts.nonce, parts.ciphertext)?;
+    timings.decrypt = started.elapsed();
 
-    let m_cost = u32::from_le_bytes(data[salt_offset + 32..salt_offset + 36].try_into().unwrap());
-    let t_cost = u32::from_le_bytes(data[salt_offset + 36..salt_offset + 40].try_into().unwrap());
-    let p_cost = u32::from_le_bytes(data[salt_offset + 40..salt_offset + 44].try_into().unwrap());
+    let started = Instant::now();
+    let vault = parse_vault_json(&plaintext);
+    drop(plaintext);
+    timings.deserialize = started.elapsed();
 
-    let mut nonce = [0u8; 24];
-    nonce.copy_from_slice(&data[salt_offset + 44..salt_offset + 68]);
+    Ok((vault?, timings))
+}
 
-    let ct_len = u32::from_le_bytes(data[salt_offset + 68..salt_offset + 72].try_into().unwrap()) as usize;
+/// `Config::max_vault_size_mb`, or its default if the config can't be read.
+fn max_vault_size_mb() -> u64 {
+    crate::config::load_config().unwrap_or_default().max_vault_size_mb
+}
 
-    if data.len() < ct_offset + ct_len {
-        return Err(CryptoKeeperError::InvalidVaultFormat);
+/// Refuse to decrypt a vault whose ciphertext is over `limit_mb`, before any
+/// memory is spent on the plaintext.
+fn check_vault_size(ciphertext_len: usize, limit_mb: u64) -> Result<()> {
+    const MB: u64 = 1024 * 1024;
+    let len = ciphertext_len as u64;
+    if len > limit_mb.saturating_mul(MB) {
+        return Err(CryptoKeeperError::VaultTooLarge(len.div_ceil(MB), limit_mb));
     }
+    Ok(())
+}
 
-    let ciphertext = &data[ct_offset..ct_offset + ct_len];
+/// Decrypt a vault or backup body and deserialize it.
+///
+/// The decrypted JSON is every secret in the vault in one buffer. `cipher::decrypt`
+/// hands it over as `Zeroizing`, and it is dropped, and so wiped, as soon as
+/// parsing finishes, successful or not. From then on the secrets live only in
+/// the `Entry` values, which wipe themselves on drop.
+fn decrypt_vault(key: &[u8; 32], parts: &EncryptedParts) -> Result<VaultData> {
+    let plaintext: Zeroizing<Vec<u8>> = cipher::decrypt(key, &parts.nonce, parts.ciphertext)?;
+    let vault = parse_vault_json(&plaintext);
+    drop(plaintext);
+    vault
+}
+
+/// Deserialize decrypted vault JSON. Errors name the field that failed but
+/// never quote serde's message, which can include decrypted values.
+fn parse_vault_json(plaintext: &[u8]) -> Result<VaultData> {
+    let json = std::str::from_utf8(plaintext)
+        .map_err(|e| invalid_contents("text that is not UTF-8", plaintext, e.valid_up_to()))?;
+    serde_json::from_str(json).map_err(|e| {
+        let problem = match e.classify() {
+            serde_json::error::Category::Eof => "data that ends early",
+            serde_json::error::Category::Syntax => "malformed JSON",
+            _ => "a value of the wrong type",
+        };
+        // Lines and columns are 1-based; columns count bytes
+        let line_start: usize = json
+            .split_inclusive('\n')
+            .take(e.line().saturating_sub(1))
+            .map(str::len)
+            .sum();
+        invalid_contents(problem, plaintext, line_start + e.column().saturating_sub(1))
+    })
+}
 
-    let key = kdf::derive_key(password, &salt, m_cost, t_cost, p_cost)?;
-    let plaintext = cipher::decrypt(&*key, &nonce, ciphertext)?;
-    let vault: VaultData = serde_json::from_slice(&plaintext)?;
+fn invalid_contents(problem: &str, json: &[u8], offset: usize) -> CryptoKeeperError {
+    let detail = match field_before(&json[..offset.min(json.len())]) {
+        Some(field) => format!("{} in field '{}'", problem, field),
+        None => problem.to_string(),
+    };
+    CryptoKeeperError::InvalidVaultContents(detail)
+}
 
-    Ok(vault)
+/// The last object key in `json`, if it looks like one of our field names
+/// rather than text from inside a value.
+fn field_before(json: &[u8]) -> Option<&str> {
+    let colon = json.windows(2).rposition(|w| w == b"\":")?;
+    let start = json[..colon].iter().rposition(|&b| b == b'"')? + 1;
+    let key = std::str::from_utf8(&json[start..colon]).ok()?;
+    let is_field = !key.is_empty()
+        && key.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_');
+    is_field.then_some(key)
 }
 
 /// Prompt for master password and unlock the vault.
 pub fn prompt_and_unlock() -> Result<(VaultData, Zeroizing<String>)> {
+    let (vault, password, timings) = prompt_and_unlock_timed()?;
+    let threshold_ms = crate::config::load_config().map_or(0, |c| c.fast_unlock_warn_ms);
+    if let Some(warning) = fast_unlock_warning(timings.kdf, threshold_ms) {
+        crate::ui::borders::print_warning(&format!(
+            "{} Run `cryptokeeper passwd`.",
+            warning
+        ));
+    }
+    Ok((vault, password))
+}
+
+/// Like `prompt_and_unlock`, also reporting how long each unlock step took.
+pub fn prompt_and_unlock_timed() -> Result<(VaultData, Zeroizing<String>, UnlockTimings)> {
     if !vault_exists() {
         return Err(CryptoKeeperError::VaultNotFound);
     }
@@ -230,23 +697,101 @@ pub fn prompt_and_unlock() -> Result<(VaultData, Zeroizing<String>)> {
         return Err(CryptoKeeperError::EmptyPassword);
     }
 
-    eprintln!("Unlocking vault...");
-    let vault = read_vault(password.as_bytes(), &vault_path())?;
+    let (tx, rx) = mpsc::channel();
+    let thread_password = password.clone();
+    thread::spawn(move || {
+        let result = read_vault_timed(thread_password.as_bytes(), &vault_path());
+        drop(thread_password);
+        send_or_discard(&tx, result);
+    });
+    let (vault, timings) = wait_with_spinner("Unlocking vault...", &rx)?;
+    let (m_cost, t_cost, p_cost) = timings.kdf_params;
+    logging::debug!(
+        "Unlocked {} entries in {:?}: read {:?}, Argon2 {:?} ({} KiB, {} iterations, {} lanes), decrypt {:?}, parse {:?}",
+        vault.entries.len(),
+        timings.total(),
+        timings.read,
+        timings.kdf,
+        m_cost,
+        t_cost,
+        p_cost,
+        timings.decrypt,
+        timings.deserialize
+    );
 
-    Ok((vault, password))
+    Ok((vault, password, timings))
+}
+
+/// Wait for a background result, drawing a spinner with elapsed time on stderr.
+/// Esc or Ctrl+C abandons the wait and returns `Cancelled`.
+fn wait_with_spinner<T>(label: &str, rx: &mpsc::Receiver<Result<T>>) -> Result<T> {
+    use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+    use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+    use std::io::{IsTerminal, Write};
+
+    let disconnected = || CryptoKeeperError::Encryption("Unlock worker exited unexpectedly".into());
+
+    if !std::io::stderr().is_terminal() || enable_raw_mode().is_err() {
+        logging::info!("{}", label);
+        return rx.recv().map_err(|_| disconnected())?;
+    }
+
+    let started = Instant::now();
+    let mut stderr = std::io::stderr();
+    let result = loop {
+        match rx.recv_timeout(Duration::from_millis(80)) {
+            Ok(result) => break result,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break Err(disconnected()),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+        }
+
+        let elapsed = started.elapsed();
+        let _ = write!(
+            stderr,
+            "\r\x1b[2K{} {} {:.1}s (Esc to cancel)",
+            crate::ui::theme::spinner_frame(elapsed),
+            label,
+            elapsed.as_secs_f32()
+        );
+        let _ = stderr.flush();
+
+        if let Ok(true) = event::poll(Duration::ZERO) {
+            if let Ok(Event::Key(key)) = event::read() {
+                let ctrl_c = key.code == KeyCode::Char('c')
+                    && key.modifiers.contains(KeyModifiers::CONTROL);
+                if key.code == KeyCode::Esc || ctrl_c {
+                    break Err(CryptoKeeperError::Cancelled);
+                }
+            }
+        }
+    };
+
+    let _ = write!(stderr, "\r\x1b[2K");
+    let _ = stderr.flush();
+    let _ = disable_raw_mode();
+    result
+}
+
+/// Refuse to write while another process holds the vault lock.
+fn ensure_unlocked() -> Result<()> {
+    match super::lock::holder() {
+        Some(pid) => Err(CryptoKeeperError::VaultLocked(pid)),
+        None => Ok(()),
+    }
 }
 
-/// Save vault with the given password.
-pub fn save_vault(vault: &VaultData, password: &[u8]) -> Result<()> {
+/// Save vault with the given password. Returns the format version the file
+/// was upgraded from, if it was older, for the caller to report with
+/// `upgrade_notice`.
+pub fn save_vault(vault: &VaultData, password: &[u8]) -> Result<Option<u32>> {
+    ensure_unlocked()?;
     write_vault(vault, password, &vault_path())
 }
 
-/// Unlock vault and return the derived key and salt for key caching (REPL mode).
-pub fn unlock_vault_returning_key(
-    password: &[u8],
-) -> Result<(VaultData, Zeroizing<[u8; 32]>, [u8; 32])> {
-    let path = vault_path();
-    let data = fs::read(&path)?;
+/// Unlock vault and return the derived key, its salt and Argon2 variant for key
+/// caching (REPL mode).
+pub fn unlock_vault_returning_key(password: &[u8]) -> UnlockResult {
+    let data = FileStore.read_bytes(&vault_path())?;
 
     if data.len() < VaultHeader::HEADER_SIZE_V1 {
         return Err(CryptoKeeperError::InvalidVaultFormat);
@@ -257,43 +802,46 @@ pub fn unlock_vault_returning_key(
         return Err(CryptoKeeperError::InvalidVaultFormat);
     }
 
-    let version = u32::from_le_bytes(data[4..8].try_into().unwrap());
-    let salt_offset = if version == VaultHeader::FORMAT_VERSION_V2 {
-        let meta_len = u32::from_le_bytes(data[8..12].try_into().unwrap()) as usize;
-        12 + meta_len
-    } else {
-        8
-    };
-
-    let ct_offset = salt_offset + 32 + 4 + 4 + 4 + 24 + 4;
-    if data.len() < ct_offset {
-        return Err(CryptoKeeperError::InvalidVaultFormat);
-    }
-
-    let mut salt = [0u8; 32];
-    salt.copy_from_slice(&data[salt_offset..salt_offset + 32]);
-
-    let m_cost = u32::from_le_bytes(data[salt_offset + 32..salt_offset + 36].try_into().unwrap());
-    let t_cost = u32::from_le_bytes(data[salt_offset + 36..salt_offset + 40].try_into().unwrap());
-    let p_cost = u32::from_le_bytes(data[salt_offset + 40..salt_offset + 44].try_into().unwrap());
+    let parts = parse_encrypted(&data)?;
+    check_vault_size(parts.ciphertext.len(), max_vault_size_mb())?;
+    let key = kdf::derive_key_with(
+        password,
+        &parts.salt,
+        parts.m_cost,
+        parts.t_cost,
+        parts.p_cost,
+        parts.kdf_algorithm,
+    )?;
+    let vault = decrypt_vault(&key, &parts)?;
 
-    let mut nonce = [0u8; 24];
-    nonce.copy_from_slice(&data[salt_offset + 44..salt_offset + 68]);
+    Ok((vault, key, parts.salt, parts.kdf_algorithm))
+}
 
-    let ct_len =
-        u32::from_le_bytes(data[salt_offset + 68..salt_offset + 72].try_into().unwrap()) as usize;
+/// Result of unlocking the vault: the data plus the derived key, its salt and
+/// Argon2 variant.
+pub type UnlockResult = Result<(VaultData, Zeroizing<[u8; 32]>, [u8; 32], KdfAlgorithm)>;
+
+/// Run `unlock_vault_returning_key` on a background thread so the caller can keep
+/// redrawing while Argon2 derives the key. The result arrives on the returned channel.
+/// The thread is detached: to cancel, drop the receiver, and the key and
+/// decrypted vault are wiped as soon as the derivation finishes.
+pub fn spawn_unlock(password: Zeroizing<String>) -> mpsc::Receiver<UnlockResult> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let result = unlock_vault_returning_key(password.as_bytes());
+        drop(password);
+        send_or_discard(&tx, result);
+    });
+    rx
+}
 
-    if data.len() < ct_offset + ct_len {
-        return Err(CryptoKeeperError::InvalidVaultFormat);
+/// Deliver an unlock worker's result. If the receiver is gone (the unlock was
+/// cancelled), the result is dropped right here, which zeroizes the derived
+/// key (`Zeroizing`) and every decrypted secret (`Entry`'s `Drop`).
+fn send_or_discard<T>(tx: &mpsc::Sender<Result<T>>, result: Result<T>) {
+    if let Err(mpsc::SendError(abandoned)) = tx.send(result) {
+        drop(abandoned);
     }
-
-    let ciphertext = &data[ct_offset..ct_offset + ct_len];
-
-    let key = kdf::derive_key(password, &salt, m_cost, t_cost, p_cost)?;
-    let plaintext = cipher::decrypt(&*key, &nonce, ciphertext)?;
-    let vault: VaultData = serde_json::from_slice(&plaintext)?;
-
-    Ok((vault, key, salt))
 }
 
 /// Read vault using a pre-derived master key (for recovery flow).
@@ -305,73 +853,40 @@ pub fn read_vault_with_key(key: &[u8; 32], raw_data: &[u8]) -> Result<VaultData>
     if magic != VaultHeader::MAGIC {
         return Err(CryptoKeeperError::InvalidVaultFormat);
     }
-    let version = u32::from_le_bytes(raw_data[4..8].try_into().unwrap());
-    let salt_offset = if version == VaultHeader::FORMAT_VERSION_V2 {
-        let meta_len = u32::from_le_bytes(raw_data[8..12].try_into().unwrap()) as usize;
-        12 + meta_len
-    } else {
-        8
-    };
-    let ct_offset = salt_offset + 32 + 4 + 4 + 4 + 24 + 4;
-    if raw_data.len() < ct_offset {
-        return Err(CryptoKeeperError::InvalidVaultFormat);
-    }
-    let mut nonce = [0u8; 24];
-    nonce.copy_from_slice(&raw_data[salt_offset + 44..salt_offset + 68]);
-    let ct_len =
-        u32::from_le_bytes(raw_data[salt_offset + 68..salt_offset + 72].try_into().unwrap())
-            as usize;
-    if raw_data.len() < ct_offset + ct_len {
-        return Err(CryptoKeeperError::InvalidVaultFormat);
-    }
-    let ciphertext = &raw_data[ct_offset..ct_offset + ct_len];
-    let plaintext = cipher::decrypt(key, &nonce, ciphertext)?;
-    let vault: VaultData = serde_json::from_slice(&plaintext)?;
-    Ok(vault)
+    let parts = parse_encrypted(raw_data)?;
+    check_vault_size(parts.ciphertext.len(), max_vault_size_mb())?;
+    decrypt_vault(key, &parts)
 }
 
 /// Save vault using a pre-derived key (skips Argon2 derivation for REPL mode).
+/// `kdf_algorithm` is the variant the key was derived with, recorded in the
+/// header. Returns the format version the file was upgraded from, as
+/// `save_vault`.
 pub fn save_vault_with_key(
     vault: &VaultData,
     key: &[u8; 32],
     salt: &[u8; 32],
-) -> Result<()> {
+    kdf_algorithm: KdfAlgorithm,
+) -> Result<Option<u32>> {
+    ensure_unlocked()?;
+    let previous = outdated_version(&FileStore, &vault_path());
     let plaintext = Zeroizing::new(serde_json::to_vec(vault)?);
 
     let nonce = cipher::generate_nonce();
     let ciphertext = cipher::encrypt(key, &nonce, &plaintext)?;
-    let ct_len = ciphertext.len() as u32;
-
-    let meta = vault.metadata();
-    let meta_json = serde_json::to_vec(&meta)?;
-    let meta_len = meta_json.len() as u32;
+    let meta_json = serde_json::to_vec(&vault.metadata())?;
+    let v3 = Some((meta_json.as_slice(), kdf_algorithm));
+    let data = assemble_file(VaultHeader::MAGIC, v3, salt, &nonce, &ciphertext);
 
-    let mut data = Vec::new();
-    data.extend_from_slice(VaultHeader::MAGIC);
-    data.extend_from_slice(&VaultHeader::FORMAT_VERSION_V2.to_le_bytes());
-    data.extend_from_slice(&meta_len.to_le_bytes());
-    data.extend_from_slice(&meta_json);
-    data.extend_from_slice(salt);
-    data.extend_from_slice(&kdf::DEFAULT_M_COST.to_le_bytes());
-    data.extend_from_slice(&kdf::DEFAULT_T_COST.to_le_bytes());
-    data.extend_from_slice(&kdf::DEFAULT_P_COST.to_le_bytes());
-    data.extend_from_slice(&nonce);
-    data.extend_from_slice(&ct_len.to_le_bytes());
-    data.extend_from_slice(&ciphertext);
-
-    let path = vault_path();
-    let temp_path = path.with_extension("tmp");
-    fs::write(&temp_path, &data)?;
-    set_file_permissions(&temp_path)?;
-    fs::rename(&temp_path, path)?;
-
-    Ok(())
+    FileStore.write_bytes_atomic(&vault_path(), &data)?;
+    Ok(previous)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::vault::model::{Entry, SecretType};
+    use crate::vault::store::MemStore;
     use chrono::Utc;
     use tempfile::TempDir;
 
@@ -383,79 +898,438 @@ mod tests {
             secret_type: SecretType::PrivateKey,
             network: "Ethereum".to_string(),
             public_address: None,
+            address_is_manual: false,
+            derivation_account: 0,
+            derivation_index: 0,
             username: None,
             url: None,
             notes: "Test note".to_string(),
+            custom_fields: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
             has_secondary_password: false,
             entry_key_wrapped: None,
             entry_key_nonce: None,
             entry_key_salt: None,
+            entry_key_kdf: None,
             encrypted_secret: None,
             encrypted_secret_nonce: None,
+            mask_metadata: false,
+            clipboard_timeout_override: None,
+            archived: false,
         });
         vault
     }
 
     #[test]
     fn test_vault_roundtrip() {
-        let dir = TempDir::new().unwrap();
-        let path = dir.path().join("vault.ck");
+        let store = MemStore::default();
+        let path = Path::new("vault.ck");
         let password = b"test-password";
         let vault = test_vault();
 
-        write_vault(&vault, password, &path).unwrap();
-        let loaded = read_vault(password, &path).unwrap();
+        write_encrypted_file(&store, &vault, password, path, VaultHeader::MAGIC).unwrap();
+        let loaded = read_encrypted_file(&store, password, path, &[VaultHeader::MAGIC]).unwrap();
 
         assert_eq!(loaded.entries.len(), 1);
         assert_eq!(loaded.entries[0].name, "Test Key");
         assert_eq!(loaded.entries[0].secret, "0xdeadbeef");
     }
 
+    #[test]
+    fn test_new_vaults_record_argon2_variant() {
+        let vault = test_vault();
+        let data = encrypt_vault(&vault, b"pw", VaultHeader::MAGIC).unwrap();
+        let version = u32::from_le_bytes(data[4..8].try_into().unwrap());
+        assert_eq!(version, VaultHeader::FORMAT_VERSION_V3);
+        assert_eq!(parse_encrypted(&data).unwrap().kdf_algorithm, KdfAlgorithm::DEFAULT);
+
+        // Backups record it too, behind an empty metadata block
+        let backup = encrypt_vault(&vault, b"pw", BackupHeader::MAGIC).unwrap();
+        let version = u32::from_le_bytes(backup[4..8].try_into().unwrap());
+        assert_eq!(version, VaultHeader::FORMAT_VERSION_V3);
+        assert_eq!(&backup[8..14], b"\x02\0\0\0[]");
+        assert_eq!(parse_encrypted(&backup).unwrap().kdf_algorithm, KdfAlgorithm::DEFAULT);
+    }
+
+    #[test]
+    fn test_older_files_survive_a_new_default() {
+        use argon2::{Algorithm, Version};
+
+        // Stands in for a future `KdfAlgorithm::DEFAULT`
+        let new_default = KdfAlgorithm {
+            algorithm: Algorithm::Argon2i,
+            version: Version::V0x10,
+        };
+        let vault = test_vault();
+        let store = MemStore::default();
+        let path = Path::new("file");
+        let opens = |data: &[u8]| {
+            store.write_bytes_atomic(path, data).unwrap();
+            let magics = [VaultHeader::MAGIC, BackupHeader::MAGIC];
+            read_encrypted_file(&store, b"pw", path, &magics).unwrap().entries[0].secret == "0xdeadbeef"
+        };
+
+        // Files written under the new default record it
+        for magic in [VaultHeader::MAGIC, BackupHeader::MAGIC] {
+            assert!(opens(&encrypt_vault_with(&vault, b"pw", magic, new_default).unwrap()));
+        }
+
+        // Files from before the variant bytes still open with the legacy one
+        let salt = kdf::generate_salt();
+        let nonce = cipher::generate_nonce();
+        let key = kdf::derive_key(
+            b"pw",
+            &salt,
+            kdf::DEFAULT_M_COST,
+            kdf::DEFAULT_T_COST,
+            kdf::DEFAULT_P_COST,
+        )
+        .unwrap();
+        let ciphertext = cipher::encrypt(&key, &nonce, &serde_json::to_vec(&vault).unwrap()).unwrap();
+        let v1_backup = assemble_file(BackupHeader::MAGIC, None, &salt, &nonce, &ciphertext);
+        assert!(opens(&v1_backup));
+
+        // V2: the V1 layout with the metadata block after the version
+        let meta_json = serde_json::to_vec(&vault.metadata()).unwrap();
+        let mut v2_vault = assemble_file(VaultHeader::MAGIC, None, &salt, &nonce, &ciphertext);
+        v2_vault[4..8].copy_from_slice(&VaultHeader::FORMAT_VERSION_V2.to_le_bytes());
+        let block = [&(meta_json.len() as u32).to_le_bytes()[..], &meta_json].concat();
+        v2_vault.splice(8..8, block);
+        assert_eq!(parse_encrypted(&v2_vault).unwrap().kdf_algorithm, KdfAlgorithm::LEGACY);
+        assert!(opens(&v2_vault));
+    }
+
+    #[test]
+    fn test_explicit_argon2_variant_decrypts() {
+        use argon2::{Algorithm, Version};
+
+        // A vault written under a different default than today's
+        let other = KdfAlgorithm {
+            algorithm: Algorithm::Argon2i,
+            version: Version::V0x10,
+        };
+        let vault = test_vault();
+        let salt = kdf::generate_salt();
+        let nonce = cipher::generate_nonce();
+        let key = kdf::derive_key_with(
+            b"pw",
+            &salt,
+            kdf::DEFAULT_M_COST,
+            kdf::DEFAULT_T_COST,
+            kdf::DEFAULT_P_COST,
+            other,
+        )
+        .unwrap();
+        let ciphertext = cipher::encrypt(&key, &nonce, &serde_json::to_vec(&vault).unwrap()).unwrap();
+        let meta_json = serde_json::to_vec(&vault.metadata()).unwrap();
+        let data = assemble_file(VaultHeader::MAGIC, Some((&meta_json, other)), &salt, &nonce, &ciphertext);
+
+        let store = MemStore::default();
+        let path = Path::new("vault.ck");
+        store.write_bytes_atomic(path, &data).unwrap();
+        let loaded = read_encrypted_file(&store, b"pw", path, &[VaultHeader::MAGIC]).unwrap();
+        assert_eq!(loaded.entries[0].secret, "0xdeadbeef");
+        assert_eq!(read_vault_with_key(&key, &data).unwrap().entries.len(), 1);
+    }
+
+    #[test]
+    fn test_v1_vault_upgrades_on_save() {
+        let store = MemStore::default();
+        let path = Path::new("vault.ck");
+        let mut vault = test_vault();
+
+        // A vault written before the metadata block existed
+        let salt = kdf::generate_salt();
+        let nonce = cipher::generate_nonce();
+        let key = kdf::derive_key(
+            b"pw",
+            &salt,
+            kdf::DEFAULT_M_COST,
+            kdf::DEFAULT_T_COST,
+            kdf::DEFAULT_P_COST,
+        )
+        .unwrap();
+        let ciphertext = cipher::encrypt(&key, &nonce, &serde_json::to_vec(&vault).unwrap()).unwrap();
+        let data = assemble_file(VaultHeader::MAGIC, None, &salt, &nonce, &ciphertext);
+        store.write_bytes_atomic(path, &data).unwrap();
+        assert_eq!(outdated_version(&store, path), Some(VaultHeader::FORMAT_VERSION_V1));
+
+        let mut loaded = read_encrypted_file(&store, b"pw", path, &[VaultHeader::MAGIC]).unwrap();
+        let mut added = vault.entries.remove(0);
+        added.name = "Added".to_string();
+        loaded.entries.push(added);
+        let upgraded = write_vault_upgrading(&store, &loaded, b"pw", path).unwrap();
+        assert_eq!(upgraded, Some(VaultHeader::FORMAT_VERSION_V1));
+
+        let data = store.read_bytes(path).unwrap();
+        assert_eq!(&data[0..4], VaultHeader::MAGIC);
+        let version = u32::from_le_bytes(data[4..8].try_into().unwrap());
+        assert_eq!(version, VaultHeader::FORMAT_VERSION_V3);
+        let meta_len = u32::from_le_bytes(data[8..12].try_into().unwrap()) as usize;
+        let meta: Vec<EntryMeta> = serde_json::from_slice(&data[12..12 + meta_len]).unwrap();
+        let names: Vec<_> = meta.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["Test Key", "Added"]);
+
+        let reloaded = read_encrypted_file(&store, b"pw", path, &[VaultHeader::MAGIC]).unwrap();
+        assert_eq!(reloaded.entries.len(), 2);
+        assert_eq!(reloaded.entries[0].secret, "0xdeadbeef");
+        assert_eq!(reloaded.entries[1].name, "Added");
+
+        // Already current: nothing more to report
+        assert_eq!(write_vault_upgrading(&store, &reloaded, b"pw", path).unwrap(), None);
+    }
+
     #[test]
     fn test_vault_wrong_password() {
+        let store = MemStore::default();
+        let path = Path::new("vault.ck");
+        let vault = test_vault();
+
+        write_encrypted_file(&store, &vault, b"correct", path, VaultHeader::MAGIC).unwrap();
+        let result = read_encrypted_file(&store, b"wrong", path, &[VaultHeader::MAGIC]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_rewrites() {
         let dir = TempDir::new().unwrap();
         let path = dir.path().join("vault.ck");
         let vault = test_vault();
 
-        write_vault(&vault, b"correct", &path).unwrap();
-        let result = read_vault(b"wrong", &path);
-        assert!(result.is_err());
+        write_vault(&vault, b"pw", &path).unwrap();
+        let first = fingerprint(&path).unwrap();
+        assert_eq!(first, fingerprint(&path).unwrap());
+
+        // Re-encryption uses a fresh salt and nonce, so the bytes always change.
+        write_vault(&vault, b"pw", &path).unwrap();
+        assert_ne!(first, fingerprint(&path).unwrap());
     }
 
     #[test]
     fn test_backup_roundtrip() {
-        let dir = TempDir::new().unwrap();
-        let path = dir.path().join("backup.ck");
+        let store = MemStore::default();
+        let path = Path::new("backup.ck");
         let password = b"backup-pass";
         let vault = test_vault();
 
-        write_backup(&vault, password, &path).unwrap();
-        let loaded = read_backup(password, &path).unwrap();
+        write_encrypted_file(&store, &vault, password, path, BackupHeader::MAGIC).unwrap();
+        let loaded = read_encrypted_file(&store, password, path, &[BackupHeader::MAGIC]).unwrap();
 
         assert_eq!(loaded.entries.len(), 1);
         assert_eq!(loaded.entries[0].name, "Test Key");
     }
 
+    #[test]
+    fn test_armored_backup_roundtrip() {
+        let store = MemStore::default();
+        let path = Path::new("backup.txt");
+        let vault = test_vault();
+
+        let text = formats::armor(&backup_bytes(&vault, b"backup-pass").unwrap());
+        store.write_bytes_atomic(path, text.as_bytes()).unwrap();
+        let loaded = read_encrypted_file(&store, b"backup-pass", path, &[BackupHeader::MAGIC]).unwrap();
+
+        assert_eq!(loaded.entries.len(), 1);
+        assert_eq!(loaded.entries[0].secret, "0xdeadbeef");
+    }
+
     #[test]
     fn test_backup_wrong_magic() {
-        let dir = TempDir::new().unwrap();
-        let path = dir.path().join("backup.ck");
+        let store = MemStore::default();
+        let path = Path::new("backup.ck");
         let vault = test_vault();
 
         // Write as vault, try to read as backup
-        write_vault(&vault, b"pass", &path).unwrap();
-        let result = read_backup(b"pass", &path);
+        write_encrypted_file(&store, &vault, b"pass", path, VaultHeader::MAGIC).unwrap();
+        let result = read_encrypted_file(&store, b"pass", path, &[BackupHeader::MAGIC]);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_read_any_encrypted_accepts_both() {
+        let dir = TempDir::new().unwrap();
+        let vault_file = dir.path().join("vault.ck");
+        let backup_file = dir.path().join("backup.ck");
+        let vault = test_vault();
+
+        write_vault(&vault, b"pass", &vault_file).unwrap();
+        write_backup(&vault, b"pass", &backup_file).unwrap();
+
+        assert_eq!(read_any_encrypted(b"pass", &vault_file).unwrap().entries.len(), 1);
+        assert_eq!(read_any_encrypted(b"pass", &backup_file).unwrap().entries.len(), 1);
+    }
+
+    #[test]
+    fn test_read_any_encrypted_finds_armor_inside_a_note() {
+        let dir = TempDir::new().unwrap();
+        let backup_file = dir.path().join("backup.ck");
+        let note_file = dir.path().join("note.txt");
+        write_backup(&test_vault(), b"pass", &backup_file).unwrap();
+
+        let block = formats::armor(&fs::read(&backup_file).unwrap());
+        let note = format!("Vault backup, March\nkeep offline\n\n{}\n-- end\n", block);
+        fs::write(&note_file, note).unwrap();
+
+        assert_eq!(read_any_encrypted(b"pass", &note_file).unwrap().entries.len(), 1);
+    }
+
+    #[test]
+    fn test_check_dir_writable() {
+        let dir = TempDir::new().unwrap();
+        let nested = dir.path().join("new").join("vault");
+        check_dir_writable(&nested).unwrap();
+        assert!(nested.is_dir());
+        assert_eq!(fs::read_dir(&nested).unwrap().count(), 0);
+
+        // A regular file where the directory should be
+        let file = dir.path().join("file");
+        fs::write(&file, b"x").unwrap();
+        assert!(matches!(
+            check_dir_writable(&file.join("vault")),
+            Err(CryptoKeeperError::VaultDirNotWritable(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_dir_order() {
+        let dir = TempDir::new().unwrap();
+        let legacy = dir.path().join(".cryptokeeper");
+        let xdg = dir.path().join("share/cryptokeeper");
+        let custom = dir.path().join("custom");
+
+        assert_eq!(
+            resolve_dir(Some(custom.clone()), legacy.clone(), Some(xdg.clone())),
+            custom
+        );
+        assert_eq!(resolve_dir(None, legacy.clone(), Some(xdg.clone())), xdg);
+        assert_eq!(resolve_dir(None, legacy.clone(), None), legacy);
+
+        // An existing legacy directory keeps being used
+        fs::create_dir(&legacy).unwrap();
+        assert_eq!(resolve_dir(None, legacy.clone(), Some(xdg)), legacy);
+    }
+
+    #[test]
+    fn test_cancelled_unlock_result_is_dropped() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        struct Secret(Rc<Cell<bool>>);
+        impl Drop for Secret {
+            fn drop(&mut self) {
+                self.0.set(true);
+            }
+        }
+
+        let dropped = Rc::new(Cell::new(false));
+        let (tx, rx) = mpsc::channel();
+        drop(rx);
+        send_or_discard(&tx, Ok(Secret(dropped.clone())));
+        assert!(dropped.get());
+    }
+
+    #[test]
+    fn test_fast_unlock_warning() {
+        assert!(fast_unlock_warning(Duration::from_millis(40), 100).is_some());
+        assert!(fast_unlock_warning(Duration::from_millis(100), 100).is_none());
+        assert!(fast_unlock_warning(Duration::from_millis(40), 0).is_none());
+    }
+
     #[test]
     fn test_corrupted_file() {
         let dir = TempDir::new().unwrap();
         let path = dir.path().join("vault.ck");
         fs::write(&path, b"too short").unwrap();
-        let result = read_vault(b"pass", &path);
+        let result = read_vault_timed(b"pass", &path);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_orphaned_temp_found_and_restored() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("vault.ck");
+        assert!(find_orphaned_temp(&path).is_none());
+
+        // Killed mid-save, with the vault itself damaged
+        write_vault(&test_vault(), b"pass", &temp_path(&path)).unwrap();
+        fs::write(&path, b"garbage").unwrap();
+        let orphan = find_orphaned_temp(&path).unwrap();
+        assert_eq!((orphan.temp_entries, orphan.vault_entries), (Some(1), None));
+
+        let replaced = orphan.restore().unwrap().unwrap();
+        assert_eq!(fs::read(&replaced).unwrap(), b"garbage");
+        assert_eq!(read_vault_timed(b"pass", &path).unwrap().0.entries.len(), 1);
+        assert!(find_orphaned_temp(&path).is_none());
+
+        // A second restore keeps the earlier damaged copy too
+        write_vault(&test_vault(), b"pass", &temp_path(&path)).unwrap();
+        fs::write(&path, b"more garbage").unwrap();
+        let again = find_orphaned_temp(&path).unwrap().restore().unwrap().unwrap();
+        assert_ne!(again, replaced);
+        assert_eq!(fs::read(&replaced).unwrap(), b"garbage");
+        assert_eq!(fs::read(&again).unwrap(), b"more garbage");
+
+        // A truncated leftover next to a good vault is just discarded
+        fs::write(temp_path(&path), b"CKV").unwrap();
+        let orphan = find_orphaned_temp(&path).unwrap();
+        assert_eq!((orphan.temp_entries, orphan.vault_entries), (None, Some(1)));
+        orphan.discard().unwrap();
+        assert!(find_orphaned_temp(&path).is_none());
+    }
+
+    #[test]
+    fn test_vault_size_limit() {
+        assert!(check_vault_size(1024 * 1024, 1).is_ok());
+        assert!(matches!(
+            check_vault_size(3 * 1024 * 1024 + 1, 2),
+            Err(CryptoKeeperError::VaultTooLarge(4, 2))
+        ));
+    }
+
+    #[test]
+    fn test_bad_plaintext_names_field_without_value() {
+        let json = serde_json::to_string(&test_vault()).unwrap();
+
+        let wrong_type = json.replace("\"notes\":\"Test note\"", "\"notes\":12345");
+        let err = parse_vault_json(wrong_type.as_bytes()).unwrap_err().to_string();
+        assert!(err.contains("field 'notes'"), "{}", err);
+        assert!(!err.contains("12345"));
+
+        let mut not_utf8 = json.into_bytes();
+        let at = not_utf8.windows(4).position(|w| w == b"Test").unwrap();
+        not_utf8[at] = 0xff;
+        let err = parse_vault_json(&not_utf8).unwrap_err().to_string();
+        assert!(err.contains("not UTF-8 in field 'name'"), "{}", err);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_loose_permissions_found_and_fixed() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let vault_dir = dir.path().join("vault");
+        let path = vault_dir.join("vault.ck");
+        ensure_private_dir(&vault_dir).unwrap();
+        FileStore.write_bytes_atomic(&path, b"data").unwrap();
+        assert!(loose_permissions(Some(&vault_dir), &path).is_empty());
+
+        fs::set_permissions(&vault_dir, fs::Permissions::from_mode(0o755)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let loose = loose_permissions(Some(&vault_dir), &path);
+        assert_eq!(loose.len(), 2);
+        assert_eq!((loose[0].mode, loose[0].wanted_mode()), (0o755, 0o700));
+        assert_eq!((loose[1].mode, loose[1].wanted_mode()), (0o644, 0o600));
+
+        // A folder that is not CryptoKeeper's own is left alone
+        let loose = loose_permissions(None, &path);
+        assert_eq!(loose.len(), 1);
+        assert!(!loose[0].is_dir);
+
+        for item in &loose_permissions(Some(&vault_dir), &path) {
+            item.fix().unwrap();
+        }
+        assert!(loose_permissions(Some(&vault_dir), &path).is_empty());
+    }
 }
@@ -0,0 +1,123 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+
+/// Where encrypted vault and backup bytes live. The format and encryption code
+/// in `storage` only sees byte buffers, so a remote backend can slot in here.
+pub trait VaultStore {
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>>;
+
+    /// Replace the contents at `path` so readers see either the old or the new
+    /// bytes, never a partial write.
+    fn write_bytes_atomic(&self, path: &Path, data: &[u8]) -> Result<()>;
+
+    fn exists(&self, path: &Path) -> bool;
+}
+
+/// Local filesystem store: writes go to a `.tmp` sibling, get owner-only
+/// permissions, then are renamed over the target.
+pub struct FileStore;
+
+impl VaultStore for FileStore {
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        Ok(fs::read(path)?)
+    }
+
+    fn write_bytes_atomic(&self, path: &Path, data: &[u8]) -> Result<()> {
+        let temp_path = temp_path(path);
+        fs::write(&temp_path, data)?;
+        set_file_permissions(&temp_path)?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+}
+
+/// Where `FileStore` stages a write to `path` before renaming it into place.
+pub(crate) fn temp_path(path: &Path) -> PathBuf {
+    path.with_extension("tmp")
+}
+
+/// Restrict `path` to its owner (0o600).
+#[cfg(unix)]
+pub(crate) fn set_file_permissions(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn set_file_permissions(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// In-memory store for exercising the vault format without touching disk.
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemStore {
+    files: std::cell::RefCell<std::collections::HashMap<std::path::PathBuf, Vec<u8>>>,
+}
+
+#[cfg(test)]
+impl VaultStore for MemStore {
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        self.files.borrow().get(path).cloned().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, path.display().to_string()).into()
+        })
+    }
+
+    fn write_bytes_atomic(&self, path: &Path, data: &[u8]) -> Result<()> {
+        self.files.borrow_mut().insert(path.to_path_buf(), data.to_vec());
+        Ok(())
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.files.borrow().contains_key(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn mem_store_roundtrip() {
+        let store = MemStore::default();
+        let path = Path::new("vault.ck");
+        assert!(!store.exists(path));
+        assert!(store.read_bytes(path).is_err());
+
+        store.write_bytes_atomic(path, b"first").unwrap();
+        store.write_bytes_atomic(path, b"second").unwrap();
+        assert!(store.exists(path));
+        assert_eq!(store.read_bytes(path).unwrap(), b"second");
+    }
+
+    #[test]
+    fn file_store_replaces_without_leftovers() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("vault.ck");
+
+        FileStore.write_bytes_atomic(&path, b"first").unwrap();
+        FileStore.write_bytes_atomic(&path, b"second").unwrap();
+        assert_eq!(FileStore.read_bytes(&path).unwrap(), b"second");
+        assert!(!temp_path(&path).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_store_writes_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("vault.ck");
+        FileStore.write_bytes_atomic(&path, b"data").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}